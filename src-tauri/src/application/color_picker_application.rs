@@ -1,10 +1,13 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
 use tauri::State;
 
 use crate::{
-    domain::color_picker_domain::ColorPickerStore,
+    domain::{color_picker_domain::ColorPickerStore, import_domain::SwatchFormat},
+    import::swatches_import,
     repository::{
-        color_picker_repository,
+        self, color_picker_repository,
         undo_repository::{self, UndoRedoActions},
     },
     AppState,
@@ -18,23 +21,50 @@ pub fn save_color_picker_store(
     state: &State<AppState>,
     color_store: &ColorPickerStore,
 ) -> Result<()> {
-    color_picker_repository::save_color_picker_store(&state, color_store)?;
+    color_picker_repository::save_color_picker_store(state, color_store)?;
     do_color_picker(state, color_store)
 }
 
+/// Add a sample per group of the swatch file (`.ase`, `.aco`, `.gpl`, `.sketchpalette`)
+pub fn import_swatches_into_color_picker(
+    state: &State<AppState>,
+    path: &PathBuf,
+) -> Result<ColorPickerStore> {
+    let format = SwatchFormat::from_path(path)?;
+    let file_name = path
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or("swatches");
+    let groups = swatches_import::read_swatches(&format, &repository::read_file(path)?, file_name)?;
+    let mut color_store = fetch_color_picker_store(state)?;
+    let samples = swatches_import::to_color_samples(&groups, &color_store.samples);
+    color_store.samples.extend(samples);
+    save_color_picker_store(state, &color_store)?;
+    Ok(color_store)
+}
+
+/// Undo / redo history of the color picker store file
+fn color_picker_history_id(state: &State<AppState>) -> String {
+    undo_repository::tool_history_id("color-picker", Some(&state.color_picker_path))
+}
+
 pub fn do_color_picker(state: &State<AppState>, color_picker: &ColorPickerStore) -> Result<()> {
-    undo_repository::set_new::<ColorPickerStore>(&state, &"color-picker-undoredo", &color_picker)?;
+    undo_repository::set_new::<ColorPickerStore>(
+        state,
+        &color_picker_history_id(state),
+        color_picker,
+    )?;
     Ok(())
 }
 
 pub fn undo_color_picker(state: State<AppState>) -> Result<ColorPickerStore> {
-    undo_repository::undo::<ColorPickerStore>(&state, &"color-picker-undoredo")
+    undo_repository::undo::<ColorPickerStore>(&state, &color_picker_history_id(&state))
 }
 
 pub fn redo_color_picker(state: State<AppState>) -> Result<ColorPickerStore> {
-    undo_repository::redo::<ColorPickerStore>(&state, &"color-picker-undoredo")
+    undo_repository::redo::<ColorPickerStore>(&state, &color_picker_history_id(&state))
 }
 
 pub fn can_undo_redo_color_picker(state: State<AppState>) -> Result<UndoRedoActions> {
-    undo_repository::can_undo_redo::<ColorPickerStore>(&state, &"color-picker-undoredo")
+    undo_repository::can_undo_redo::<ColorPickerStore>(&state, &color_picker_history_id(&state))
 }
@@ -1,22 +1,35 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
 use tauri::{AppHandle, State};
 
 use crate::{
     application::home_application::fetch_presets_dressing,
+    audit, changelog, diff,
     domain::{
+        audit_domain::ContrastAudit,
+        changelog_domain::{Changelog, ChangelogEntry},
+        color_vision_domain::ColorVisionSimulation,
         design_system_domain::{
             DesignSystem, DesignSystemCreationPayload, DesignSystemMetadata, ExportPayload,
-            ExportsMetadata, Fonts, IndependantColors, Palette, Radius, SemanticColorTokens,
-            Shadows, Space, Typographies,
+            ExportsMetadata, Fonts, IndependantColors, Palette, PaletteRenamePayload,
+            PaletteRenameReport, Radius, SemanticColorTokens, Shadows, Space, Typographies,
         },
+        diff_domain::DesignSystemDiff,
+        export_domain::{ExportFile, ExportFormat},
         home_domain::PresetDressing,
+        import_domain::{DesignSystemImportPayload, ImportReport},
+        lint_domain::LintReport,
+        palette_builder_domain::PaletteBuilderFile,
+        reference_domain::{self, resolve_references, restore_references},
+        version_domain::DesignSystemVersion,
     },
+    export, import, lint,
     repository::{
         self, assert_file_in_directory, compute_path,
         design_system_repository::{self},
         fonts_repository::{self, load_design_system_fonts},
+        palette_builder_repository,
         undo_repository::{self, UndoRedoActions},
     },
     utils::generate_uuid,
@@ -49,6 +62,14 @@ pub fn create_design_system(payload: DesignSystemCreationPayload) -> Result<Desi
         exports: ExportsMetadata {
             css: None,
             figma_token_studio: None,
+            dtcg: None,
+            style_dictionary: None,
+            scss: None,
+            less: None,
+            tailwind: None,
+            android: None,
+            ios: None,
+            swatches: None,
             readme: None,
         },
         update_date: String::new(),
@@ -63,12 +84,13 @@ pub fn find_design_system(
     state: &State<AppState>,
     design_system_path: &String,
 ) -> Result<DesignSystem> {
-    println!("find design system");
+    eprintln!("find design system");
     let design_system_pathbuf: PathBuf = PathBuf::from(design_system_path);
-    design_system_repository::remove_empty_temp(&design_system_pathbuf)?;
+    let mut stored_design_system: DesignSystem = load_stored_design_system(&design_system_pathbuf)?;
+    let mut design_system: DesignSystem = stored_design_system.clone();
+    resolve_references(&mut design_system);
     let fetch_path = repository::compute_fetch_pathbuf(&design_system_pathbuf);
-    let mut metadata: DesignSystemMetadata =
-        design_system_repository::find_design_system_metadata(&design_system_pathbuf)?;
+    let metadata: &mut DesignSystemMetadata = &mut design_system.metadata;
 
     //Verify banner & logo
     let images_pathbuf: PathBuf = design_system_repository::get_images_path(&design_system_pathbuf);
@@ -77,7 +99,7 @@ pub fn find_design_system(
             if !PathBuf::from(&metadata.banner).is_file() {
                 let images: PresetDressing = fetch_presets_dressing(app.clone())?;
                 metadata.banner = images.banners[0].clone();
-                design_system_repository::save_metadata(&fetch_path.fetch_pathbuf, &metadata)?;
+                design_system_repository::save_metadata(&fetch_path.fetch_pathbuf, metadata)?;
             }
         }
         Ok(_) => {
@@ -89,7 +111,7 @@ pub fn find_design_system(
             if !PathBuf::from(&metadata.logo).is_file() {
                 let images: PresetDressing = fetch_presets_dressing(app)?;
                 metadata.logo = images.logos[0].clone();
-                design_system_repository::save_metadata(&fetch_path.fetch_pathbuf, &metadata)?;
+                design_system_repository::save_metadata(&fetch_path.fetch_pathbuf, metadata)?;
             }
         }
         Ok(_) => {
@@ -97,84 +119,136 @@ pub fn find_design_system(
         }
     };
 
+    stored_design_system.metadata = design_system.metadata.clone();
+    sync_design_system_history(state, design_system_path, &stored_design_system)?;
     let UndoRedoActions { can_redo, can_undo } =
-        undo_repository::can_undo_redo::<DesignSystem>(&state, design_system_path)?;
-    metadata.can_redo = can_redo;
-    metadata.can_undo = can_undo;
+        undo_repository::can_undo_redo::<DesignSystem>(state, design_system_path)?;
+    design_system.metadata.can_redo = can_redo;
+    design_system.metadata.can_undo = can_undo;
+
+    Ok(design_system)
+}
+
+/// The history is kept between the sessions, the design system may have been edited outside of
+/// uitea since (ex: git pull) : the design system on disk becomes a new entry of the history. The
+/// entries are stored design systems, with the color references
+fn sync_design_system_history(
+    state: &State<AppState>,
+    design_system_path: &str,
+    stored_design_system: &DesignSystem,
+) -> Result<()> {
+    if let Ok(mut present) = undo_repository::get_present::<DesignSystem>(state, design_system_path)
+    {
+        present.metadata = stored_design_system.metadata.clone();
+        if serde_json::to_value(&present)? != serde_json::to_value(stored_design_system)? {
+            undo_repository::set_new::<DesignSystem>(
+                state,
+                design_system_path,
+                stored_design_system,
+            )?;
+        }
+    }
+    Ok(())
+}
 
-    let fonts = load_design_system_fonts(&design_system_pathbuf)?;
+/// Load a design system from its folder (the tmp copy when there are unsaved changes), without any
+/// dependency to the tauri app. An interrupted save is recovered and the missing files are created.
+/// The color references are resolved
+pub fn load_design_system(design_system_pathbuf: &PathBuf) -> Result<DesignSystem> {
+    let mut design_system: DesignSystem = load_stored_design_system(design_system_pathbuf)?;
+    resolve_references(&mut design_system);
+    Ok(design_system)
+}
+
+/// Read a design system saved in `design_system_pathbuf` (never its tmp copy) without writing
+/// anything, used by the cli & the reports. The color references are resolved
+pub fn read_design_system(design_system_pathbuf: &Path) -> Result<DesignSystem> {
+    let mut design_system: DesignSystem =
+        design_system_repository::read_saved_design_system(design_system_pathbuf)?;
+    resolve_references(&mut design_system);
+    Ok(design_system)
+}
+
+/// Design system as written in its folder, with the color references
+fn load_stored_design_system(design_system_pathbuf: &PathBuf) -> Result<DesignSystem> {
+    design_system_repository::recover_interrupted_save(design_system_pathbuf)?;
+    design_system_repository::remove_empty_temp(design_system_pathbuf)?;
+    let mut metadata: DesignSystemMetadata =
+        design_system_repository::find_design_system_metadata(design_system_pathbuf)?;
+
+    let fonts = load_design_system_fonts(design_system_pathbuf)?;
     metadata.fonts = fonts;
 
     let palettes: Vec<Palette> =
-        match design_system_repository::fetch_palettes(&design_system_pathbuf) {
+        match design_system_repository::fetch_palettes(design_system_pathbuf) {
             Err(_) => {
-                design_system_repository::init_palettes(&design_system_pathbuf)?;
-                design_system_repository::fetch_palettes(&design_system_pathbuf)
+                design_system_repository::init_palettes(design_system_pathbuf)?;
+                design_system_repository::fetch_palettes(design_system_pathbuf)
             }
             Ok(colors) => Ok(colors),
         }?;
 
-    let themes = design_system_repository::fetch_themes(&design_system_pathbuf);
+    let themes = design_system_repository::fetch_themes(design_system_pathbuf);
 
     let semantic_color_tokens: SemanticColorTokens =
-        match design_system_repository::fetch_semantic_color_tokens(&design_system_pathbuf) {
+        match design_system_repository::fetch_semantic_color_tokens(design_system_pathbuf) {
             Err(_) => {
-                design_system_repository::init_semantic_color_tokens(&design_system_pathbuf)?;
-                design_system_repository::fetch_semantic_color_tokens(&design_system_pathbuf)
+                design_system_repository::init_semantic_color_tokens(design_system_pathbuf)?;
+                design_system_repository::fetch_semantic_color_tokens(design_system_pathbuf)
             }
             Ok(tokens) => Ok(tokens),
         }?;
 
     let independant_colors: IndependantColors =
-        match design_system_repository::fetch_independant_colors(&design_system_pathbuf) {
+        match design_system_repository::fetch_independant_colors(design_system_pathbuf) {
             Err(_) => {
-                design_system_repository::init_independant_colors(&design_system_pathbuf)?;
-                design_system_repository::fetch_independant_colors(&design_system_pathbuf)
+                design_system_repository::init_independant_colors(design_system_pathbuf)?;
+                design_system_repository::fetch_independant_colors(design_system_pathbuf)
             }
             Ok(independant_colors) => Ok(independant_colors),
         }?;
 
-    let fonts: Fonts = match design_system_repository::fetch_fonts(&design_system_pathbuf) {
+    let fonts: Fonts = match design_system_repository::fetch_fonts(design_system_pathbuf) {
         Err(_) => {
-            design_system_repository::init_fonts(&design_system_pathbuf)?;
-            design_system_repository::fetch_fonts(&design_system_pathbuf)
+            design_system_repository::init_fonts(design_system_pathbuf)?;
+            design_system_repository::fetch_fonts(design_system_pathbuf)
         }
         Ok(font) => Ok(font),
     }?;
 
     let typography: Typographies =
-        match design_system_repository::fetch_typography(&design_system_pathbuf) {
+        match design_system_repository::fetch_typography(design_system_pathbuf) {
             Err(_) => {
-                design_system_repository::init_typography(&design_system_pathbuf)?;
-                design_system_repository::fetch_typography(&design_system_pathbuf)
+                design_system_repository::init_typography(design_system_pathbuf)?;
+                design_system_repository::fetch_typography(design_system_pathbuf)
             }
             Ok(typo) => Ok(typo),
         }?;
 
-    let spaces: Vec<Space> = match design_system_repository::fetch_spaces(&design_system_pathbuf) {
+    let spaces: Vec<Space> = match design_system_repository::fetch_spaces(design_system_pathbuf) {
         Err(_) => {
-            design_system_repository::init_spaces(&design_system_pathbuf)?;
-            design_system_repository::fetch_spaces(&design_system_pathbuf)
+            design_system_repository::init_spaces(design_system_pathbuf)?;
+            design_system_repository::fetch_spaces(design_system_pathbuf)
         }
         Ok(space) => Ok(space),
     }?;
 
-    let radius: Radius = match design_system_repository::fetch_radius(&design_system_pathbuf) {
+    let radius: Radius = match design_system_repository::fetch_radius(design_system_pathbuf) {
         Err(_) => {
-            design_system_repository::init_radius(&design_system_pathbuf)?;
-            design_system_repository::fetch_radius(&design_system_pathbuf)
+            design_system_repository::init_radius(design_system_pathbuf)?;
+            design_system_repository::fetch_radius(design_system_pathbuf)
         }
         Ok(radius) => Ok(radius),
     }?;
 
-    let effects: Vec<Shadows> =
-        match design_system_repository::fetch_effects(&design_system_pathbuf) {
-            Err(_) => {
-                design_system_repository::init_effects(&design_system_pathbuf)?;
-                design_system_repository::fetch_effects(&design_system_pathbuf)
-            }
-            Ok(radius) => Ok(radius),
-        }?;
+    let effects: Vec<Shadows> = match design_system_repository::fetch_effects(design_system_pathbuf)
+    {
+        Err(_) => {
+            design_system_repository::init_effects(design_system_pathbuf)?;
+            design_system_repository::fetch_effects(design_system_pathbuf)
+        }
+        Ok(radius) => Ok(radius),
+    }?;
 
     Ok(DesignSystem {
         metadata,
@@ -197,20 +271,41 @@ pub fn save_design_system(
     is_tmp: bool,
     new_historic_entry: bool,
 ) -> Result<DesignSystem> {
-    println!("save design system");
+    eprintln!("save design system");
+    let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+    // The webview edits the resolved design system
+    if let Ok(stored_design_system) = load_stored_design_system(&design_system_path) {
+        restore_references(design_system, &stored_design_system);
+    }
+    save_stored_design_system(app, state, design_system, is_tmp, new_historic_entry)
+}
+
+/// Save a design system with its color references (as stored in the folder and in the undo
+/// history), the returned design system is resolved
+fn save_stored_design_system(
+    app: AppHandle,
+    state: &State<AppState>,
+    design_system: &DesignSystem,
+    is_tmp: bool,
+    new_historic_entry: bool,
+) -> Result<DesignSystem> {
     let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+    design_system_repository::recover_interrupted_save(&design_system_path)?;
     design_system_repository::remove_empty_temp(&design_system_path)?;
     let design_system_string_path = design_system_path
         .to_str()
         .ok_or_else(|| anyhow!("fail to find db path"))?;
-    match undo_repository::get_present::<DesignSystem>(&state, design_system_string_path) {
+    match undo_repository::get_present::<DesignSystem>(state, design_system_string_path) {
         Ok(_) => {}
         Err(_) => {
-            println!("Fail to load present_state, try to write it.");
-            let initial_design_system: DesignSystem =
-                find_design_system(app, &state, &String::from(design_system_string_path))?;
+            eprintln!("Fail to load present_state, try to write it.");
+            // The design system before this save
+            let mut initial_design_system: DesignSystem =
+                load_stored_design_system(&design_system_path)?;
+            initial_design_system.metadata =
+                find_design_system(app, state, &String::from(design_system_string_path))?.metadata;
             undo_repository::set_new::<DesignSystem>(
-                &state,
+                state,
                 design_system_string_path,
                 &initial_design_system,
             )?;
@@ -219,11 +314,7 @@ pub fn save_design_system(
     let mut design_system = design_system.clone();
     design_system_repository::save_design_system(&mut design_system, is_tmp)?;
     if new_historic_entry && is_tmp {
-        undo_repository::set_new::<DesignSystem>(
-            &state,
-            design_system_string_path,
-            &design_system,
-        )?;
+        undo_repository::set_new::<DesignSystem>(state, design_system_string_path, &design_system)?;
     }
 
     if !is_tmp {
@@ -233,43 +324,282 @@ pub fn save_design_system(
     }
 
     let can_undo_redo: UndoRedoActions =
-        undo_repository::can_undo_redo::<DesignSystem>(&state, &design_system_string_path)?;
+        undo_repository::can_undo_redo::<DesignSystem>(state, design_system_string_path)?;
     design_system.metadata.can_redo = can_undo_redo.can_redo;
     design_system.metadata.can_undo = can_undo_redo.can_undo;
     let update_date: String =
         design_system_repository::get_design_system_update_date(&design_system_path)?;
     design_system.metadata.update_date = update_date;
+    resolve_references(&mut design_system);
     Ok(design_system)
 }
 
 pub fn undo_design_system(
     app: AppHandle,
     state: &State<AppState>,
-    design_system_path: &String,
+    design_system_path: &str,
 ) -> Result<()> {
-    println!("undo design system");
-    let mut design_system: DesignSystem =
+    eprintln!("undo design system");
+    let design_system: DesignSystem =
         undo_repository::undo::<DesignSystem>(state, design_system_path)?;
-    println!("undo : {:?}", design_system);
-    save_design_system(app, &state, &mut design_system, true, false)?;
-    println!("undo success");
+    save_stored_design_system(app, state, &design_system, true, false)?;
+    eprintln!("undo success");
     Ok(())
 }
 
 pub fn redo_design_system(
     app: AppHandle,
     state: &State<AppState>,
-    design_system_path: &String,
+    design_system_path: &str,
 ) -> Result<()> {
-    println!("redo design system");
-    let mut design_system: DesignSystem =
+    eprintln!("redo design system");
+    let design_system: DesignSystem =
         undo_repository::redo::<DesignSystem>(state, design_system_path)?;
-    println!("redo : {:?}", design_system);
-    save_design_system(app, &state, &mut design_system, true, false)?;
-    println!("redo success");
+    save_stored_design_system(app, state, &design_system, true, false)?;
+    eprintln!("redo success");
     Ok(())
 }
 
+/// Rename a palette (or one of its tints) with every reference to it : semantic tokens, themes,
+/// typography & shadow colors, independant colors and palette builder files of the design system.
+/// The palette builder files are not part of the tmp copy : the rename is a full save (with the
+/// unsaved changes), and the files are written back if the design system can not be saved
+pub fn rename_palette(
+    app: AppHandle,
+    state: &State<AppState>,
+    payload: PaletteRenamePayload,
+) -> Result<PaletteRenameReport> {
+    let PaletteRenamePayload {
+        mut design_system,
+        palette_name,
+        tint_label,
+        new_name,
+    } = payload;
+    let new_name: &str = new_name.trim();
+    let design_system_pathbuf: PathBuf = design_system.metadata.design_system_path.clone();
+
+    // The references of the webview are resolved, rename the stored ones
+    if let Ok(stored_design_system) = load_stored_design_system(&design_system_pathbuf) {
+        restore_references(&mut design_system, &stored_design_system);
+    }
+    let renamed_references = match &tint_label {
+        None => reference_domain::rename_palette(&mut design_system, &palette_name, new_name)?,
+        Some(tint_label) => {
+            reference_domain::rename_tint(&mut design_system, &palette_name, tint_label, new_name)?
+        }
+    };
+
+    let palette_builders: Vec<(PathBuf, PaletteBuilderFile, PaletteBuilderFile)> =
+        palette_builder_repository::fetch_design_system_palette_builder_files(
+            &design_system_pathbuf,
+        )?
+        .into_iter()
+        .filter_map(|(path, original)| {
+            let mut palette_builder = original.clone();
+            palette_builder
+                .rename_palette(&palette_name, tint_label.as_deref(), new_name)
+                .then(|| (path, original, palette_builder))
+        })
+        .collect();
+    let design_system =
+        palette_builder_repository::save_renamed_palette_builder_files(&palette_builders, || {
+            save_design_system(app, state, &mut design_system, false, true)
+        })?;
+
+    Ok(PaletteRenameReport {
+        design_system,
+        renamed_references,
+        palette_builder_paths: palette_builders
+            .into_iter()
+            .map(|(path, _, _)| path)
+            .collect(),
+    })
+}
+
+/// Snapshot the saved design system as a named version
+pub fn create_design_system_version(
+    design_system_pathbuf: PathBuf,
+    version_name: &str,
+    description: Option<String>,
+) -> Result<DesignSystemVersion> {
+    let version_name: &str = version_name.trim();
+    let description: Option<String> = description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    let version: DesignSystemVersion = design_system_repository::create_version(
+        &design_system_pathbuf,
+        version_name,
+        description,
+    )?;
+    generate_design_system_changelog(design_system_pathbuf)?;
+    Ok(version)
+}
+
+pub fn fetch_design_system_versions(
+    design_system_pathbuf: PathBuf,
+) -> Result<Vec<DesignSystemVersion>> {
+    design_system_repository::fetch_versions(&design_system_pathbuf)
+}
+
+/// Token changes from a version to another one, or to the saved design system without
+/// `to_version`. Only the saved files are read
+pub fn diff_design_system_versions(
+    design_system_pathbuf: PathBuf,
+    from_version: &str,
+    to_version: Option<&str>,
+) -> Result<DesignSystemDiff> {
+    let previous: DesignSystem = read_design_system(&design_system_repository::get_version_path(
+        &design_system_pathbuf,
+        from_version,
+    )?)?;
+    let current: DesignSystem = match to_version {
+        Some(to_version) => read_design_system(&design_system_repository::get_version_path(
+            &design_system_pathbuf,
+            to_version,
+        )?)?,
+        None => read_design_system(&design_system_pathbuf)?,
+    };
+    Ok(diff::diff_design_systems(&previous, &current))
+}
+
+/// Token changes between two design system folders (a version folder is a design system)
+pub fn diff_design_systems(
+    previous_design_system_pathbuf: PathBuf,
+    design_system_pathbuf: PathBuf,
+) -> Result<DesignSystemDiff> {
+    let previous: DesignSystem = read_design_system(&previous_design_system_pathbuf)?;
+    let current: DesignSystem = read_design_system(&design_system_pathbuf)?;
+    Ok(diff::diff_design_systems(&previous, &current))
+}
+
+/// Changes of each version since the previous one, and of the saved design system since the last
+/// version, written to the `CHANGELOG.md` of the design system. Only the saved files are read
+pub fn generate_design_system_changelog(design_system_pathbuf: PathBuf) -> Result<Changelog> {
+    let current: DesignSystem = read_design_system(&design_system_pathbuf)?;
+    let mut entries: Vec<ChangelogEntry> = vec![];
+    let mut previous: Option<(String, DesignSystem)> = None;
+    for version in design_system_repository::fetch_versions(&design_system_pathbuf)? {
+        let version_name: String = version.name.clone();
+        let design_system: DesignSystem = read_design_system(
+            &design_system_repository::get_version_path(&design_system_pathbuf, &version_name)?,
+        )?;
+        entries.push(changelog::changelog_entry(
+            Some(version),
+            previous
+                .as_ref()
+                .map(|(name, previous)| (name.as_str(), previous)),
+            &design_system,
+        ));
+        previous = Some((version_name, design_system));
+    }
+    if let Some((previous_name, previous)) = &previous {
+        let unreleased: ChangelogEntry =
+            changelog::changelog_entry(None, Some((previous_name, previous)), &current);
+        if unreleased.bump.is_some() {
+            entries.push(unreleased);
+        }
+    }
+    entries.reverse();
+    let changelog = Changelog { entries };
+
+    design_system_repository::save_changelog(
+        &design_system_pathbuf,
+        &changelog::changelog_markdown(&current.metadata.design_system_name, &changelog),
+    )?;
+    Ok(changelog)
+}
+
+/// Replace the design system by one of its versions, the restored design system is a new entry of
+/// the undo history
+pub fn restore_design_system_version(
+    app: AppHandle,
+    state: &State<AppState>,
+    design_system_path: &String,
+    version_name: &str,
+) -> Result<DesignSystem> {
+    let design_system_pathbuf: PathBuf = PathBuf::from(design_system_path);
+    design_system_repository::restore_version(&design_system_pathbuf, version_name)?;
+    let restored_design_system: DesignSystem = load_stored_design_system(&design_system_pathbuf)?;
+    undo_repository::set_new::<DesignSystem>(state, design_system_path, &restored_design_system)?;
+    find_design_system(app, state, design_system_path)
+}
+
+/// Generate an export of the design system into `out_pathbuf` (default : the exports folder), from
+/// the files saved on disk or with the unsaved changes of the tmp copy (`is_tmp`)
+pub fn generate_export(
+    design_system_pathbuf: &Path,
+    format: &ExportFormat,
+    out_pathbuf: Option<PathBuf>,
+    is_tmp: bool,
+) -> Result<Vec<PathBuf>> {
+    let design_system: DesignSystem = if is_tmp {
+        read_design_system(&repository::compute_fetch_pathbuf(design_system_pathbuf).fetch_pathbuf)?
+    } else {
+        read_design_system(design_system_pathbuf)?
+    };
+    let export_files: Vec<ExportFile> = export::generate_export(&design_system, format)?;
+    let export_pathbuf: PathBuf = out_pathbuf
+        .unwrap_or_else(|| design_system_repository::get_exports_path(design_system_pathbuf));
+    design_system_repository::write_export_files(&export_pathbuf, &export_files)
+}
+
+/// WCAG & APCA contrast of the semantic color tokens, for every theme (unsaved changes included)
+pub fn audit_contrast(design_system_pathbuf: PathBuf) -> Result<ContrastAudit> {
+    let design_system: DesignSystem = read_design_system(
+        &repository::compute_fetch_pathbuf(&design_system_pathbuf).fetch_pathbuf,
+    )?;
+    audit::audit_contrast(&design_system)
+}
+
+/// Palettes & color combinations as seen with the color vision deficiencies, from the edited design
+/// system (token crafter)
+pub fn simulate_color_vision(design_system: &DesignSystem) -> Result<Vec<ColorVisionSimulation>> {
+    Ok(audit::simulate_color_vision(design_system))
+}
+
+/// Issues of the design system (unsaved changes included), with the rule levels of its `lint.yaml`
+pub fn lint_design_system(design_system_pathbuf: PathBuf) -> Result<LintReport> {
+    let design_system: DesignSystem = read_design_system(
+        &repository::compute_fetch_pathbuf(&design_system_pathbuf).fetch_pathbuf,
+    )?;
+    let config = design_system_repository::fetch_lint_config(&design_system_pathbuf)?;
+    let font_files: Vec<String> = load_design_system_fonts(&design_system_pathbuf)?
+        .into_iter()
+        .map(|file| file.filename)
+        .collect();
+    Ok(lint::lint_design_system(
+        &design_system,
+        &config,
+        &font_files,
+    ))
+}
+
+/// Create a new design system from a tokens file (DTCG...)
+pub fn import_design_system(payload: DesignSystemImportPayload) -> Result<ImportReport> {
+    let content: String = repository::read_file_to_string(&payload.file_path)?;
+    let metadata: DesignSystemMetadata = create_design_system(payload.to_creation_payload())?;
+    let design_system_pathbuf: PathBuf = metadata.design_system_path.clone();
+
+    let import_result = load_design_system(&design_system_pathbuf).and_then(|mut design_system| {
+        let unmapped_tokens: Vec<String> =
+            import::import_design_system(&mut design_system, &payload.format, &content)?;
+        design_system_repository::save_design_system(&mut design_system, false)?;
+        Ok(unmapped_tokens)
+    });
+    let unmapped_tokens: Vec<String> = match import_result {
+        Ok(unmapped_tokens) => unmapped_tokens,
+        Err(err) => {
+            design_system_repository::delete_design_system(&design_system_pathbuf)?;
+            return Err(err);
+        }
+    };
+
+    Ok(ImportReport {
+        design_system_metadata: load_design_system(&design_system_pathbuf)?.metadata,
+        unmapped_tokens,
+    })
+}
+
 pub fn register_export(payload: ExportPayload) -> Result<()> {
     design_system_repository::register_export(payload)
 }
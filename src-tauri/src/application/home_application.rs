@@ -72,7 +72,7 @@ pub fn encode_image_base64(path: String) -> Result<ImageLocal> {
 }
 
 pub fn svg_to_png(svg: &str, design_system_path: Option<PathBuf>) -> Result<String> {
-    home_repository::svg_to_png(&svg, design_system_path)
+    home_repository::svg_to_png(svg, design_system_path)
 }
 
 pub fn update_user_settings(state: State<AppState>, user_settings: UserSettings) -> Result<()> {
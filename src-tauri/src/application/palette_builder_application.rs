@@ -1,15 +1,18 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::{
     domain::{
         home_domain::{RecentFile, RecentFileCategory},
+        import_domain::SwatchFormat,
         palette_builder_domain::{
             PaletteBuilder, PaletteBuilderFile, PaletteBuilderMetadata,
             PaletteBuilderRenamePayload, PaletteBuilderUndoRedo,
         },
+        palette_generation_domain,
     },
+    import::swatches_import,
     repository::{
-        design_system_repository, home_repository, palette_builder_repository,
+        self, design_system_repository, home_repository, palette_builder_repository,
         undo_repository::{self, UndoRedoActions},
     },
     AppState,
@@ -35,7 +38,7 @@ pub fn save_palette_builder(state: State<AppState>, palette_builder: PaletteBuil
 pub fn load_palette_builder(state: State<AppState>, path: &PathBuf) -> Result<PaletteBuilderFile> {
     let palette_builder: PaletteBuilderFile =
         palette_builder_repository::load_palette_builder(path)?;
-    if !design_system_repository::is_under_design_system(&path) {
+    if !design_system_repository::is_under_design_system(path) {
         home_repository::insert_recent_file(
             state,
             RecentFile {
@@ -48,18 +51,61 @@ pub fn load_palette_builder(state: State<AppState>, path: &PathBuf) -> Result<Pa
     Ok(palette_builder)
 }
 
+/// Append a palette per group of the swatch file, the swatches are the anchors of the palette.
+/// The palette builder is not saved, the user reviews the palettes before saving
+pub fn import_swatches_into_palette_builder(
+    mut palette_builder: PaletteBuilderFile,
+    path: &PathBuf,
+) -> Result<PaletteBuilderFile> {
+    let format = SwatchFormat::from_path(path)?;
+    let file_name = path
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or("swatches");
+    let groups = swatches_import::read_swatches(&format, &repository::read_file(path)?, file_name)?;
+    let palettes =
+        swatches_import::to_palette_builds(&groups, &palette_builder.settings.palette_settings);
+    palette_builder.palettes.extend(palettes);
+    Ok(palette_builder)
+}
+
+/// Regenerate the tints of every palette from its center, anchors & settings
+pub fn regenerate_palette_builder(
+    mut palette_builder: PaletteBuilderFile,
+) -> Result<PaletteBuilderFile> {
+    for palette in palette_builder.palettes.iter_mut() {
+        palette.tints = palette_generation_domain::regenerate_palette_tints(
+            palette,
+            &palette_builder.settings,
+        )?;
+    }
+    Ok(palette_builder)
+}
+
+/// Regenerate a palette builder file without the webview, written in place or to `out_path`
+pub fn regenerate_palette_builder_file(
+    path: &PathBuf,
+    out_path: Option<PathBuf>,
+) -> Result<PathBuf> {
+    let palette_builder =
+        regenerate_palette_builder(palette_builder_repository::load_palette_builder(path)?)?;
+    let out_path = out_path.unwrap_or_else(|| path.clone());
+    palette_builder_repository::save_palette_builder_file(out_path.clone(), &palette_builder)?;
+    Ok(out_path)
+}
+
 pub fn save_palette_builder_into_design_system(
-    design_system_path: &PathBuf,
+    design_system_path: &Path,
     palette_builder: &PaletteBuilder,
 ) -> Result<()> {
     palette_builder_repository::save_palette_builder_into_design_system(
-        &design_system_path,
-        &palette_builder,
+        design_system_path,
+        palette_builder,
     )
 }
 
 pub fn fetch_design_system_palette_builders(
-    design_system_path: &PathBuf,
+    design_system_path: &Path,
 ) -> Result<Vec<PaletteBuilderMetadata>> {
     palette_builder_repository::fetch_design_system_palette_builders(design_system_path)
 }
@@ -72,26 +118,50 @@ pub fn rename_palette_builder(payload: PaletteBuilderRenamePayload) -> Result<()
     palette_builder_repository::rename_palette_builder(payload)
 }
 
+/// Undo / redo history of the palette builder file, `None` for a palette builder not saved yet
+fn palette_builder_history_id(path: Option<&PathBuf>) -> String {
+    undo_repository::tool_history_id("palette-builder", path.map(PathBuf::as_path))
+}
+
 pub fn do_palette_builder(
     state: State<AppState>,
+    path: Option<PathBuf>,
     palette_builder: PaletteBuilderUndoRedo,
 ) -> Result<()> {
     undo_repository::set_new::<PaletteBuilderUndoRedo>(
         &state,
-        &"palette-builder",
+        &palette_builder_history_id(path.as_ref()),
         &palette_builder,
     )?;
     Ok(())
 }
 
-pub fn undo_palette_builder(state: State<AppState>) -> Result<PaletteBuilderUndoRedo> {
-    undo_repository::undo::<PaletteBuilderUndoRedo>(&state, &"palette-builder")
+pub fn undo_palette_builder(
+    state: State<AppState>,
+    path: Option<PathBuf>,
+) -> Result<PaletteBuilderUndoRedo> {
+    undo_repository::undo::<PaletteBuilderUndoRedo>(
+        &state,
+        &palette_builder_history_id(path.as_ref()),
+    )
 }
 
-pub fn redo_palette_builder(state: State<AppState>) -> Result<PaletteBuilderUndoRedo> {
-    undo_repository::redo::<PaletteBuilderUndoRedo>(&state, &"palette-builder")
+pub fn redo_palette_builder(
+    state: State<AppState>,
+    path: Option<PathBuf>,
+) -> Result<PaletteBuilderUndoRedo> {
+    undo_repository::redo::<PaletteBuilderUndoRedo>(
+        &state,
+        &palette_builder_history_id(path.as_ref()),
+    )
 }
 
-pub fn can_undo_redo_palette_builder(state: State<AppState>) -> Result<UndoRedoActions> {
-    undo_repository::can_undo_redo::<PaletteBuilderUndoRedo>(&state, &"palette-builder")
+pub fn can_undo_redo_palette_builder(
+    state: State<AppState>,
+    path: Option<PathBuf>,
+) -> Result<UndoRedoActions> {
+    undo_repository::can_undo_redo::<PaletteBuilderUndoRedo>(
+        &state,
+        &palette_builder_history_id(path.as_ref()),
+    )
 }
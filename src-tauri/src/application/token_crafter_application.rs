@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
 use tauri::State;
 
@@ -7,26 +9,50 @@ use crate::{
     AppState,
 };
 
+/// Undo / redo history of the token crafter of a design system
+fn token_crafter_history_id(design_system_path: Option<&PathBuf>) -> String {
+    undo_repository::tool_history_id("token-crafter", design_system_path.map(PathBuf::as_path))
+}
+
 pub fn do_token_crafter(
     state: &State<AppState>,
+    design_system_path: Option<PathBuf>,
     token_crafter: &ColorCombinationCollection,
 ) -> Result<()> {
     undo_repository::set_new::<ColorCombinationCollection>(
-        &state,
-        &"token-crafter-undoredo",
-        &token_crafter,
+        state,
+        &token_crafter_history_id(design_system_path.as_ref()),
+        token_crafter,
     )?;
     Ok(())
 }
 
-pub fn undo_token_crafter(state: State<AppState>) -> Result<ColorCombinationCollection> {
-    undo_repository::undo::<ColorCombinationCollection>(&state, &"token-crafter-undoredo")
+pub fn undo_token_crafter(
+    state: State<AppState>,
+    design_system_path: Option<PathBuf>,
+) -> Result<ColorCombinationCollection> {
+    undo_repository::undo::<ColorCombinationCollection>(
+        &state,
+        &token_crafter_history_id(design_system_path.as_ref()),
+    )
 }
 
-pub fn redo_token_crafter(state: State<AppState>) -> Result<ColorCombinationCollection> {
-    undo_repository::redo::<ColorCombinationCollection>(&state, &"token-crafter-undoredo")
+pub fn redo_token_crafter(
+    state: State<AppState>,
+    design_system_path: Option<PathBuf>,
+) -> Result<ColorCombinationCollection> {
+    undo_repository::redo::<ColorCombinationCollection>(
+        &state,
+        &token_crafter_history_id(design_system_path.as_ref()),
+    )
 }
 
-pub fn can_undo_redo_token_crafter(state: State<AppState>) -> Result<UndoRedoActions> {
-    undo_repository::can_undo_redo::<ColorCombinationCollection>(&state, &"token-crafter-undoredo")
+pub fn can_undo_redo_token_crafter(
+    state: State<AppState>,
+    design_system_path: Option<PathBuf>,
+) -> Result<UndoRedoActions> {
+    undo_repository::can_undo_redo::<ColorCombinationCollection>(
+        &state,
+        &token_crafter_history_id(design_system_path.as_ref()),
+    )
 }
@@ -0,0 +1,600 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::{
+    domain::{
+        audit_domain::{
+            ContrastAudit, ContrastCheck, ContrastUsage, ThemeContrastAudit, APCA_NON_TEXT_AA,
+            APCA_NON_TEXT_AAA, APCA_TEXT_AA, APCA_TEXT_AAA, WCAG_NON_TEXT_AA, WCAG_TEXT_AA,
+            WCAG_TEXT_AAA,
+        },
+        color_domain::{apca_contrast, contrast_ratio, delta_e_76, Rgb},
+        color_vision_domain::{
+            self, ColorVisionDeficiency, ColorVisionSimulation, DistinguishabilityWarning,
+            SimulatedColor, SimulatedCombination, SimulatedCombinationCollection, SimulatedPalette,
+            SimulatedTint, STATE_DELTA_E_THRESHOLD,
+        },
+        design_system_domain::{ColorCombination, DesignSystem},
+        theme_domain::DEFAULT_THEME_BACKGROUND,
+    },
+    export::{recolor_themes, resolve_color},
+};
+
+const MAIN_THEME_NAME: &str = "default";
+const THEME_BACKGROUND_TOKEN: &str = "theme-background";
+
+/// State of a color combination with its rendered background, text & border
+type StateColors = (&'static str, [Option<Rgb>; 3]);
+
+/// Semantic color token drawn over a background, colors not resolved yet
+struct ContrastPair<'a> {
+    token_name: String,
+    color: &'a str,
+    background_token_name: String,
+    background: Option<&'a str>,
+    usage: ContrastUsage,
+}
+
+/// WCAG 2.1 ratio & APCA Lc of the semantic color tokens over their background, for the main theme
+/// and every other theme (recolored palettes & semantic token overrides)
+pub fn audit_contrast(design_system: &DesignSystem) -> Result<ContrastAudit> {
+    let main_theme_name = design_system
+        .themes
+        .main_theme
+        .as_ref()
+        .map(|theme| theme.name.as_str())
+        .unwrap_or(MAIN_THEME_NAME);
+    let main_theme_background = design_system
+        .themes
+        .main_theme
+        .as_ref()
+        .map(|theme| theme.background.as_str())
+        .unwrap_or(DEFAULT_THEME_BACKGROUND);
+
+    let mut themes = vec![audit_theme(
+        main_theme_name,
+        main_theme_background,
+        design_system,
+    )];
+    for (theme_name, recolored) in recolor_themes(design_system) {
+        let theme_background = design_system
+            .themes
+            .other_themes
+            .iter()
+            .find(|theme| theme.name == theme_name)
+            .map(|theme| theme.background.as_str())
+            .unwrap_or(DEFAULT_THEME_BACKGROUND);
+        let theme_design_system = DesignSystem {
+            palettes: recolored.palettes,
+            independant_colors: recolored.independant_colors,
+            semantic_color_tokens: design_system.semantic_color_tokens.for_theme(&theme_name),
+            ..design_system.clone()
+        };
+        themes.push(audit_theme(
+            &theme_name,
+            theme_background,
+            &theme_design_system,
+        ));
+    }
+    Ok(ContrastAudit { themes })
+}
+
+fn audit_theme(
+    theme_name: &str,
+    theme_background: &str,
+    design_system: &DesignSystem,
+) -> ThemeContrastAudit {
+    let mut checks: Vec<ContrastCheck> = vec![];
+    let mut unresolved_tokens: Vec<String> = vec![];
+    let mut resolve = |color: &str| -> Option<Rgb> {
+        let resolved = resolve_color(design_system, color);
+        if resolved.is_none() && !unresolved_tokens.iter().any(|token| token == color) {
+            unresolved_tokens.push(color.to_string());
+        }
+        resolved
+    };
+
+    // Translucent backgrounds are drawn over the theme background
+    let theme_background = opaque_background(theme_background);
+
+    for pair in contrast_pairs(design_system) {
+        let background = match pair.background {
+            Some(background) => match resolve(background) {
+                Some(background) => background.blend_over(&theme_background),
+                None => continue,
+            },
+            None => theme_background,
+        };
+        let foreground = match resolve(pair.color) {
+            Some(foreground) => foreground.blend_over(&background),
+            None => continue,
+        };
+        checks.push(contrast_check(pair, &foreground, &background));
+    }
+
+    ThemeContrastAudit {
+        theme_name: theme_name.to_string(),
+        checks,
+        unresolved_tokens,
+    }
+}
+
+/// Theme background drawn over white
+fn opaque_background(theme_background: &str) -> Rgb {
+    let white = Rgb::new(1.0, 1.0, 1.0);
+    Rgb::parse(theme_background)
+        .unwrap_or(white)
+        .blend_over(&white)
+}
+
+/// Base texts & border over the base background, then the text & border of every color combination
+/// state over its background (the base background when the combination has none)
+fn contrast_pairs(design_system: &DesignSystem) -> Vec<ContrastPair<'_>> {
+    let semantic_color_tokens = &design_system.semantic_color_tokens;
+    let base_background = semantic_color_tokens.background.as_deref();
+    let base_background_token_name = match base_background {
+        Some(_) => "base-background",
+        None => THEME_BACKGROUND_TOKEN,
+    };
+
+    let mut pairs: Vec<ContrastPair> = [
+        (
+            "base-text-light",
+            &semantic_color_tokens.text_light,
+            ContrastUsage::Text,
+        ),
+        (
+            "base-text-default",
+            &semantic_color_tokens.text_default,
+            ContrastUsage::Text,
+        ),
+        (
+            "base-text-dark",
+            &semantic_color_tokens.text_dark,
+            ContrastUsage::Text,
+        ),
+        (
+            "base-border",
+            &semantic_color_tokens.border,
+            ContrastUsage::NonText,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(token_name, color, usage)| {
+        color.as_deref().map(|color| ContrastPair {
+            token_name: String::from(token_name),
+            color,
+            background_token_name: String::from(base_background_token_name),
+            background: base_background,
+            usage,
+        })
+    })
+    .collect();
+
+    for collection in &semantic_color_tokens.color_combination_collections {
+        let combination_name = collection
+            .combination_name
+            .as_deref()
+            .unwrap_or("collection");
+        for (state, combination) in [
+            ("", &collection.default),
+            ("-hover", &collection.hover),
+            ("-focus", &collection.focus),
+            ("-active", &collection.active),
+        ] {
+            if let Some(ColorCombination {
+                background,
+                text,
+                border,
+            }) = combination
+            {
+                let (background_token_name, background) = match background.as_deref() {
+                    Some(background) => (
+                        format!("{}{}-background", combination_name, state),
+                        Some(background),
+                    ),
+                    None => (String::from(base_background_token_name), base_background),
+                };
+                for (property, color, usage) in [
+                    ("text", text, ContrastUsage::Text),
+                    ("border", border, ContrastUsage::NonText),
+                ] {
+                    if let Some(color) = color.as_deref() {
+                        pairs.push(ContrastPair {
+                            token_name: format!("{}{}-{}", combination_name, state, property),
+                            color,
+                            background_token_name: background_token_name.clone(),
+                            background,
+                            usage,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    pairs
+}
+
+fn contrast_check(pair: ContrastPair, foreground: &Rgb, background: &Rgb) -> ContrastCheck {
+    let wcag_ratio = contrast_ratio(foreground, background);
+    let apca_lc = apca_contrast(foreground, background);
+    let (wcag_aa, wcag_aaa, apca_aa, apca_aaa) = match pair.usage {
+        ContrastUsage::Text => (WCAG_TEXT_AA, WCAG_TEXT_AAA, APCA_TEXT_AA, APCA_TEXT_AAA),
+        // No AAA level for the non-text contrast in WCAG 2.1
+        ContrastUsage::NonText => (
+            WCAG_NON_TEXT_AA,
+            WCAG_NON_TEXT_AA,
+            APCA_NON_TEXT_AA,
+            APCA_NON_TEXT_AAA,
+        ),
+    };
+    ContrastCheck {
+        token_name: pair.token_name,
+        background_token_name: pair.background_token_name,
+        foreground: foreground.to_hex(),
+        background: background.to_hex(),
+        usage: pair.usage,
+        wcag_ratio: (wcag_ratio * 100.0).round() / 100.0,
+        wcag_aa: wcag_ratio >= wcag_aa,
+        wcag_aaa: wcag_ratio >= wcag_aaa,
+        apca_lc: (apca_lc * 10.0).round() / 10.0,
+        apca_aa: apca_lc.abs() >= apca_aa,
+        apca_aaa: apca_lc.abs() >= apca_aaa,
+    }
+}
+
+/// Palette tints & color combinations of the main theme as seen with every color vision deficiency,
+/// with a warning when two states of a combination can no longer be told apart
+pub fn simulate_color_vision(design_system: &DesignSystem) -> Vec<ColorVisionSimulation> {
+    let combinations = combination_states(design_system);
+    ColorVisionDeficiency::ALL
+        .iter()
+        .map(|deficiency| {
+            let simulate = |color: &Rgb| SimulatedColor {
+                color: color.to_hex(),
+                simulated: color_vision_domain::simulate_color_vision(color, *deficiency).to_hex(),
+            };
+
+            let palettes: Vec<SimulatedPalette> = design_system
+                .palettes
+                .iter()
+                .map(|palette| SimulatedPalette {
+                    palette_name: palette.palette_name.clone(),
+                    tints: palette
+                        .tints
+                        .iter()
+                        .filter_map(|tint| {
+                            Rgb::parse(&tint.color).ok().map(|color| SimulatedTint {
+                                label: tint.label.clone(),
+                                color: simulate(&color),
+                            })
+                        })
+                        .collect(),
+                })
+                .collect();
+
+            let mut warnings: Vec<DistinguishabilityWarning> = vec![];
+            for (combination_name, states) in &combinations {
+                for (index, (first_state, first_colors)) in states.iter().enumerate() {
+                    for (second_state, second_colors) in &states[index + 1..] {
+                        let delta_e = states_delta_e(first_colors, second_colors, |color| *color);
+                        let simulated_delta_e =
+                            states_delta_e(first_colors, second_colors, |color| {
+                                color_vision_domain::simulate_color_vision(color, *deficiency)
+                            });
+                        if delta_e >= STATE_DELTA_E_THRESHOLD
+                            && simulated_delta_e < STATE_DELTA_E_THRESHOLD
+                        {
+                            warnings.push(DistinguishabilityWarning {
+                                combination_name: combination_name.clone(),
+                                first_state: first_state.to_string(),
+                                second_state: second_state.to_string(),
+                                delta_e: (delta_e * 100.0).round() / 100.0,
+                                simulated_delta_e: (simulated_delta_e * 100.0).round() / 100.0,
+                            });
+                        }
+                    }
+                }
+            }
+
+            ColorVisionSimulation {
+                deficiency: *deficiency,
+                palettes,
+                combinations: combinations
+                    .iter()
+                    .map(
+                        |(combination_name, states)| SimulatedCombinationCollection {
+                            combination_name: combination_name.clone(),
+                            states: states
+                                .iter()
+                                .map(|(state, [background, text, border])| SimulatedCombination {
+                                    state: state.to_string(),
+                                    background: background.as_ref().map(simulate),
+                                    text: text.as_ref().map(simulate),
+                                    border: border.as_ref().map(simulate),
+                                })
+                                .collect(),
+                        },
+                    )
+                    .collect(),
+                warnings,
+            }
+        })
+        .collect()
+}
+
+/// Rendered colors (background, text, border) of every color combination state. The missing
+/// properties inherit the default state then the base semantic tokens, translucent colors are drawn
+/// over their background
+fn combination_states(design_system: &DesignSystem) -> Vec<(String, Vec<StateColors>)> {
+    let semantic_color_tokens = &design_system.semantic_color_tokens;
+    let theme_background = opaque_background(
+        design_system
+            .themes
+            .main_theme
+            .as_ref()
+            .map(|theme| theme.background.as_str())
+            .unwrap_or(DEFAULT_THEME_BACKGROUND),
+    );
+
+    semantic_color_tokens
+        .color_combination_collections
+        .iter()
+        .map(|collection| {
+            let default = collection.default.as_ref();
+            let states = [
+                ("default", &collection.default),
+                ("hover", &collection.hover),
+                ("focus", &collection.focus),
+                ("active", &collection.active),
+            ]
+            .into_iter()
+            .filter_map(|(state, combination)| {
+                combination.as_ref().map(|combination| {
+                    let property = |value: fn(&ColorCombination) -> &Option<String>,
+                                    base: &Option<String>|
+                     -> Option<Rgb> {
+                        value(combination)
+                            .as_ref()
+                            .or_else(|| default.and_then(|default| value(default).as_ref()))
+                            .or(base.as_ref())
+                            .and_then(|color| resolve_color(design_system, color))
+                    };
+                    let background = property(
+                        |combination| &combination.background,
+                        &semantic_color_tokens.background,
+                    )
+                    .map(|background| background.blend_over(&theme_background))
+                    .unwrap_or(theme_background);
+                    let text = property(
+                        |combination| &combination.text,
+                        &semantic_color_tokens.text_default,
+                    )
+                    .map(|text| text.blend_over(&background));
+                    let border = property(
+                        |combination| &combination.border,
+                        &semantic_color_tokens.border,
+                    )
+                    .map(|border| border.blend_over(&background));
+                    (state, [Some(background), text, border])
+                })
+            })
+            .collect();
+            (
+                collection
+                    .combination_name
+                    .clone()
+                    .unwrap_or_else(|| String::from("collection")),
+                states,
+            )
+        })
+        .collect()
+}
+
+/// Largest ΔE 1976 between the colors of two states, once transformed
+fn states_delta_e(
+    first: &[Option<Rgb>; 3],
+    second: &[Option<Rgb>; 3],
+    transform: impl Fn(&Rgb) -> Rgb,
+) -> f64 {
+    first
+        .iter()
+        .zip(second)
+        .filter_map(|(first, second)| match (first, second) {
+            (Some(first), Some(second)) => Some(delta_e_76(&transform(first), &transform(second))),
+            _ => None,
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Markdown report : a table per theme, then the unresolved tokens
+pub fn contrast_audit_markdown(audit: &ContrastAudit) -> String {
+    let pass = |passed: bool| if passed { "pass" } else { "fail" };
+    let mut markdown = String::from("# Contrast audit\n");
+    for theme in &audit.themes {
+        let _ = write!(
+            markdown,
+            "\n## Theme {}\n\n\
+            | Token | Background | Colors | WCAG 2.1 | AA | AAA | APCA Lc | AA | AAA |\n\
+            | --- | --- | --- | --- | --- | --- | --- | --- | --- |\n",
+            theme.theme_name
+        );
+        for check in &theme.checks {
+            let _ = writeln!(
+                markdown,
+                "| {}{} | {} | {} / {} | {:.2}:1 | {} | {} | {:.1} | {} | {} |",
+                check.token_name,
+                match check.usage {
+                    ContrastUsage::Text => "",
+                    ContrastUsage::NonText => " (non-text)",
+                },
+                check.background_token_name,
+                check.foreground,
+                check.background,
+                check.wcag_ratio,
+                pass(check.wcag_aa),
+                pass(check.wcag_aaa),
+                check.apca_lc,
+                pass(check.apca_aa),
+                pass(check.apca_aaa),
+            );
+        }
+        if !theme.unresolved_tokens.is_empty() {
+            let _ = writeln!(
+                markdown,
+                "\nUnresolved tokens : {}",
+                theme.unresolved_tokens.join(", ")
+            );
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn find_check<'a>(theme: &'a ThemeContrastAudit, token_name: &str) -> &'a ContrastCheck {
+        theme
+            .checks
+            .iter()
+            .find(|check| check.token_name == token_name)
+            .unwrap()
+    }
+
+    #[test]
+    fn audits_the_main_theme_then_the_other_themes() {
+        let audit = audit_contrast(&sample_design_system()).unwrap();
+        let theme_names: Vec<&str> = audit
+            .themes
+            .iter()
+            .map(|theme| theme.theme_name.as_str())
+            .collect();
+        assert_eq!(theme_names, vec!["light", "dark"]);
+
+        let light = &audit.themes[0];
+        let token_names: Vec<&str> = light
+            .checks
+            .iter()
+            .map(|check| check.token_name.as_str())
+            .collect();
+        assert_eq!(
+            token_names,
+            vec!["base-text-light", "base-text-default", "button-text"]
+        );
+        assert!(light.unresolved_tokens.is_empty());
+
+        let text = find_check(light, "base-text-default");
+        assert_eq!(text.background_token_name, "base-background");
+        assert_eq!(text.foreground, "#111827");
+        assert_eq!(text.background, "#f9fafb");
+        assert!(text.wcag_aa && text.wcag_aaa && text.apca_aa);
+
+        let button = find_check(light, "button-text");
+        assert_eq!(button.background_token_name, "button-background");
+        assert_eq!(button.foreground, "#ffffff");
+        assert_eq!(button.background, "#3b82f6");
+        assert_eq!(button.wcag_ratio, 3.68);
+        assert!(!button.wcag_aa && !button.wcag_aaa);
+        assert!(button.apca_lc < 0.0);
+    }
+
+    #[test]
+    fn theme_overrides_are_audited() {
+        let design_system = sample_design_system();
+        let mut without_overrides = design_system.clone();
+        without_overrides
+            .semantic_color_tokens
+            .theme_overrides
+            .clear();
+
+        let audit = audit_contrast(&design_system).unwrap();
+        let audit_without_overrides = audit_contrast(&without_overrides).unwrap();
+        let dark = find_check(&audit.themes[1], "base-text-default");
+        let dark_without_overrides =
+            find_check(&audit_without_overrides.themes[1], "base-text-default");
+        assert_ne!(dark.foreground, dark_without_overrides.foreground);
+        assert_ne!(dark.background, dark_without_overrides.background);
+        // The main theme ignores the overrides
+        assert_eq!(
+            find_check(&audit.themes[0], "base-text-default").foreground,
+            find_check(&audit_without_overrides.themes[0], "base-text-default").foreground
+        );
+    }
+
+    #[test]
+    fn unresolved_tokens_are_listed() {
+        let mut design_system = sample_design_system();
+        design_system.semantic_color_tokens.text_dark = Some(String::from("palette-missing-500"));
+        design_system.semantic_color_tokens.border = Some(String::from("#00000080"));
+
+        let audit = audit_contrast(&design_system).unwrap();
+        let light = &audit.themes[0];
+        assert_eq!(
+            light.unresolved_tokens,
+            vec![String::from("palette-missing-500")]
+        );
+        assert!(light
+            .checks
+            .iter()
+            .all(|check| check.token_name != "base-text-dark"));
+        // Translucent colors are drawn over their background
+        let border = find_check(light, "base-border");
+        assert_eq!(border.usage, ContrastUsage::NonText);
+        assert_eq!(border.foreground, "#7c7d7d");
+
+        let markdown = contrast_audit_markdown(&audit);
+        assert!(markdown.contains("## Theme light\n"));
+        assert!(
+            markdown.contains("| base-border (non-text) | base-background | #7c7d7d / #f9fafb |")
+        );
+        assert!(markdown.contains("\nUnresolved tokens : palette-missing-500\n"));
+    }
+
+    #[test]
+    fn simulates_every_deficiency() {
+        let simulations = simulate_color_vision(&sample_design_system());
+        assert_eq!(simulations.len(), ColorVisionDeficiency::ALL.len());
+        let simulation = &simulations[0];
+        assert_eq!(simulation.deficiency, ColorVisionDeficiency::Protanopia);
+        assert_eq!(simulation.palettes[0].palette_name, "primary");
+        assert_eq!(simulation.palettes[0].tints[1].color.color, "#3b82f6");
+        assert_ne!(simulation.palettes[0].tints[1].color.simulated, "#3b82f6");
+
+        // The hover state inherits the default text
+        let states = &simulation.combinations[0].states;
+        assert_eq!(states[1].state, "hover");
+        assert_eq!(states[1].text.as_ref().unwrap().color, "#ffffff");
+        assert!(states[1].border.is_none());
+    }
+
+    #[test]
+    fn warns_when_states_collapse() {
+        let mut design_system = sample_design_system();
+        let button = &mut design_system
+            .semantic_color_tokens
+            .color_combination_collections[0];
+        // Pink & teal only differ along the missing cone axis of the deuteranopia
+        button.default.as_mut().unwrap().background = Some(String::from("#a8617d"));
+        button.hover.as_mut().unwrap().background = Some(String::from("#12917a"));
+
+        let simulations = simulate_color_vision(&design_system);
+        let warnings = |deficiency: ColorVisionDeficiency| {
+            simulations
+                .iter()
+                .find(|simulation| simulation.deficiency == deficiency)
+                .unwrap()
+                .warnings
+                .clone()
+        };
+        let deuteranopia = warnings(ColorVisionDeficiency::Deuteranopia);
+        assert_eq!(deuteranopia.len(), 1);
+        assert_eq!(deuteranopia[0].combination_name, "button");
+        assert_eq!(deuteranopia[0].first_state, "default");
+        assert_eq!(deuteranopia[0].second_state, "hover");
+        assert!(deuteranopia[0].simulated_delta_e < STATE_DELTA_E_THRESHOLD);
+        assert!(warnings(ColorVisionDeficiency::Tritanopia).is_empty());
+    }
+}
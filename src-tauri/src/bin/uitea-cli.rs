@@ -0,0 +1,554 @@
+//! Headless uitea : regenerate the exports of a design system folder without the GUI.
+//!
+//! ```text
+//! uitea-cli export <design-system-dir> --format <format> [--out <dir>]
+//! uitea-cli import <tokens-file> --format <dtcg|css|tokens-studio> --into <dir> [--name <name>]
+//! uitea-cli regenerate <palette-builder.yaml>... [--out <file>]
+//! uitea-cli audit <design-system-dir> [--json]
+//! uitea-cli lint <design-system-dir> [--json]
+//! uitea-cli diff <previous-design-system-dir> <design-system-dir> [--json]
+//! uitea-cli changelog <design-system-dir> [--json]
+//! ```
+//!
+//! `lint` exits with the code 1 when an issue has the `error` level. A version of a design system
+//! (`<design-system-dir>/versions/<name>`) can be compared with `diff` as a design system folder.
+//! `changelog` writes the `CHANGELOG.md` of the design system from its versions and prints it.
+//!
+//! Apart from the exported files, the imported design system and the changelog, the design system
+//! folders are only read : no missing file is created and an interrupted save is not recovered. The
+//! reports are written on stdout, the diagnostics on stderr.
+use std::{env, fs, path::PathBuf, process, str::FromStr};
+
+use anyhow::{anyhow, bail, Result};
+use uitea_lib::{
+    application::{design_system_application, palette_builder_application},
+    audit::contrast_audit_markdown,
+    diff::diff_markdown,
+    domain::{
+        export_domain::ExportFormat,
+        import_domain::{DesignSystemImportPayload, ImportFormat, ImportReport},
+    },
+    export::serde_name,
+};
+
+const USAGE: &str = "Usage:
+  uitea-cli export <design-system-dir> --format <format> [--out <dir>]
+  uitea-cli import <tokens-file> --format <dtcg|css|tokens-studio> --into <dir> [--name <name>]
+  uitea-cli regenerate <palette-builder.yaml>... [--out <file>]
+  uitea-cli audit <design-system-dir> [--json]
+  uitea-cli lint <design-system-dir> [--json]
+  uitea-cli diff <previous-design-system-dir> <design-system-dir> [--json]
+  uitea-cli changelog <design-system-dir> [--json]
+
+Options:
+  --format <format>  Export to generate : css, tokens-studio, dtcg, style-dictionary, scss,
+                     less, tailwind, android, ios, swatches
+                     Format of the imported file : dtcg, css, tokens-studio
+  --out <dir>        Output folder (default : <design-system-dir>/exports)
+                     Regenerated palette builder file (default : overwrite the file, single file only)
+  --into <dir>       Folder in which the design system is created
+  --name <name>      Name of the design system (default : name of the tokens file)
+  --json             Contrast audit, lint issues, token changes or changelog as JSON
+                     (default : text report)";
+
+struct ExportCommand {
+    design_system_path: PathBuf,
+    format: ExportFormat,
+    out_path: Option<PathBuf>,
+}
+
+fn parse_export_command(args: &[String]) -> Result<ExportCommand> {
+    let mut design_system_path: Option<PathBuf> = None;
+    let mut format: Option<ExportFormat> = None;
+    let mut out_path: Option<PathBuf> = None;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for --format"))?;
+                format = Some(ExportFormat::from_str(value)?);
+            }
+            "--out" => {
+                let value = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for --out"))?;
+                out_path = Some(PathBuf::from(value));
+            }
+            value if value.starts_with("--") => bail!("Unknown option : {}", value),
+            value => {
+                if design_system_path.is_some() {
+                    bail!("Unexpected argument : {}", value);
+                }
+                design_system_path = Some(PathBuf::from(value));
+            }
+        }
+    }
+
+    Ok(ExportCommand {
+        design_system_path: design_system_path
+            .ok_or_else(|| anyhow!("Missing design system folder"))?,
+        format: format.ok_or_else(|| anyhow!("Missing --format"))?,
+        out_path,
+    })
+}
+
+struct ImportCommand {
+    file_path: PathBuf,
+    format: ImportFormat,
+    folder_path: PathBuf,
+    name: Option<String>,
+}
+
+fn parse_import_command(args: &[String]) -> Result<ImportCommand> {
+    let mut file_path: Option<PathBuf> = None;
+    let mut format: Option<ImportFormat> = None;
+    let mut folder_path: Option<PathBuf> = None;
+    let mut name: Option<String> = None;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for --format"))?;
+                format = Some(ImportFormat::from_str(value)?);
+            }
+            "--into" => {
+                let value = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for --into"))?;
+                folder_path = Some(PathBuf::from(value));
+            }
+            "--name" => {
+                let value = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for --name"))?;
+                name = Some(value.clone());
+            }
+            value if value.starts_with("--") => bail!("Unknown option : {}", value),
+            value => {
+                if file_path.is_some() {
+                    bail!("Unexpected argument : {}", value);
+                }
+                file_path = Some(PathBuf::from(value));
+            }
+        }
+    }
+
+    Ok(ImportCommand {
+        file_path: file_path.ok_or_else(|| anyhow!("Missing tokens file"))?,
+        format: format.ok_or_else(|| anyhow!("Missing --format"))?,
+        folder_path: folder_path.ok_or_else(|| anyhow!("Missing --into"))?,
+        name,
+    })
+}
+
+struct RegenerateCommand {
+    file_paths: Vec<PathBuf>,
+    out_path: Option<PathBuf>,
+}
+
+fn parse_regenerate_command(args: &[String]) -> Result<RegenerateCommand> {
+    let mut file_paths: Vec<PathBuf> = vec![];
+    let mut out_path: Option<PathBuf> = None;
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--out" => {
+                let value = args_iter
+                    .next()
+                    .ok_or_else(|| anyhow!("Missing value for --out"))?;
+                out_path = Some(PathBuf::from(value));
+            }
+            value if value.starts_with("--") => bail!("Unknown option : {}", value),
+            value => file_paths.push(PathBuf::from(value)),
+        }
+    }
+
+    if file_paths.is_empty() {
+        bail!("Missing palette builder file");
+    }
+    if out_path.is_some() && file_paths.len() > 1 {
+        bail!("--out needs a single palette builder file");
+    }
+    Ok(RegenerateCommand {
+        file_paths,
+        out_path,
+    })
+}
+
+/// Command reporting on a design system folder (audit, lint, changelog)
+struct ReportCommand {
+    design_system_path: PathBuf,
+    json: bool,
+}
+
+fn parse_report_command(args: &[String]) -> Result<ReportCommand> {
+    let mut design_system_path: Option<PathBuf> = None;
+    let mut json = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            value if value.starts_with("--") => bail!("Unknown option : {}", value),
+            value => {
+                if design_system_path.is_some() {
+                    bail!("Unexpected argument : {}", value);
+                }
+                design_system_path = Some(PathBuf::from(value));
+            }
+        }
+    }
+
+    Ok(ReportCommand {
+        design_system_path: design_system_path
+            .ok_or_else(|| anyhow!("Missing design system folder"))?,
+        json,
+    })
+}
+
+struct DiffCommand {
+    previous_design_system_path: PathBuf,
+    design_system_path: PathBuf,
+    json: bool,
+}
+
+fn parse_diff_command(args: &[String]) -> Result<DiffCommand> {
+    let mut design_system_paths: Vec<PathBuf> = vec![];
+    let mut json = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            value if value.starts_with("--") => bail!("Unknown option : {}", value),
+            value => {
+                if design_system_paths.len() == 2 {
+                    bail!("Unexpected argument : {}", value);
+                }
+                design_system_paths.push(PathBuf::from(value));
+            }
+        }
+    }
+
+    let mut design_system_paths = design_system_paths.into_iter();
+    Ok(DiffCommand {
+        previous_design_system_path: design_system_paths
+            .next()
+            .ok_or_else(|| anyhow!("Missing previous design system folder"))?,
+        design_system_path: design_system_paths
+            .next()
+            .ok_or_else(|| anyhow!("Missing design system folder"))?,
+        json,
+    })
+}
+
+fn run(args: &[String]) -> Result<()> {
+    match args.first().map(|command| command.as_str()) {
+        Some("export") => {
+            let ExportCommand {
+                design_system_path,
+                format,
+                out_path,
+            } = parse_export_command(&args[1..])?;
+            let written_files: Vec<PathBuf> = design_system_application::generate_export(
+                &design_system_path,
+                &format,
+                out_path,
+                false,
+            )?;
+            for file in written_files {
+                eprintln!("exported {}", file.display());
+            }
+            Ok(())
+        }
+        Some("import") => {
+            let ImportCommand {
+                file_path,
+                format,
+                folder_path,
+                name,
+            } = parse_import_command(&args[1..])?;
+            // Tokens file name without its extensions (tokens.json -> tokens)
+            let default_name: String = file_path
+                .file_name()
+                .and_then(|filename| filename.to_str())
+                .and_then(|filename| filename.split('.').next())
+                .unwrap_or("design-system")
+                .to_string();
+            let ImportReport {
+                design_system_metadata,
+                unmapped_tokens,
+            } = design_system_application::import_design_system(DesignSystemImportPayload {
+                name: name.unwrap_or(default_name),
+                folder_path: folder_path.to_string_lossy().into_owned(),
+                banner: String::new(),
+                logo: String::new(),
+                file_path,
+                format,
+            })?;
+            for token in &unmapped_tokens {
+                eprintln!("unmapped token {}", token);
+            }
+            eprintln!(
+                "imported {}",
+                design_system_metadata.design_system_path.display()
+            );
+            Ok(())
+        }
+        Some("regenerate") => {
+            let RegenerateCommand {
+                file_paths,
+                out_path,
+            } = parse_regenerate_command(&args[1..])?;
+            for file_path in file_paths {
+                let written_file = palette_builder_application::regenerate_palette_builder_file(
+                    &file_path,
+                    out_path.clone(),
+                )?;
+                eprintln!("regenerated {}", written_file.display());
+            }
+            Ok(())
+        }
+        Some("audit") => {
+            let ReportCommand {
+                design_system_path,
+                json,
+            } = parse_report_command(&args[1..])?;
+            let audit = design_system_application::audit_contrast(design_system_path)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&audit)?);
+            } else {
+                print!("{}", contrast_audit_markdown(&audit));
+            }
+            Ok(())
+        }
+        Some("lint") => {
+            let ReportCommand {
+                design_system_path,
+                json,
+            } = parse_report_command(&args[1..])?;
+            let report = design_system_application::lint_design_system(design_system_path)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for issue in &report.issues {
+                    println!(
+                        "{} [{}] {} : {}",
+                        serde_name(&issue.level),
+                        serde_name(&issue.rule),
+                        issue.location,
+                        issue.message
+                    );
+                }
+            }
+            if report.has_errors() {
+                process::exit(1);
+            }
+            Ok(())
+        }
+        Some("diff") => {
+            let DiffCommand {
+                previous_design_system_path,
+                design_system_path,
+                json,
+            } = parse_diff_command(&args[1..])?;
+            let diff = design_system_application::diff_design_systems(
+                previous_design_system_path,
+                design_system_path,
+            )?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                print!("{}", diff_markdown(&diff));
+            }
+            Ok(())
+        }
+        Some("changelog") => {
+            let ReportCommand {
+                design_system_path,
+                json,
+            } = parse_report_command(&args[1..])?;
+            let changelog = design_system_application::generate_design_system_changelog(
+                design_system_path.clone(),
+            )?;
+            let changelog_path: PathBuf = design_system_path.join("CHANGELOG.md");
+            if json {
+                println!("{}", serde_json::to_string_pretty(&changelog)?);
+            } else {
+                print!("{}", fs::read_to_string(&changelog_path)?);
+            }
+            eprintln!("generated {}", changelog_path.display());
+            Ok(())
+        }
+        Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => bail!("Unknown command : {}", command),
+        None => bail!("Missing command"),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("error: {:#}\n\n{}", err, USAGE);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn error(result: Result<impl Sized>) -> String {
+        match result {
+            Ok(_) => panic!("the arguments should be rejected"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_the_export_command() {
+        let command =
+            parse_export_command(&args(&["--format", "tailwind", "ds", "--out", "out"])).unwrap();
+        assert_eq!(command.design_system_path, PathBuf::from("ds"));
+        assert_eq!(command.format, ExportFormat::Tailwind);
+        assert_eq!(command.out_path, Some(PathBuf::from("out")));
+
+        assert_eq!(
+            error(parse_export_command(&args(&["ds"]))),
+            "Missing --format"
+        );
+        assert_eq!(
+            error(parse_export_command(&args(&["ds", "--format", "pdf"]))),
+            "Unknown export format : pdf"
+        );
+        assert_eq!(
+            error(parse_export_command(&args(&[
+                "ds", "other", "--format", "css"
+            ]))),
+            "Unexpected argument : other"
+        );
+        assert_eq!(
+            error(parse_export_command(&args(&["ds", "--out"]))),
+            "Missing value for --out"
+        );
+    }
+
+    #[test]
+    fn parses_the_import_command() {
+        let command = parse_import_command(&args(&[
+            "tokens.json",
+            "--format",
+            "tokens-studio",
+            "--into",
+            "folder",
+        ]))
+        .unwrap();
+        assert_eq!(command.file_path, PathBuf::from("tokens.json"));
+        assert_eq!(command.format, ImportFormat::TokensStudio);
+        assert_eq!(command.folder_path, PathBuf::from("folder"));
+        assert_eq!(command.name, None);
+
+        assert_eq!(
+            error(parse_import_command(&args(&[
+                "tokens.json",
+                "--format",
+                "css"
+            ]))),
+            "Missing --into"
+        );
+    }
+
+    #[test]
+    fn parses_the_regenerate_and_report_commands() {
+        let command = parse_regenerate_command(&args(&["a.yaml", "b.yaml"])).unwrap();
+        assert_eq!(command.file_paths.len(), 2);
+        assert_eq!(
+            error(parse_regenerate_command(&args(&[
+                "a.yaml", "b.yaml", "--out", "c.yaml"
+            ]))),
+            "--out needs a single palette builder file"
+        );
+        assert_eq!(
+            error(parse_regenerate_command(&args(&[]))),
+            "Missing palette builder file"
+        );
+
+        let command = parse_report_command(&args(&["--json", "ds"])).unwrap();
+        assert_eq!(command.design_system_path, PathBuf::from("ds"));
+        assert!(command.json);
+        assert_eq!(
+            error(parse_report_command(&args(&["ds", "--verbose"]))),
+            "Unknown option : --verbose"
+        );
+
+        let command = parse_diff_command(&args(&["previous", "ds"])).unwrap();
+        assert_eq!(
+            command.previous_design_system_path,
+            PathBuf::from("previous")
+        );
+        assert_eq!(command.design_system_path, PathBuf::from("ds"));
+        assert!(!command.json);
+        assert_eq!(
+            error(parse_diff_command(&args(&["previous"]))),
+            "Missing design system folder"
+        );
+    }
+
+    #[test]
+    fn imports_then_exports_a_design_system() {
+        let folder_path = env::temp_dir().join(format!("uitea-cli-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&folder_path).unwrap();
+        let tokens_path = folder_path.join("brand.tokens.css");
+        fs::write(
+            &tokens_path,
+            ":root { --primary-500: #3b82f6; --space-sm: 8px; }",
+        )
+        .unwrap();
+
+        run(&args(&[
+            "import",
+            tokens_path.to_str().unwrap(),
+            "--format",
+            "css",
+            "--into",
+            folder_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+        let design_system_path = folder_path.join("brand");
+        assert!(design_system_path.is_dir());
+
+        let out_path = folder_path.join("exports");
+        run(&args(&[
+            "export",
+            design_system_path.to_str().unwrap(),
+            "--format",
+            "scss",
+            "--out",
+            out_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+        let scss = fs::read_to_string(out_path.join("_tokens.scss")).unwrap();
+        assert!(scss.contains("$palette-primary-500: #3b82f6;\n"));
+        assert!(scss.contains("$space-sm: 8px;\n"));
+
+        fs::remove_dir_all(&folder_path).unwrap();
+    }
+
+    #[test]
+    fn unknown_commands_are_errors() {
+        assert_eq!(error(run(&args(&["publish"]))), "Unknown command : publish");
+        assert_eq!(error(run(&args(&[]))), "Missing command");
+    }
+}
@@ -0,0 +1,323 @@
+use std::fmt::Write;
+
+use crate::{
+    diff::diff_design_systems,
+    domain::{
+        changelog_domain::{Changelog, ChangelogEntry, TokenRename, VersionChanges},
+        design_system_domain::DesignSystem,
+        diff_domain::{DiffCategory, TokenChange, TokenChangeKind},
+        version_domain::DesignSystemVersion,
+    },
+    export::serde_name,
+};
+
+/// Changes from `previous` to `current`. A token removed and added with the same value in the same
+/// category is a rename, a palette is renamed when its tints (labels & colors) are the same
+pub fn version_changes(previous: &DesignSystem, current: &DesignSystem) -> VersionChanges {
+    let mut added: Vec<TokenChange> = vec![];
+    let mut removed: Vec<TokenChange> = vec![];
+    let mut changed: Vec<TokenChange> = vec![];
+    for change in diff_design_systems(previous, current).changes {
+        match change.kind {
+            TokenChangeKind::Added => added.push(change),
+            TokenChangeKind::Removed => removed.push(change),
+            TokenChangeKind::Changed => changed.push(change),
+        }
+    }
+
+    let mut renamed: Vec<TokenRename> = vec![];
+    removed.retain(|removed_change| {
+        let previous_key: Option<String> = rename_key(previous, removed_change);
+        let rename_index: Option<usize> = added.iter().position(|added_change| {
+            added_change.category == removed_change.category
+                && previous_key.is_some()
+                && rename_key(current, added_change) == previous_key
+        });
+        match rename_index {
+            Some(index) => {
+                let added_change: TokenChange = added.remove(index);
+                renamed.push(TokenRename {
+                    category: removed_change.category,
+                    previous_token_name: removed_change.token_name.clone(),
+                    token_name: added_change.token_name,
+                });
+                false
+            }
+            None => true,
+        }
+    });
+
+    VersionChanges {
+        added,
+        removed,
+        renamed,
+        changed,
+    }
+}
+
+/// Value identifying a token through a rename
+fn rename_key(design_system: &DesignSystem, change: &TokenChange) -> Option<String> {
+    match change.category {
+        DiffCategory::Palette => design_system
+            .palettes
+            .iter()
+            .find(|palette| format!("palette-{}", palette.palette_name) == change.token_name)
+            .map(|palette| {
+                palette
+                    .tints
+                    .iter()
+                    .map(|tint| format!("{} {}", tint.label, tint.color))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            }),
+        _ => change
+            .value
+            .clone()
+            .or_else(|| change.previous_value.clone()),
+    }
+}
+
+/// Changelog entry of `version` (`None` for the changes not yet in a version), `previous` is the
+/// previous version name & design system
+pub fn changelog_entry(
+    version: Option<DesignSystemVersion>,
+    previous: Option<(&str, &DesignSystem)>,
+    current: &DesignSystem,
+) -> ChangelogEntry {
+    match previous {
+        None => ChangelogEntry {
+            version,
+            previous_version_name: None,
+            changes: None,
+            bump: None,
+            suggested_version: None,
+        },
+        Some((previous_version_name, previous)) => {
+            let changes: VersionChanges = version_changes(previous, current);
+            let bump = changes.bump();
+            ChangelogEntry {
+                version,
+                previous_version_name: Some(previous_version_name.to_string()),
+                suggested_version: bump.and_then(|bump| bump.apply(previous_version_name)),
+                bump,
+                changes: Some(changes),
+            }
+        }
+    }
+}
+
+/// `CHANGELOG.md` of the design system : breaking changes, additions & value changes by version
+pub fn changelog_markdown(design_system_name: &str, changelog: &Changelog) -> String {
+    let mut markdown = format!(
+        "# Changelog\n\n\
+        Token changes of {} by version. A removed or renamed token breaks the consumers of the \
+        exports (major version), an added token is a minor version and a changed value a patch.\n",
+        design_system_name
+    );
+    if changelog.entries.is_empty() {
+        markdown.push_str("\nNo version yet\n");
+        return markdown;
+    }
+    for entry in &changelog.entries {
+        match &entry.version {
+            Some(version) => {
+                // RFC 3339 date, the time is dropped
+                let date: &str = version
+                    .update_date
+                    .get(..10)
+                    .unwrap_or(&version.update_date);
+                let _ = writeln!(markdown, "\n## {} - {}", version.name, date);
+                if let Some(description) = &version.description {
+                    let _ = writeln!(markdown, "\n{}", description);
+                }
+            }
+            None => markdown.push_str("\n## Unreleased\n"),
+        }
+        let (changes, previous_name) = match (&entry.changes, &entry.previous_version_name) {
+            (Some(changes), Some(previous_name)) => (changes, previous_name),
+            _ => {
+                markdown.push_str("\nInitial version\n");
+                continue;
+            }
+        };
+        match entry.bump {
+            Some(bump) => {
+                let _ = write!(
+                    markdown,
+                    "\nSuggested bump since {} : **{}**",
+                    previous_name,
+                    serde_name(&bump)
+                );
+                if let Some(suggested_version) = &entry.suggested_version {
+                    let _ = write!(markdown, " ({})", suggested_version);
+                }
+                markdown.push('\n');
+                if entry.is_under_bumped() {
+                    let _ = writeln!(
+                        markdown,
+                        "\n> Released with a version lower than the suggested {}",
+                        entry.suggested_version.as_deref().unwrap_or_default()
+                    );
+                }
+            }
+            None => {
+                let _ = writeln!(markdown, "\nNo token change since {}", previous_name);
+            }
+        }
+        write_version_changes(&mut markdown, changes);
+    }
+    markdown
+}
+
+fn write_version_changes(markdown: &mut String, changes: &VersionChanges) {
+    if !changes.renamed.is_empty() || !changes.removed.is_empty() {
+        markdown.push_str("\n### Breaking changes\n\n");
+        for rename in &changes.renamed {
+            let _ = writeln!(
+                markdown,
+                "- Renamed `{}` to `{}` ({})",
+                rename.previous_token_name,
+                rename.token_name,
+                rename.category.title()
+            );
+        }
+        for change in &changes.removed {
+            let _ = writeln!(
+                markdown,
+                "- Removed `{}` ({})",
+                change.token_name,
+                change.category.title()
+            );
+        }
+    }
+    if !changes.added.is_empty() {
+        markdown.push_str("\n### Added\n\n");
+        for change in &changes.added {
+            let _ = writeln!(
+                markdown,
+                "- `{}` : {} ({})",
+                change.token_name,
+                change.value.as_deref().unwrap_or_default(),
+                change.category.title()
+            );
+        }
+    }
+    if !changes.changed.is_empty() {
+        markdown.push_str("\n### Changed\n\n");
+        for change in &changes.changed {
+            let _ = write!(
+                markdown,
+                "- `{}` : {} → {}",
+                change.token_name,
+                change.previous_value.as_deref().unwrap_or_default(),
+                change.value.as_deref().unwrap_or_default()
+            );
+            if let Some(delta_e) = change.delta_e {
+                let _ = write!(markdown, " (ΔE 2000 {:.2})", delta_e);
+            }
+            markdown.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::changelog_domain::SemverBump, test_fixtures::sample_design_system};
+
+    fn version(name: &str) -> Option<DesignSystemVersion> {
+        Some(DesignSystemVersion {
+            name: name.to_string(),
+            description: Some(String::from("Release notes")),
+            update_date: String::from("2024-03-01T10:00:00Z"),
+        })
+    }
+
+    #[test]
+    fn renamed_palettes_are_breaking_changes() {
+        let previous = sample_design_system();
+        let mut current = previous.clone();
+        current.palettes[1].palette_name = String::from("neutral");
+
+        let changes = version_changes(&previous, &current);
+        assert_eq!(changes.renamed.len(), 1);
+        assert_eq!(changes.renamed[0].category, DiffCategory::Palette);
+        assert_eq!(changes.renamed[0].previous_token_name, "palette-gray");
+        assert_eq!(changes.renamed[0].token_name, "palette-neutral");
+        assert!(changes
+            .added
+            .iter()
+            .chain(&changes.removed)
+            .all(|change| change.category != DiffCategory::Palette));
+        assert_eq!(changes.bump(), Some(SemverBump::Major));
+    }
+
+    #[test]
+    fn renamed_tokens_keep_their_value() {
+        let previous = sample_design_system();
+        let mut current = previous.clone();
+        current.radius.additionals_radius[0].radius_key = String::from("large");
+
+        let changes = version_changes(&previous, &current);
+        assert!(changes.added.is_empty() && changes.removed.is_empty());
+        assert_eq!(changes.renamed[0].previous_token_name, "radius-lg");
+        assert_eq!(changes.renamed[0].token_name, "radius-large");
+    }
+
+    #[test]
+    fn suggests_the_bump_of_the_changes() {
+        let previous = sample_design_system();
+        let mut added = previous.clone();
+        let mut font = previous.fonts.additionals[0].clone();
+        font.font_name = String::from("code");
+        font.value = String::from("Fira Code");
+        added.fonts.additionals.push(font);
+        let mut changed = previous.clone();
+        changed.independant_colors.independant_colors[0].color = String::from("#059669");
+
+        let entry = changelog_entry(version("v1.3.0"), Some(("v1.2.5", &previous)), &added);
+        assert_eq!(entry.bump, Some(SemverBump::Minor));
+        assert_eq!(entry.suggested_version.as_deref(), Some("v1.3.0"));
+        assert!(!entry.is_under_bumped());
+
+        let entry = changelog_entry(None, Some(("1.2.5", &previous)), &changed);
+        assert_eq!(entry.bump, Some(SemverBump::Patch));
+        assert_eq!(entry.suggested_version.as_deref(), Some("1.2.6"));
+
+        let entry = changelog_entry(None, Some(("beta", &previous)), &changed);
+        assert_eq!(entry.bump, Some(SemverBump::Patch));
+        assert_eq!(entry.suggested_version, None);
+
+        let entry = changelog_entry(None, Some(("1.2.5", &previous)), &previous);
+        assert_eq!(entry.bump, None);
+        assert!(entry.changes.unwrap().is_empty());
+    }
+
+    #[test]
+    fn changelog_markdown_lists_the_versions() {
+        let first = sample_design_system();
+        let mut second = first.clone();
+        second.shadows.clear();
+        second.spaces[0].space_value.value = 8.0;
+        let changelog = Changelog {
+            entries: vec![
+                changelog_entry(None, Some(("1.1.0", &second)), &second),
+                changelog_entry(version("1.1.0"), Some(("1.0.0", &first)), &second),
+                changelog_entry(version("1.0.0"), None, &first),
+            ],
+        };
+
+        let markdown = changelog_markdown("Demo", &changelog);
+        assert!(markdown.starts_with("# Changelog\n\nToken changes of Demo by version."));
+        assert!(markdown.contains("## Unreleased\n\nNo token change since 1.1.0\n"));
+        assert!(markdown.contains("## 1.1.0 - 2024-03-01\n\nRelease notes\n"));
+        assert!(markdown.contains("Suggested bump since 1.0.0 : **major** (2.0.0)\n"));
+        assert!(markdown.contains("> Released with a version lower than the suggested 2.0.0\n"));
+        assert!(markdown.contains("### Breaking changes\n\n- Removed `shadow-shadow` (Shadow"));
+        assert!(markdown.contains("### Changed\n\n- `space-1` : 4px → 8px\n"));
+        assert!(markdown.ends_with("## 1.0.0 - 2024-03-01\n\nRelease notes\n\nInitial version\n"));
+        assert!(
+            changelog_markdown("Demo", &Changelog { entries: vec![] }).contains("No version yet")
+        );
+    }
+}
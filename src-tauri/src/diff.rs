@@ -0,0 +1,441 @@
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use crate::{
+    domain::{
+        color_domain::{delta_e_2000, Rgb},
+        design_system_domain::DesignSystem,
+        diff_domain::{DesignSystemDiff, DiffCategory, TokenChange, TokenChangeKind},
+    },
+    export::{
+        independant_color_tokens, measurement_to_css, palette_token_name, resolve_color,
+        semantic_color_tokens, shadows_to_css, typography_properties,
+    },
+};
+
+/// Token of a design system with its exported value, and its color for the ΔE 2000
+struct DiffToken {
+    category: DiffCategory,
+    value: String,
+    color: Option<Rgb>,
+    /// Palette or typography scale of the token, a group added or removed as a whole is listed
+    /// instead of its tokens
+    group: Option<String>,
+}
+
+/// Changes from `previous` to `current` : palettes & typography scales added or removed first, then
+/// the tokens in the order of `previous` (the added ones last)
+pub fn diff_design_systems(previous: &DesignSystem, current: &DesignSystem) -> DesignSystemDiff {
+    let mut changes: Vec<TokenChange> = group_changes(
+        DiffCategory::Palette,
+        &palette_groups(previous),
+        &palette_groups(current),
+    );
+    changes.extend(group_changes(
+        DiffCategory::TypographyScale,
+        &typography_groups(previous),
+        &typography_groups(current),
+    ));
+    let changed_groups: Vec<String> = changes
+        .iter()
+        .map(|change| change.token_name.clone())
+        .collect();
+    let in_changed_group = |token: &DiffToken| {
+        token
+            .group
+            .as_ref()
+            .map_or(false, |group| changed_groups.contains(group))
+    };
+
+    let previous_tokens = design_system_tokens(previous);
+    let mut current_tokens = design_system_tokens(current);
+    for (token_name, previous_token) in previous_tokens {
+        let token = current_tokens.shift_remove(&token_name);
+        if in_changed_group(&previous_token) {
+            continue;
+        }
+        match token {
+            Some(token) if token.value == previous_token.value => {}
+            Some(token) => changes.push(TokenChange {
+                category: token.category,
+                token_name,
+                kind: TokenChangeKind::Changed,
+                delta_e: match (&previous_token.color, &token.color) {
+                    (Some(previous_color), Some(color)) => {
+                        Some((delta_e_2000(previous_color, color) * 100.0).round() / 100.0)
+                    }
+                    _ => None,
+                },
+                previous_value: Some(previous_token.value),
+                value: Some(token.value),
+            }),
+            None => changes.push(TokenChange {
+                category: previous_token.category,
+                token_name,
+                kind: TokenChangeKind::Removed,
+                previous_value: Some(previous_token.value),
+                value: None,
+                delta_e: None,
+            }),
+        }
+    }
+    changes.extend(
+        current_tokens
+            .into_iter()
+            .filter(|(_, token)| !in_changed_group(token))
+            .map(|(token_name, token)| TokenChange {
+                category: token.category,
+                token_name,
+                kind: TokenChangeKind::Added,
+                previous_value: None,
+                value: Some(token.value),
+                delta_e: None,
+            }),
+    );
+    DesignSystemDiff { changes }
+}
+
+/// Groups (palettes or typography scales) added or removed
+fn group_changes(
+    category: DiffCategory,
+    previous_groups: &IndexMap<String, String>,
+    current_groups: &IndexMap<String, String>,
+) -> Vec<TokenChange> {
+    let removed = previous_groups
+        .iter()
+        .filter(|(name, _)| !current_groups.contains_key(*name))
+        .map(|(name, previous_value)| TokenChange {
+            category,
+            token_name: name.clone(),
+            kind: TokenChangeKind::Removed,
+            previous_value: Some(previous_value.clone()),
+            value: None,
+            delta_e: None,
+        });
+    let added = current_groups
+        .iter()
+        .filter(|(name, _)| !previous_groups.contains_key(*name))
+        .map(|(name, value)| TokenChange {
+            category,
+            token_name: name.clone(),
+            kind: TokenChangeKind::Added,
+            previous_value: None,
+            value: Some(value.clone()),
+            delta_e: None,
+        });
+    removed.chain(added).collect()
+}
+
+/// Palettes (palette-{name} -> tints summary)
+fn palette_groups(design_system: &DesignSystem) -> IndexMap<String, String> {
+    design_system
+        .palettes
+        .iter()
+        .map(|palette| {
+            (
+                format!("palette-{}", palette.palette_name),
+                match palette.tints.len() {
+                    1 => String::from("1 tint"),
+                    count => format!("{} tints", count),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Typography scales (typography-{scale} -> font size / line height)
+fn typography_groups(design_system: &DesignSystem) -> IndexMap<String, String> {
+    design_system
+        .typography
+        .scales()
+        .into_iter()
+        .map(|(scale_name, scale)| {
+            (
+                format!("typography-{}", scale_name),
+                format!(
+                    "{} / {}",
+                    measurement_to_css(&scale.font_size),
+                    measurement_to_css(&scale.line_height)
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Every token of the design system (token name -> token), the tokens referencing a color hold the
+/// referenced token name & its color
+fn design_system_tokens(design_system: &DesignSystem) -> IndexMap<String, DiffToken> {
+    let mut tokens: IndexMap<String, DiffToken> = IndexMap::new();
+    let color_token = |category: DiffCategory, color: &str| DiffToken {
+        category,
+        value: color.to_string(),
+        color: Rgb::parse(color).ok(),
+        group: None,
+    };
+    let reference_token = |category: DiffCategory, token_name: &str| {
+        let color: Option<Rgb> = resolve_color(design_system, token_name);
+        DiffToken {
+            category,
+            value: match &color {
+                Some(color) if color.to_hex() != token_name => {
+                    format!("{} ({})", token_name, color.to_hex())
+                }
+                _ => token_name.to_string(),
+            },
+            color,
+            group: None,
+        }
+    };
+    let value_token = |category: DiffCategory, value: String| DiffToken {
+        category,
+        value,
+        color: None,
+        group: None,
+    };
+
+    for palette in &design_system.palettes {
+        for tint in &palette.tints {
+            tokens.insert(
+                palette_token_name(palette, tint),
+                DiffToken {
+                    group: Some(format!("palette-{}", palette.palette_name)),
+                    ..color_token(DiffCategory::Tint, &tint.color)
+                },
+            );
+        }
+    }
+    for (name, color) in independant_color_tokens(&design_system.independant_colors) {
+        tokens.insert(name, color_token(DiffCategory::IndependantColor, &color));
+    }
+
+    let semantic_tokens = semantic_color_tokens(&design_system.semantic_color_tokens);
+    for (name, token_name) in &semantic_tokens {
+        tokens.insert(
+            name.clone(),
+            reference_token(DiffCategory::SemanticToken, token_name),
+        );
+    }
+    // Only the values which differ from the main theme
+    for theme_name in design_system.semantic_color_tokens.theme_overrides.keys() {
+        let theme_tokens =
+            semantic_color_tokens(&design_system.semantic_color_tokens.for_theme(theme_name));
+        for (name, token_name) in theme_tokens {
+            if !semantic_tokens.contains(&(name.clone(), token_name.clone())) {
+                tokens.insert(
+                    format!("themes.{}.{}", theme_name, name),
+                    reference_token(DiffCategory::SemanticToken, &token_name),
+                );
+            }
+        }
+    }
+
+    let themes = &design_system.themes;
+    for theme in themes.main_theme.iter().chain(&themes.other_themes) {
+        tokens.insert(
+            format!("themes.{}.background", theme.name),
+            color_token(DiffCategory::ThemeBackground, &theme.background),
+        );
+    }
+
+    tokens.insert(
+        String::from("font-default"),
+        value_token(DiffCategory::Font, design_system.fonts.default.clone()),
+    );
+    for font in &design_system.fonts.additionals {
+        tokens.insert(
+            format!("font-{}", font.font_name),
+            value_token(DiffCategory::Font, font.value.clone()),
+        );
+    }
+
+    for space in &design_system.spaces {
+        tokens.insert(
+            format!("space-{}", space.space_key),
+            value_token(DiffCategory::Space, measurement_to_css(&space.space_value)),
+        );
+    }
+
+    tokens.insert(
+        String::from("radius"),
+        value_token(
+            DiffCategory::Radius,
+            measurement_to_css(&design_system.radius.default),
+        ),
+    );
+    for radius in &design_system.radius.additionals_radius {
+        tokens.insert(
+            format!("radius-{}", radius.radius_key),
+            value_token(
+                DiffCategory::Radius,
+                measurement_to_css(&radius.radius_value),
+            ),
+        );
+    }
+
+    for shadows in &design_system.shadows {
+        tokens.insert(
+            format!("shadow-{}", shadows.shadow_name),
+            value_token(DiffCategory::Shadow, shadows_to_css(design_system, shadows)),
+        );
+    }
+
+    for (scale_name, scale) in design_system.typography.scales() {
+        for (property, value) in typography_properties(scale_name, scale, |name| name.to_string()) {
+            let token = if property == "color" {
+                reference_token(DiffCategory::TypographyScale, &value)
+            } else {
+                value_token(DiffCategory::TypographyScale, value)
+            };
+            tokens.insert(
+                format!("typography-{}.{}", scale_name, property),
+                DiffToken {
+                    group: Some(format!("typography-{}", scale_name)),
+                    ..token
+                },
+            );
+        }
+    }
+    tokens
+}
+
+/// Markdown report of the changes by category (ex: for a pull request comment)
+pub fn diff_markdown(diff: &DesignSystemDiff) -> String {
+    let mut markdown = String::from("# Design system changes\n");
+    if diff.changes.is_empty() {
+        markdown.push_str("\nNo token change\n");
+        return markdown;
+    }
+    for category in DiffCategory::ALL {
+        let changes: Vec<&TokenChange> = diff
+            .changes
+            .iter()
+            .filter(|change| change.category == category)
+            .collect();
+        if changes.is_empty() {
+            continue;
+        }
+        let _ = write!(
+            markdown,
+            "\n## {}\n\n\
+            | Token | Change | Before | After | ΔE 2000 |\n\
+            | --- | --- | --- | --- | --- |\n",
+            category.title()
+        );
+        for change in changes {
+            let _ = writeln!(
+                markdown,
+                "| {} | {} | {} | {} | {} |",
+                change.token_name,
+                match change.kind {
+                    TokenChangeKind::Added => "added",
+                    TokenChangeKind::Removed => "removed",
+                    TokenChangeKind::Changed => "changed",
+                },
+                change.previous_value.as_deref().unwrap_or(""),
+                change.value.as_deref().unwrap_or(""),
+                change
+                    .delta_e
+                    .map(|delta_e| format!("{:.2}", delta_e))
+                    .unwrap_or_default(),
+            );
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::design_system_domain::{Measurement, Space, UnitOfMeasurement},
+        test_fixtures::sample_design_system,
+    };
+
+    fn find_change<'a>(diff: &'a DesignSystemDiff, token_name: &str) -> Option<&'a TokenChange> {
+        diff.changes
+            .iter()
+            .find(|change| change.token_name == token_name)
+    }
+
+    #[test]
+    fn same_design_systems_have_no_change() {
+        let design_system = sample_design_system();
+        let diff = diff_design_systems(&design_system, &design_system);
+        assert!(diff.changes.is_empty());
+        assert!(diff_markdown(&diff).contains("No token change"));
+    }
+
+    #[test]
+    fn changed_colors_have_a_delta_e() {
+        let previous = sample_design_system();
+        let mut current = previous.clone();
+        current.palettes[0].tints[1].color = String::from("#2563eb");
+
+        let diff = diff_design_systems(&previous, &current);
+        let change = find_change(&diff, "palette-primary-500").unwrap();
+        assert_eq!(change.category, DiffCategory::Tint);
+        assert_eq!(change.kind, TokenChangeKind::Changed);
+        assert_eq!(change.previous_value.as_deref(), Some("#3b82f6"));
+        assert_eq!(change.value.as_deref(), Some("#2563eb"));
+        assert!(change.delta_e.unwrap() > 0.0);
+        assert!(
+            diff_markdown(&diff).contains("| palette-primary-500 | changed | #3b82f6 | #2563eb |")
+        );
+    }
+
+    #[test]
+    fn removed_palettes_are_listed_instead_of_their_tints() {
+        let previous = sample_design_system();
+        let mut current = previous.clone();
+        current.palettes.remove(0);
+
+        let diff = diff_design_systems(&previous, &current);
+        let change = find_change(&diff, "palette-primary").unwrap();
+        assert_eq!(change.category, DiffCategory::Palette);
+        assert_eq!(change.kind, TokenChangeKind::Removed);
+        assert_eq!(change.previous_value.as_deref(), Some("3 tints"));
+        assert!(diff
+            .changes
+            .iter()
+            .all(|change| change.category != DiffCategory::Tint));
+    }
+
+    #[test]
+    fn added_tokens_are_listed_last() {
+        let previous = sample_design_system();
+        let mut current = previous.clone();
+        current.spaces.push(Space {
+            space_key: String::from("3"),
+            space_value: Measurement {
+                unit: UnitOfMeasurement::PX,
+                value: 12.0,
+            },
+        });
+        current.spaces[0].space_value.value = 2.0;
+
+        let diff = diff_design_systems(&previous, &current);
+        assert_eq!(diff.changes.len(), 2);
+        assert_eq!(diff.changes[0].token_name, "space-1");
+        assert_eq!(diff.changes[0].kind, TokenChangeKind::Changed);
+        assert_eq!(diff.changes[0].delta_e, None);
+        assert_eq!(diff.changes[1].token_name, "space-3");
+        assert_eq!(diff.changes[1].kind, TokenChangeKind::Added);
+        assert_eq!(diff.changes[1].category, DiffCategory::Space);
+    }
+
+    #[test]
+    fn theme_overrides_are_compared() {
+        let previous = sample_design_system();
+        let mut current = previous.clone();
+        current.semantic_color_tokens.theme_overrides.clear();
+
+        let diff = diff_design_systems(&previous, &current);
+        assert!(!diff.changes.is_empty());
+        assert!(diff.changes.iter().all(|change| {
+            change.token_name.starts_with("themes.dark.")
+                && change.kind == TokenChangeKind::Removed
+                && change.category == DiffCategory::SemanticToken
+        }));
+    }
+}
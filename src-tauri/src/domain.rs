@@ -1,15 +1,27 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use serde::{Deserialize, Serialize, Serializer};
 
+pub mod audit_domain;
+pub mod changelog_domain;
+pub mod color_domain;
 pub mod color_picker_domain;
+pub mod color_vision_domain;
 pub mod design_system_domain;
+pub mod diff_domain;
+pub mod export_domain;
 pub mod fonts_domain;
 pub mod home_domain;
 pub mod image_domain;
+pub mod import_domain;
+pub mod lint_domain;
 pub mod palette_builder_domain;
+pub mod palette_generation_domain;
+pub mod reference_domain;
+pub mod theme_domain;
+pub mod version_domain;
 
-pub fn serialize_pathbuf_as_string<S>(path: &PathBuf, serializer: S) -> Result<S::Ok, S::Error>
+pub fn serialize_pathbuf_as_string<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// WCAG 2.x contrast ratios : body text (1.4.3 / 1.4.6) and non-text elements (1.4.11, AA only)
+pub const WCAG_TEXT_AA: f64 = 4.5;
+pub const WCAG_TEXT_AAA: f64 = 7.0;
+pub const WCAG_NON_TEXT_AA: f64 = 3.0;
+
+/// APCA Lc (absolute value) : body text (Bronze level Lc 75, preferred Lc 90) and non-text
+/// elements (Lc 30 minimum, Lc 45 for thin lines)
+pub const APCA_TEXT_AA: f64 = 75.0;
+pub const APCA_TEXT_AAA: f64 = 90.0;
+pub const APCA_NON_TEXT_AA: f64 = 30.0;
+pub const APCA_NON_TEXT_AAA: f64 = 45.0;
+
+/// Element drawn with the foreground color
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContrastUsage {
+    Text,
+    /// Borders (WCAG non-text contrast)
+    NonText,
+}
+
+/// Contrast of a semantic color token over its background, colors resolved in a theme
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContrastCheck {
+    /// Semantic token of the foreground (ex: base-text-default, button-hover-text)
+    pub token_name: String,
+    /// Semantic token of the background (ex: base-background), `theme-background` without one
+    pub background_token_name: String,
+    pub foreground: String,
+    pub background: String,
+    pub usage: ContrastUsage,
+    pub wcag_ratio: f64,
+    pub wcag_aa: bool,
+    pub wcag_aaa: bool,
+    pub apca_lc: f64,
+    pub apca_aa: bool,
+    pub apca_aaa: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeContrastAudit {
+    pub theme_name: String,
+    pub checks: Vec<ContrastCheck>,
+    /// Referenced tokens without color (deleted tint, invalid color...)
+    pub unresolved_tokens: Vec<String>,
+}
+
+/// Contrast audit of the semantic color tokens, main theme first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContrastAudit {
+    pub themes: Vec<ThemeContrastAudit>,
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    diff_domain::{DiffCategory, TokenChange},
+    version_domain::DesignSystemVersion,
+};
+
+/// Semantic versioning bump of the exports : a removed or renamed token breaks the consumers, an
+/// added token is a new feature, a changed value is a fix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SemverBump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl SemverBump {
+    /// Version following `version_name` (`1.2.3` or `v1.2.3`, the prefix is kept), `None` when the
+    /// name is not a semantic version
+    pub fn apply(&self, version_name: &str) -> Option<String> {
+        let (prefix, [major, minor, patch]) = parse_semver(version_name)?;
+        let [major, minor, patch] = match self {
+            SemverBump::Major => [major + 1, 0, 0],
+            SemverBump::Minor => [major, minor + 1, 0],
+            SemverBump::Patch => [major, minor, patch + 1],
+        };
+        Some(format!("{}{}.{}.{}", prefix, major, minor, patch))
+    }
+}
+
+/// (prefix, [major, minor, patch]) of `1.2.3` or `v1.2.3`
+pub fn parse_semver(version_name: &str) -> Option<(&str, [u64; 3])> {
+    let (prefix, version) = match version_name.strip_prefix('v') {
+        Some(version) => ("v", version),
+        None => ("", version_name),
+    };
+    let mut numbers = version.split('.').map(|number| number.parse::<u64>().ok());
+    let major = numbers.next()??;
+    let minor = numbers.next()??;
+    let patch = numbers.next()??;
+    if numbers.next().is_some() {
+        return None;
+    }
+    Some((prefix, [major, minor, patch]))
+}
+
+/// Token (or palette, typography scale) removed and added again with the same value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRename {
+    pub category: DiffCategory,
+    pub previous_token_name: String,
+    pub token_name: String,
+}
+
+/// Token changes between two versions, the renames are not listed as removed & added
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionChanges {
+    pub added: Vec<TokenChange>,
+    pub removed: Vec<TokenChange>,
+    pub renamed: Vec<TokenRename>,
+    pub changed: Vec<TokenChange>,
+}
+
+impl VersionChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// `None` without change
+    pub fn bump(&self) -> Option<SemverBump> {
+        if !self.removed.is_empty() || !self.renamed.is_empty() {
+            Some(SemverBump::Major)
+        } else if !self.added.is_empty() {
+            Some(SemverBump::Minor)
+        } else if !self.changed.is_empty() {
+            Some(SemverBump::Patch)
+        } else {
+            None
+        }
+    }
+}
+
+/// Changes of a version since the previous one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogEntry {
+    /// `None` for the saved design system changes not yet in a version
+    pub version: Option<DesignSystemVersion>,
+    /// `None` for the first version
+    pub previous_version_name: Option<String>,
+    pub changes: Option<VersionChanges>,
+    pub bump: Option<SemverBump>,
+    /// Previous version name with the bump applied, when it is a semantic version
+    pub suggested_version: Option<String>,
+}
+
+impl ChangelogEntry {
+    /// The version is named as a semantic version lower than the suggested one (ex: breaking
+    /// changes released as 1.3.0 after 1.2.0)
+    pub fn is_under_bumped(&self) -> bool {
+        let version_name: &str = match &self.version {
+            Some(version) => &version.name,
+            None => return false,
+        };
+        match (
+            parse_semver(version_name),
+            self.suggested_version.as_deref().and_then(parse_semver),
+        ) {
+            (Some((_, version)), Some((_, suggested_version))) => version < suggested_version,
+            _ => false,
+        }
+    }
+}
+
+/// `CHANGELOG.md` content of a design system, newest entry first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Changelog {
+    pub entries: Vec<ChangelogEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(version_name: &str, suggested_version: Option<&str>) -> ChangelogEntry {
+        ChangelogEntry {
+            version: Some(DesignSystemVersion {
+                name: version_name.to_string(),
+                description: None,
+                update_date: String::from("2024-01-01T00:00:00Z"),
+            }),
+            previous_version_name: Some(String::from("1.2.0")),
+            changes: None,
+            bump: Some(SemverBump::Major),
+            suggested_version: suggested_version.map(String::from),
+        }
+    }
+
+    #[test]
+    fn parses_semantic_versions() {
+        assert_eq!(parse_semver("1.2.3"), Some(("", [1, 2, 3])));
+        assert_eq!(parse_semver("v0.10.0"), Some(("v", [0, 10, 0])));
+        assert_eq!(parse_semver("1.2"), None);
+        assert_eq!(parse_semver("1.2.3.4"), None);
+        assert_eq!(parse_semver("v2-beta"), None);
+    }
+
+    #[test]
+    fn bumps_keep_the_prefix() {
+        assert_eq!(SemverBump::Major.apply("v1.2.3").as_deref(), Some("v2.0.0"));
+        assert_eq!(SemverBump::Minor.apply("1.2.3").as_deref(), Some("1.3.0"));
+        assert_eq!(SemverBump::Patch.apply("1.2.3").as_deref(), Some("1.2.4"));
+        assert_eq!(SemverBump::Patch.apply("beta"), None);
+    }
+
+    #[test]
+    fn under_bumped_versions() {
+        assert!(entry("1.3.0", Some("2.0.0")).is_under_bumped());
+        assert!(!entry("2.0.0", Some("2.0.0")).is_under_bumped());
+        assert!(!entry("v3.0.0", Some("2.0.0")).is_under_bumped());
+        assert!(!entry("beta", Some("2.0.0")).is_under_bumped());
+        assert!(!entry("1.3.0", None).is_under_bumped());
+    }
+}
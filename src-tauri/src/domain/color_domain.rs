@@ -0,0 +1,1685 @@
+use std::f64::consts::PI;
+
+use anyhow::{anyhow, Context, Result};
+
+/// A sRGB color, channels between 0 and 1 (gamma encoded). A color parsed from a wider gamut
+/// (`display-p3`, `oklch`...) keeps its channels out of the range, `clamp` maps it into sRGB
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgb {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub alpha: f64,
+}
+
+/// HSL color : hue in degrees, saturation & lightness between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+/// OKLab color (L between 0 and 1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// OKHSL color : hue in degrees, saturation & lightness between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Okhsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+/// OKLCH color : OKLab in polar coordinates, hue in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+/// CIELAB color (D50 white point, as in css)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// CIE LCh color : CIELAB in polar coordinates, hue in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+/// Display P3 color, channels between 0 and 1 (gamma encoded, as `color(display-p3 r g b)`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayP3 {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+/// HSLuv color : hue in degrees, saturation & lightness between 0 and 100
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsluv {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+impl Rgb {
+    pub fn new(r: f64, g: f64, b: f64) -> Rgb {
+        Rgb {
+            r,
+            g,
+            b,
+            alpha: 1.0,
+        }
+    }
+
+    /// Parse a css color : hex (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`), named colors, `transparent`,
+    /// `rgb()`, `hsl()`, `hwb()`, `lab()`, `lch()`, `oklab()`, `oklch()` and `color()` (`srgb`,
+    /// `srgb-linear`, `display-p3`, `xyz-d50`, `xyz-d65`)
+    pub fn parse(value: &str) -> Result<Rgb> {
+        let value = value.trim().to_lowercase();
+        if let Some(hex) = value.strip_prefix('#') {
+            return Rgb::from_hex(hex);
+        }
+        if value == "transparent" {
+            return Ok(Rgb {
+                alpha: 0.0,
+                ..Rgb::new(0.0, 0.0, 0.0)
+            });
+        }
+        if let Some((_, hex)) = NAMED_COLORS.iter().find(|(name, _)| *name == value) {
+            return Rgb::from_hex(hex);
+        }
+        let (name, args) =
+            function_arguments(&value).ok_or_else(|| anyhow!("Unsupported color : {}", value))?;
+        let color = match name {
+            "rgb" | "rgba" => parse_rgb_arguments(&args),
+            "hsl" | "hsla" => parse_hsl_arguments(&args),
+            "hwb" => parse_hwb_arguments(&args),
+            "lab" => parse_lab_arguments(&args),
+            "lch" => parse_lch_arguments(&args),
+            "oklab" => parse_oklab_arguments(&args),
+            "oklch" => parse_oklch_arguments(&args),
+            "color" => parse_color_arguments(&args),
+            _ => Err(anyhow!("Unsupported color function : {}", name)),
+        };
+        color.with_context(|| format!("Invalid color : {}", value))
+    }
+
+    fn from_hex(hex: &str) -> Result<Rgb> {
+        // The channels are sliced by byte index
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Invalid hex color : #{}", hex));
+        }
+        let expanded: String = match hex.len() {
+            3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 | 8 => hex.to_string(),
+            _ => return Err(anyhow!("Invalid hex color : #{}", hex)),
+        };
+        let channel = |index: usize| -> Result<f64> {
+            let value = u8::from_str_radix(&expanded[index..index + 2], 16)
+                .with_context(|| format!("Invalid hex color : #{}", hex))?;
+            Ok(value as f64 / 255.0)
+        };
+        Ok(Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+            alpha: if expanded.len() == 8 {
+                channel(6)?
+            } else {
+                1.0
+            },
+        })
+    }
+
+    pub fn clamp(&self) -> Rgb {
+        Rgb {
+            r: self.r.clamp(0.0, 1.0),
+            g: self.g.clamp(0.0, 1.0),
+            b: self.b.clamp(0.0, 1.0),
+            alpha: self.alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Channels as bytes (clamped into the sRGB gamut)
+    pub fn to_bytes(&self) -> [u8; 3] {
+        let rgb = self.clamp();
+        [
+            (rgb.r * 255.0).round() as u8,
+            (rgb.g * 255.0).round() as u8,
+            (rgb.b * 255.0).round() as u8,
+        ]
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` when the color is translucent
+    pub fn to_hex(&self) -> String {
+        let [r, g, b] = self.to_bytes();
+        if self.alpha < 1.0 {
+            let alpha = (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+            format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, alpha)
+        } else {
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        }
+    }
+
+    /// `AARRGGBB`, as used by the Android color resources and Compose `Color(0xAARRGGBB)`
+    pub fn to_argb_hex(&self) -> String {
+        let [r, g, b] = self.to_bytes();
+        let alpha = (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        format!("{:02X}{:02X}{:02X}{:02X}", alpha, r, g, b)
+    }
+
+    pub fn to_css_rgba(&self) -> String {
+        let [r, g, b] = self.to_bytes();
+        format!(
+            "rgba({}, {}, {}, {})",
+            r,
+            g,
+            b,
+            (self.alpha.clamp(0.0, 1.0) * 1000.0).round() / 1000.0
+        )
+    }
+
+    pub fn to_linear(&self) -> [f64; 3] {
+        [
+            srgb_transfer_inv(self.r),
+            srgb_transfer_inv(self.g),
+            srgb_transfer_inv(self.b),
+        ]
+    }
+
+    pub fn from_linear(linear: [f64; 3], alpha: f64) -> Rgb {
+        Rgb {
+            r: srgb_transfer(linear[0]),
+            g: srgb_transfer(linear[1]),
+            b: srgb_transfer(linear[2]),
+            alpha,
+        }
+    }
+
+    pub fn to_oklab(&self) -> Oklab {
+        linear_srgb_to_oklab(self.to_linear())
+    }
+
+    pub fn from_oklab(oklab: &Oklab, alpha: f64) -> Rgb {
+        Rgb::from_linear(oklab_to_linear_srgb(oklab), alpha)
+    }
+
+    pub fn to_okhsl(&self) -> Okhsl {
+        let lab = self.to_oklab();
+        let chroma = (lab.a * lab.a + lab.b * lab.b).sqrt();
+        let l = toe(lab.l);
+        if chroma < 1e-7 || lab.l <= 0.0 || lab.l >= 1.0 {
+            return Okhsl {
+                h: 0.0,
+                s: 0.0,
+                l: l.clamp(0.0, 1.0),
+            };
+        }
+        let a_ = lab.a / chroma;
+        let b_ = lab.b / chroma;
+        let h = 0.5 + 0.5 * (-lab.b).atan2(-lab.a) / PI;
+
+        let (c_0, c_mid, c_max) = get_cs(lab.l, a_, b_);
+        let mid = 0.8;
+        let mid_inv = 1.25;
+        let s = if chroma < c_mid {
+            let k_1 = mid * c_0;
+            let k_2 = 1.0 - k_1 / c_mid;
+            let t = chroma / (k_1 + k_2 * chroma);
+            t * mid
+        } else {
+            let k_0 = c_mid;
+            let k_1 = (1.0 - mid) * c_mid * c_mid * mid_inv * mid_inv / c_0;
+            let k_2 = 1.0 - k_1 / (c_max - c_mid);
+            let t = (chroma - k_0) / (k_1 + k_2 * (chroma - k_0));
+            mid + (1.0 - mid) * t
+        };
+
+        Okhsl {
+            h: (h * 360.0).rem_euclid(360.0),
+            s: s.clamp(0.0, 1.0),
+            l,
+        }
+    }
+
+    pub fn from_okhsl(okhsl: &Okhsl, alpha: f64) -> Rgb {
+        let Okhsl { h, s, l } = *okhsl;
+        if l >= 1.0 {
+            return Rgb {
+                alpha,
+                ..Rgb::new(1.0, 1.0, 1.0)
+            };
+        }
+        if l <= 0.0 {
+            return Rgb {
+                alpha,
+                ..Rgb::new(0.0, 0.0, 0.0)
+            };
+        }
+        let a_ = (h / 360.0 * 2.0 * PI).cos();
+        let b_ = (h / 360.0 * 2.0 * PI).sin();
+        let lightness = toe_inv(l);
+        if s <= 0.0 {
+            return Rgb::from_oklab(
+                &Oklab {
+                    l: lightness,
+                    a: 0.0,
+                    b: 0.0,
+                },
+                alpha,
+            );
+        }
+
+        let (c_0, c_mid, c_max) = get_cs(lightness, a_, b_);
+        let mid = 0.8;
+        let mid_inv = 1.25;
+        let chroma = if s < mid {
+            let t = mid_inv * s;
+            let k_1 = mid * c_0;
+            let k_2 = 1.0 - k_1 / c_mid;
+            t * k_1 / (1.0 - k_2 * t)
+        } else {
+            let t = (s - mid) / (1.0 - mid);
+            let k_0 = c_mid;
+            let k_1 = (1.0 - mid) * c_mid * c_mid * mid_inv * mid_inv / c_0;
+            let k_2 = 1.0 - k_1 / (c_max - c_mid);
+            k_0 + t * k_1 / (1.0 - k_2 * t)
+        };
+
+        Rgb::from_oklab(
+            &Oklab {
+                l: lightness,
+                a: chroma * a_,
+                b: chroma * b_,
+            },
+            alpha,
+        )
+    }
+
+    /// The hue of an achromatic color is 0
+    pub fn to_hsl(&self) -> Hsl {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let l = (max + min) / 2.0;
+        let chroma = max - min;
+        if chroma < 1e-8 {
+            return Hsl { h: 0.0, s: 0.0, l };
+        }
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (max - l) / l.min(1.0 - l)
+        };
+        let h = if max == self.r {
+            (self.g - self.b) / chroma + if self.g < self.b { 6.0 } else { 0.0 }
+        } else if max == self.g {
+            (self.b - self.r) / chroma + 2.0
+        } else {
+            (self.r - self.g) / chroma + 4.0
+        };
+        Hsl {
+            h: (h * 60.0).rem_euclid(360.0),
+            s,
+            l,
+        }
+    }
+
+    pub fn from_hsl(hsl: &Hsl, alpha: f64) -> Rgb {
+        let Hsl { h, s, l } = *hsl;
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        Rgb {
+            r: r + m,
+            g: g + m,
+            b: b + m,
+            alpha,
+        }
+    }
+
+    pub fn to_oklch(&self) -> Oklch {
+        self.to_oklab().to_oklch()
+    }
+
+    pub fn from_oklch(oklch: &Oklch, alpha: f64) -> Rgb {
+        Rgb::from_oklab(&oklch.to_oklab(), alpha)
+    }
+
+    pub fn to_lab(&self) -> Lab {
+        let [x, y, z] = transform(&D65_TO_D50, self.to_xyz());
+        let f = |t: f64| -> f64 {
+            if t > LAB_EPSILON {
+                t.cbrt()
+            } else {
+                (LAB_KAPPA * t + 16.0) / 116.0
+            }
+        };
+        let fx = f(x / D50_WHITE[0]);
+        let fy = f(y / D50_WHITE[1]);
+        let fz = f(z / D50_WHITE[2]);
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+
+    pub fn from_lab(lab: &Lab, alpha: f64) -> Rgb {
+        let fy = (lab.l + 16.0) / 116.0;
+        let fx = lab.a / 500.0 + fy;
+        let fz = fy - lab.b / 200.0;
+        let f_inv = |t: f64| -> f64 {
+            if t.powi(3) > LAB_EPSILON {
+                t.powi(3)
+            } else {
+                (116.0 * t - 16.0) / LAB_KAPPA
+            }
+        };
+        let y = if lab.l > LAB_KAPPA * LAB_EPSILON {
+            fy.powi(3)
+        } else {
+            lab.l / LAB_KAPPA
+        };
+        let xyz_d50 = [
+            f_inv(fx) * D50_WHITE[0],
+            y * D50_WHITE[1],
+            f_inv(fz) * D50_WHITE[2],
+        ];
+        Rgb::from_xyz(transform(&D50_TO_D65, xyz_d50), alpha)
+    }
+
+    pub fn to_lch(&self) -> Lch {
+        self.to_lab().to_lch()
+    }
+
+    pub fn from_lch(lch: &Lch, alpha: f64) -> Rgb {
+        Rgb::from_lab(&lch.to_lab(), alpha)
+    }
+
+    /// Same color in the Display P3 space, a sRGB color is always inside the P3 gamut
+    pub fn to_display_p3(&self) -> DisplayP3 {
+        let [r, g, b] = transform(&XYZ_TO_LINEAR_P3, self.to_xyz());
+        DisplayP3 {
+            r: srgb_transfer(r),
+            g: srgb_transfer(g),
+            b: srgb_transfer(b),
+        }
+    }
+
+    /// Display P3 uses the sRGB transfer function, the saturated P3 colors are out of the sRGB gamut
+    pub fn from_display_p3(display_p3: &DisplayP3, alpha: f64) -> Rgb {
+        let linear = [
+            srgb_transfer_inv(display_p3.r),
+            srgb_transfer_inv(display_p3.g),
+            srgb_transfer_inv(display_p3.b),
+        ];
+        Rgb::from_xyz(transform(&LINEAR_P3_TO_XYZ, linear), alpha)
+    }
+
+    pub fn to_hsluv(&self) -> Hsluv {
+        let (l, c, h) = xyz_to_lchuv(self.to_xyz());
+        if l > 99.9999999 {
+            return Hsluv {
+                h,
+                s: 0.0,
+                l: 100.0,
+            };
+        }
+        if l < 1e-8 {
+            return Hsluv { h, s: 0.0, l: 0.0 };
+        }
+        Hsluv {
+            h,
+            s: c / max_chroma_for_lh(l, h) * 100.0,
+            l,
+        }
+    }
+
+    pub fn from_hsluv(hsluv: &Hsluv, alpha: f64) -> Rgb {
+        let Hsluv { h, s, l } = *hsluv;
+        if l > 99.9999999 {
+            return Rgb {
+                alpha,
+                ..Rgb::new(1.0, 1.0, 1.0)
+            };
+        }
+        if l < 1e-8 {
+            return Rgb {
+                alpha,
+                ..Rgb::new(0.0, 0.0, 0.0)
+            };
+        }
+        let c = max_chroma_for_lh(l, h) / 100.0 * s;
+        Rgb::from_xyz(lchuv_to_xyz(l, c, h), alpha)
+    }
+
+    /// CIE XYZ (D65 white point)
+    pub fn to_xyz(&self) -> [f64; 3] {
+        transform(&LINEAR_SRGB_TO_XYZ, self.to_linear())
+    }
+
+    pub fn from_xyz(xyz: [f64; 3], alpha: f64) -> Rgb {
+        Rgb::from_linear(transform(&XYZ_TO_LINEAR_SRGB, xyz), alpha)
+    }
+
+    /// Map a color out of the sRGB gamut by reducing its OKLCH chroma until the clipped color is
+    /// not distinguishable (CSS Color 4 gamut mapping)
+    pub fn to_gamut(&self) -> Rgb {
+        if self.is_in_gamut() {
+            return self.clamp();
+        }
+        let origin = self.to_oklch();
+        if origin.l >= 1.0 {
+            return Rgb {
+                alpha: self.alpha,
+                ..Rgb::new(1.0, 1.0, 1.0)
+            }
+            .clamp();
+        }
+        if origin.l <= 0.0 {
+            return Rgb {
+                alpha: self.alpha,
+                ..Rgb::new(0.0, 0.0, 0.0)
+            }
+            .clamp();
+        }
+        let delta_e_ok = |first: &Oklab, second: &Oklab| -> f64 {
+            ((first.l - second.l).powi(2)
+                + (first.a - second.a).powi(2)
+                + (first.b - second.b).powi(2))
+            .sqrt()
+        };
+        let mut clipped = self.clamp();
+        if delta_e_ok(&clipped.to_oklab(), &origin.to_oklab()) < GAMUT_JND {
+            return clipped;
+        }
+        let mut min = 0.0;
+        let mut max = origin.c;
+        let mut min_in_gamut = true;
+        while max - min > GAMUT_EPSILON {
+            let current = Oklch {
+                c: (min + max) / 2.0,
+                ..origin
+            };
+            let color = Rgb::from_oklch(&current, self.alpha);
+            if min_in_gamut && color.is_in_gamut() {
+                min = current.c;
+                continue;
+            }
+            clipped = color.clamp();
+            let delta_e = delta_e_ok(&clipped.to_oklab(), &current.to_oklab());
+            if delta_e < GAMUT_JND {
+                if GAMUT_JND - delta_e < GAMUT_EPSILON {
+                    break;
+                }
+                min_in_gamut = false;
+                min = current.c;
+            } else {
+                max = current.c;
+            }
+        }
+        clipped
+    }
+
+    /// Color seen over an opaque backdrop (alpha compositing)
+    pub fn blend_over(&self, backdrop: &Rgb) -> Rgb {
+        let alpha = self.alpha.clamp(0.0, 1.0);
+        let mix = |channel: f64, backdrop_channel: f64| {
+            channel * alpha + backdrop_channel * (1.0 - alpha)
+        };
+        Rgb::new(
+            mix(self.r, backdrop.r),
+            mix(self.g, backdrop.g),
+            mix(self.b, backdrop.b),
+        )
+    }
+
+    /// WCAG 2.x relative luminance
+    pub fn relative_luminance(&self) -> f64 {
+        let [r, g, b] = self.clamp().to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Channels inside the sRGB gamut (with a tolerance for the rounding of the conversions)
+    pub fn is_in_gamut(&self) -> bool {
+        let tolerance = 1e-4;
+        [self.r, self.g, self.b]
+            .iter()
+            .all(|channel| *channel >= -tolerance && *channel <= 1.0 + tolerance)
+    }
+}
+
+impl Oklab {
+    pub fn to_oklch(&self) -> Oklch {
+        let (c, h) = to_polar(self.a, self.b);
+        Oklch { l: self.l, c, h }
+    }
+}
+
+impl Oklch {
+    pub fn to_oklab(&self) -> Oklab {
+        let (a, b) = from_polar(self.c, self.h);
+        Oklab { l: self.l, a, b }
+    }
+}
+
+impl Lab {
+    pub fn to_lch(&self) -> Lch {
+        let (c, h) = to_polar(self.a, self.b);
+        Lch { l: self.l, c, h }
+    }
+}
+
+impl Lch {
+    pub fn to_lab(&self) -> Lab {
+        let (a, b) = from_polar(self.c, self.h);
+        Lab { l: self.l, a, b }
+    }
+}
+
+/// Chroma & hue (degrees) of the a / b axes, the hue of an achromatic color is 0
+fn to_polar(a: f64, b: f64) -> (f64, f64) {
+    let chroma = (a * a + b * b).sqrt();
+    let hue = if chroma < 1e-8 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    };
+    (chroma, hue)
+}
+
+fn from_polar(chroma: f64, hue: f64) -> (f64, f64) {
+    let hue = hue.to_radians();
+    (chroma * hue.cos(), chroma * hue.sin())
+}
+
+/// WCAG 2.x contrast ratio, between 1 and 21 (opaque colors)
+pub fn contrast_ratio(first: &Rgb, second: &Rgb) -> f64 {
+    let first = first.relative_luminance();
+    let second = second.relative_luminance();
+    (first.max(second) + 0.05) / (first.min(second) + 0.05)
+}
+
+/// APCA lightness contrast (Lc, APCA-W3 0.0.98G) of a text over a background (opaque colors) :
+/// positive for dark text on light background, negative for light text on dark background
+pub fn apca_contrast(text: &Rgb, background: &Rgb) -> f64 {
+    let screen_luminance = |color: &Rgb| -> f64 {
+        let color = color.clamp();
+        let luminance = 0.2126729 * color.r.powf(APCA_TRC)
+            + 0.7151522 * color.g.powf(APCA_TRC)
+            + 0.0721750 * color.b.powf(APCA_TRC);
+        if luminance >= APCA_BLACK_THRESHOLD {
+            luminance
+        } else {
+            luminance + (APCA_BLACK_THRESHOLD - luminance).powf(APCA_BLACK_CLAMP)
+        }
+    };
+    let text_y = screen_luminance(text);
+    let background_y = screen_luminance(background);
+    if (background_y - text_y).abs() < APCA_DELTA_Y_MIN {
+        return 0.0;
+    }
+    let contrast = if background_y > text_y {
+        let sapc = (background_y.powf(0.56) - text_y.powf(0.57)) * APCA_SCALE;
+        if sapc < APCA_LOW_CLIP {
+            0.0
+        } else {
+            sapc - APCA_LOW_OFFSET
+        }
+    } else {
+        let sapc = (background_y.powf(0.65) - text_y.powf(0.62)) * APCA_SCALE;
+        if sapc > -APCA_LOW_CLIP {
+            0.0
+        } else {
+            sapc + APCA_LOW_OFFSET
+        }
+    };
+    contrast * 100.0
+}
+
+/// Euclidean distance in CIELAB (ΔE 1976)
+pub fn delta_e_76(first: &Rgb, second: &Rgb) -> f64 {
+    let first = first.to_lab();
+    let second = second.to_lab();
+    ((first.l - second.l).powi(2) + (first.a - second.a).powi(2) + (first.b - second.b).powi(2))
+        .sqrt()
+}
+
+/// CIEDE2000 color difference (ΔE 2000), with the reference weighting factors kL = kC = kH = 1
+pub fn delta_e_2000(first: &Rgb, second: &Rgb) -> f64 {
+    let first = first.to_lab();
+    let second = second.to_lab();
+
+    let mean_chroma = (first.a.hypot(first.b) + second.a.hypot(second.b)) / 2.0;
+    let g = 0.5 * (1.0 - (mean_chroma.powi(7) / (mean_chroma.powi(7) + 25f64.powi(7))).sqrt());
+    let a1 = first.a * (1.0 + g);
+    let a2 = second.a * (1.0 + g);
+    let c1 = a1.hypot(first.b);
+    let c2 = a2.hypot(second.b);
+    let hue = |a: f64, b: f64| -> f64 {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1 = hue(a1, first.b);
+    let h2 = hue(a2, second.b);
+
+    let delta_l = second.l - first.l;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let delta_big_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    let mean_l = (first.l + second.l) / 2.0;
+    let mean_c = (c1 + c2) / 2.0;
+    let mean_h = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) / 2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) / 2.0
+    } else {
+        (h1 + h2 - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (mean_h - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * mean_h).to_radians().cos()
+        + 0.32 * (3.0 * mean_h + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * mean_h - 63.0).to_radians().cos();
+    let delta_theta = 30.0 * (-((mean_h - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (mean_c.powi(7) / (mean_c.powi(7) + 25f64.powi(7))).sqrt();
+    let s_l = 1.0 + (0.015 * (mean_l - 50.0).powi(2)) / (20.0 + (mean_l - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * mean_c;
+    let s_h = 1.0 + 0.015 * mean_c * t;
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    ((delta_l / s_l).powi(2)
+        + (delta_c / s_c).powi(2)
+        + (delta_big_h / s_h).powi(2)
+        + r_t * (delta_c / s_c) * (delta_big_h / s_h))
+        .sqrt()
+}
+
+/// Name & arguments of a css function (`rgb(0 0 0 / 50%)` -> `("rgb", ["0", "0", "0", "50%"])`)
+fn function_arguments(value: &str) -> Option<(&str, Vec<String>)> {
+    let open = value.find('(')?;
+    if !value.ends_with(')') {
+        return None;
+    }
+    Some((
+        value[..open].trim(),
+        value[open + 1..value.len() - 1]
+            .replace([',', '/'], " ")
+            .split_whitespace()
+            .map(String::from)
+            .collect(),
+    ))
+}
+
+/// Number, or percentage of `percent_reference` (`none` is 0)
+fn parse_number(value: &str, percent_reference: f64) -> Result<f64> {
+    if value == "none" {
+        return Ok(0.0);
+    }
+    match value.strip_suffix('%') {
+        Some(percent) => Ok(percent.parse::<f64>()? / 100.0 * percent_reference),
+        None => Ok(value.parse::<f64>()?),
+    }
+}
+
+/// Hue in degrees : unitless, `deg`, `grad`, `rad` or `turn`
+fn parse_hue(value: &str) -> Result<f64> {
+    if value == "none" {
+        return Ok(0.0);
+    }
+    let units: [(&str, f64); 4] = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / PI),
+        ("turn", 360.0),
+    ];
+    for (unit, factor) in units {
+        if let Some(number) = value.strip_suffix(unit) {
+            return Ok(number.parse::<f64>()? * factor);
+        }
+    }
+    Ok(value.parse::<f64>()?)
+}
+
+fn parse_alpha(args: &[String], index: usize) -> Result<f64> {
+    match args.get(index) {
+        Some(alpha) => parse_number(alpha, 1.0),
+        None => Ok(1.0),
+    }
+}
+
+/// Three channels & an optional alpha
+fn check_arguments(args: &[String], function: &str) -> Result<()> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err(anyhow!("Invalid {} color : {:?}", function, args));
+    }
+    Ok(())
+}
+
+fn parse_rgb_arguments(args: &[String]) -> Result<Rgb> {
+    check_arguments(args, "rgb")?;
+    let channel = |arg: &String| -> Result<f64> { Ok(parse_number(arg, 255.0)? / 255.0) };
+    Ok(Rgb {
+        r: channel(&args[0])?,
+        g: channel(&args[1])?,
+        b: channel(&args[2])?,
+        alpha: parse_alpha(args, 3)?,
+    })
+}
+
+/// `hsl(210 50% 40%)`, `hsl(210deg, 50%, 40%, 0.5)`
+fn parse_hsl_arguments(args: &[String]) -> Result<Rgb> {
+    check_arguments(args, "hsl")?;
+    let saturation = parse_number(args[1].trim_end_matches('%'), 1.0)? / 100.0;
+    let lightness = parse_number(args[2].trim_end_matches('%'), 1.0)? / 100.0;
+    let hsl = Hsl {
+        h: parse_hue(&args[0])?,
+        s: saturation.clamp(0.0, 1.0),
+        l: lightness.clamp(0.0, 1.0),
+    };
+    Ok(Rgb::from_hsl(&hsl, parse_alpha(args, 3)?))
+}
+
+/// `hwb(210 10% 20%)` : the pure hue mixed with white & black
+fn parse_hwb_arguments(args: &[String]) -> Result<Rgb> {
+    check_arguments(args, "hwb")?;
+    let hue = parse_hue(&args[0])?;
+    let mut whiteness = (parse_number(args[1].trim_end_matches('%'), 1.0)? / 100.0).clamp(0.0, 1.0);
+    let mut blackness = (parse_number(args[2].trim_end_matches('%'), 1.0)? / 100.0).clamp(0.0, 1.0);
+    if whiteness + blackness >= 1.0 {
+        let sum = whiteness + blackness;
+        whiteness /= sum;
+        blackness /= sum;
+    }
+    let pure = Rgb::from_hsl(
+        &Hsl {
+            h: hue,
+            s: 1.0,
+            l: 0.5,
+        },
+        1.0,
+    );
+    let mix = |channel: f64| channel * (1.0 - whiteness - blackness) + whiteness;
+    Ok(Rgb {
+        r: mix(pure.r),
+        g: mix(pure.g),
+        b: mix(pure.b),
+        alpha: parse_alpha(args, 3)?,
+    })
+}
+
+/// `lab(54% 40 -30)` : 100% is L 100, a & b 125
+fn parse_lab_arguments(args: &[String]) -> Result<Rgb> {
+    check_arguments(args, "lab")?;
+    let lab = Lab {
+        l: parse_number(&args[0], 100.0)?,
+        a: parse_number(&args[1], 125.0)?,
+        b: parse_number(&args[2], 125.0)?,
+    };
+    Ok(Rgb::from_lab(&lab, parse_alpha(args, 3)?))
+}
+
+/// `lch(54% 50 320)` : 100% is L 100, C 150
+fn parse_lch_arguments(args: &[String]) -> Result<Rgb> {
+    check_arguments(args, "lch")?;
+    let lch = Lch {
+        l: parse_number(&args[0], 100.0)?,
+        c: parse_number(&args[1], 150.0)?,
+        h: parse_hue(&args[2])?,
+    };
+    Ok(Rgb::from_lch(&lch, parse_alpha(args, 3)?))
+}
+
+/// `oklab(0.6 0.1 -0.1)` : 100% is L 1, a & b 0.4
+fn parse_oklab_arguments(args: &[String]) -> Result<Rgb> {
+    check_arguments(args, "oklab")?;
+    let oklab = Oklab {
+        l: parse_number(&args[0], 1.0)?,
+        a: parse_number(&args[1], 0.4)?,
+        b: parse_number(&args[2], 0.4)?,
+    };
+    Ok(Rgb::from_oklab(&oklab, parse_alpha(args, 3)?))
+}
+
+/// `oklch(60% 0.15 250)` : 100% is L 1, C 0.4
+fn parse_oklch_arguments(args: &[String]) -> Result<Rgb> {
+    check_arguments(args, "oklch")?;
+    let oklch = Oklch {
+        l: parse_number(&args[0], 1.0)?,
+        c: parse_number(&args[1], 0.4)?,
+        h: parse_hue(&args[2])?,
+    };
+    Ok(Rgb::from_oklch(&oklch, parse_alpha(args, 3)?))
+}
+
+/// `color(display-p3 1 0.5 0 / 50%)`
+fn parse_color_arguments(args: &[String]) -> Result<Rgb> {
+    let (space, channels) = match args.split_first() {
+        Some(split) => split,
+        None => return Err(anyhow!("Missing color space")),
+    };
+    check_arguments(channels, "color()")?;
+    let values = [
+        parse_number(&channels[0], 1.0)?,
+        parse_number(&channels[1], 1.0)?,
+        parse_number(&channels[2], 1.0)?,
+    ];
+    let alpha = parse_alpha(channels, 3)?;
+    match space.as_str() {
+        "srgb" => Ok(Rgb {
+            alpha,
+            ..Rgb::new(values[0], values[1], values[2])
+        }),
+        "srgb-linear" => Ok(Rgb::from_linear(values, alpha)),
+        "display-p3" => Ok(Rgb::from_display_p3(
+            &DisplayP3 {
+                r: values[0],
+                g: values[1],
+                b: values[2],
+            },
+            alpha,
+        )),
+        "xyz" | "xyz-d65" => Ok(Rgb::from_xyz(values, alpha)),
+        "xyz-d50" => Ok(Rgb::from_xyz(transform(&D50_TO_D65, values), alpha)),
+        _ => Err(anyhow!("Unsupported color space : {}", space)),
+    }
+}
+
+fn srgb_transfer(value: f64) -> f64 {
+    if value <= 0.0031308 {
+        12.92 * value
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn srgb_transfer_inv(value: f64) -> f64 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Just noticeable difference (ΔE OK) & precision of the gamut mapping
+const GAMUT_JND: f64 = 0.02;
+const GAMUT_EPSILON: f64 = 0.0001;
+
+// APCA-W3 constants (https://github.com/Myndex/apca-w3)
+const APCA_TRC: f64 = 2.4;
+const APCA_BLACK_THRESHOLD: f64 = 0.022;
+const APCA_BLACK_CLAMP: f64 = 1.414;
+const APCA_SCALE: f64 = 1.14;
+const APCA_LOW_OFFSET: f64 = 0.027;
+const APCA_LOW_CLIP: f64 = 0.1;
+const APCA_DELTA_Y_MIN: f64 = 0.0005;
+
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+// Matrices of CSS Color 4 (https://www.w3.org/TR/css-color-4/#color-conversion-code)
+
+const LINEAR_SRGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+const XYZ_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [
+        0.05563007969699366,
+        -0.20397695888897652,
+        1.0569715142428786,
+    ],
+];
+
+const LINEAR_P3_TO_XYZ: [[f64; 3]; 3] = [
+    [0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+    [0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+    [0.0, 0.04511338185890264, 1.043944368900976],
+];
+
+const XYZ_TO_LINEAR_P3: [[f64; 3]; 3] = [
+    [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+    [
+        -0.8294889695615747,
+        1.7626640603183463,
+        0.023624685841943577,
+    ],
+    [
+        0.03584583024378447,
+        -0.07617238926804182,
+        0.9568845240076872,
+    ],
+];
+
+/// Bradford chromatic adaptation
+const D65_TO_D50: [[f64; 3]; 3] = [
+    [
+        1.0479298208405488,
+        0.022946793341019088,
+        -0.05019222954313557,
+    ],
+    [
+        0.029627815688159344,
+        0.990434484573249,
+        -0.01707382502938514,
+    ],
+    [
+        -0.009243058152591178,
+        0.015055144896577895,
+        0.7518742899580008,
+    ],
+];
+
+const D50_TO_D65: [[f64; 3]; 3] = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [
+        -0.0283697093338637,
+        1.0099953980813041,
+        0.021041441191917323,
+    ],
+    [
+        0.012314014864481998,
+        -0.020507649298898964,
+        1.330365926242124,
+    ],
+];
+
+/// 3x3 matrix product (color space conversions)
+pub fn transform(matrix: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    let [x, y, z] = vector;
+    [
+        matrix[0][0] * x + matrix[0][1] * y + matrix[0][2] * z,
+        matrix[1][0] * x + matrix[1][1] * y + matrix[1][2] * z,
+        matrix[2][0] * x + matrix[2][1] * y + matrix[2][2] * z,
+    ]
+}
+
+fn linear_srgb_to_oklab(rgb: [f64; 3]) -> Oklab {
+    let [r, g, b] = rgb;
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    Oklab {
+        l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    }
+}
+
+fn oklab_to_linear_srgb(oklab: &Oklab) -> [f64; 3] {
+    let l = (oklab.l + 0.3963377774 * oklab.a + 0.2158037573 * oklab.b).powi(3);
+    let m = (oklab.l - 0.1055613458 * oklab.a - 0.0638541728 * oklab.b).powi(3);
+    let s = (oklab.l - 0.0894841775 * oklab.a - 1.2914855480 * oklab.b).powi(3);
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+}
+
+// OKHSL helpers, from Björn Ottosson's reference implementation (https://bottosson.github.io/posts/colorpicker/)
+
+fn toe(x: f64) -> f64 {
+    let k_1 = 0.206;
+    let k_2 = 0.03;
+    let k_3 = (1.0 + k_1) / (1.0 + k_2);
+    0.5 * (k_3 * x - k_1 + ((k_3 * x - k_1) * (k_3 * x - k_1) + 4.0 * k_2 * k_3 * x).sqrt())
+}
+
+fn toe_inv(x: f64) -> f64 {
+    let k_1 = 0.206;
+    let k_2 = 0.03;
+    let k_3 = (1.0 + k_1) / (1.0 + k_2);
+    (x * x + k_1 * x) / (k_3 * (x + k_2))
+}
+
+/// Maximum saturation (C / L) of a hue inside sRGB, `a` and `b` normalized
+fn compute_max_saturation(a: f64, b: f64) -> f64 {
+    let (k0, k1, k2, k3, k4, wl, wm, ws) = if -1.88170328 * a - 0.80936493 * b > 1.0 {
+        (
+            1.19086277,
+            1.76576728,
+            0.59662641,
+            0.75515197,
+            0.56771245,
+            4.0767416621,
+            -3.3077115913,
+            0.2309699292,
+        )
+    } else if 1.81444104 * a - 1.19445276 * b > 1.0 {
+        (
+            0.73956515,
+            -0.45954404,
+            0.08285427,
+            0.12541070,
+            0.14503204,
+            -1.2684380046,
+            2.6097574011,
+            -0.3413193965,
+        )
+    } else {
+        (
+            1.35733652,
+            -0.00915799,
+            -1.15130210,
+            -0.50559606,
+            0.00692167,
+            -0.0041960863,
+            -0.7034186147,
+            1.7076147010,
+        )
+    };
+
+    let saturation = k0 + k1 * a + k2 * b + k3 * a * a + k4 * a * b;
+
+    let k_l = 0.3963377774 * a + 0.2158037573 * b;
+    let k_m = -0.1055613458 * a - 0.0638541728 * b;
+    let k_s = -0.0894841775 * a - 1.2914855480 * b;
+
+    let l_ = 1.0 + saturation * k_l;
+    let m_ = 1.0 + saturation * k_m;
+    let s_ = 1.0 + saturation * k_s;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
+
+    let l_ds = 3.0 * k_l * l_ * l_;
+    let m_ds = 3.0 * k_m * m_ * m_;
+    let s_ds = 3.0 * k_s * s_ * s_;
+
+    let l_ds2 = 6.0 * k_l * k_l * l_;
+    let m_ds2 = 6.0 * k_m * k_m * m_;
+    let s_ds2 = 6.0 * k_s * k_s * s_;
+
+    let f = wl * l + wm * m + ws * s;
+    let f1 = wl * l_ds + wm * m_ds + ws * s_ds;
+    let f2 = wl * l_ds2 + wm * m_ds2 + ws * s_ds2;
+
+    saturation - f * f1 / (f1 * f1 - 0.5 * f * f2)
+}
+
+/// Lightness & chroma of the cusp of a hue, `a` and `b` normalized
+fn find_cusp(a: f64, b: f64) -> (f64, f64) {
+    let s_cusp = compute_max_saturation(a, b);
+    let [r, g, b] = oklab_to_linear_srgb(&Oklab {
+        l: 1.0,
+        a: s_cusp * a,
+        b: s_cusp * b,
+    });
+    let l_cusp = (1.0 / r.max(g).max(b)).cbrt();
+    (l_cusp, l_cusp * s_cusp)
+}
+
+fn find_gamut_intersection(a: f64, b: f64, l1: f64, c1: f64, l0: f64, cusp: (f64, f64)) -> f64 {
+    let (cusp_l, cusp_c) = cusp;
+    if ((l1 - l0) * cusp_c - (cusp_l - l0) * c1) <= 0.0 {
+        return cusp_c * l0 / (c1 * cusp_l + cusp_c * (l0 - l1));
+    }
+
+    let mut t = cusp_c * (l0 - 1.0) / (c1 * (cusp_l - 1.0) + cusp_c * (l0 - l1));
+
+    let d_l = l1 - l0;
+    let d_c = c1;
+
+    let k_l = 0.3963377774 * a + 0.2158037573 * b;
+    let k_m = -0.1055613458 * a - 0.0638541728 * b;
+    let k_s = -0.0894841775 * a - 1.2914855480 * b;
+
+    let l_dt = d_l + d_c * k_l;
+    let m_dt = d_l + d_c * k_m;
+    let s_dt = d_l + d_c * k_s;
+
+    let lightness = l0 * (1.0 - t) + t * l1;
+    let chroma = t * c1;
+
+    let l_ = lightness + chroma * k_l;
+    let m_ = lightness + chroma * k_m;
+    let s_ = lightness + chroma * k_s;
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
+
+    let ldt = 3.0 * l_dt * l_ * l_;
+    let mdt = 3.0 * m_dt * m_ * m_;
+    let sdt = 3.0 * s_dt * s_ * s_;
+
+    let ldt2 = 6.0 * l_dt * l_dt * l_;
+    let mdt2 = 6.0 * m_dt * m_dt * m_;
+    let sdt2 = 6.0 * s_dt * s_dt * s_;
+
+    let halley_step = |wl: f64, wm: f64, ws: f64| -> f64 {
+        let f = wl * l + wm * m + ws * s - 1.0;
+        let f1 = wl * ldt + wm * mdt + ws * sdt;
+        let f2 = wl * ldt2 + wm * mdt2 + ws * sdt2;
+        let u = f1 / (f1 * f1 - 0.5 * f * f2);
+        if u >= 0.0 {
+            -f * u
+        } else {
+            f64::MAX
+        }
+    };
+
+    let t_r = halley_step(4.0767416621, -3.3077115913, 0.2309699292);
+    let t_g = halley_step(-1.2684380046, 2.6097574011, -0.3413193965);
+    let t_b = halley_step(-0.0041960863, -0.7034186147, 1.7076147010);
+
+    t += t_r.min(t_g).min(t_b);
+    t
+}
+
+fn get_st_mid(a_: f64, b_: f64) -> (f64, f64) {
+    let s = 0.11516993
+        + 1.0
+            / (7.44778970
+                + 4.15901240 * b_
+                + a_ * (-2.19557347
+                    + 1.75198401 * b_
+                    + a_ * (-2.13704948 - 10.02301043 * b_
+                        + a_ * (-4.24894561 + 5.38770819 * b_ + 4.69891013 * a_))));
+    let t = 0.11239642
+        + 1.0
+            / (1.61320320 - 0.68124379 * b_
+                + a_ * (0.40370612
+                    + 0.90148123 * b_
+                    + a_ * (-0.27087943
+                        + 0.61223990 * b_
+                        + a_ * (0.00299215 - 0.45399568 * b_ - 0.14661872 * a_))));
+    (s, t)
+}
+
+/// (C_0, C_mid, C_max) used by the OKHSL saturation scale
+fn get_cs(l: f64, a_: f64, b_: f64) -> (f64, f64, f64) {
+    let cusp = find_cusp(a_, b_);
+    let c_max = find_gamut_intersection(a_, b_, l, 1.0, l, cusp);
+    let (cusp_l, cusp_c) = cusp;
+    let st_max = (cusp_c / cusp_l, cusp_c / (1.0 - cusp_l));
+
+    let k = c_max / (l * st_max.0).min((1.0 - l) * st_max.1);
+
+    let (st_mid_s, st_mid_t) = get_st_mid(a_, b_);
+    let c_a = l * st_mid_s;
+    let c_b = (1.0 - l) * st_mid_t;
+    let c_mid = 0.9
+        * k
+        * (1.0 / (1.0 / c_a.powi(4) + 1.0 / c_b.powi(4)))
+            .sqrt()
+            .sqrt();
+
+    let c_a = l * 0.4;
+    let c_b = (1.0 - l) * 0.8;
+    let c_0 = (1.0 / (1.0 / (c_a * c_a) + 1.0 / (c_b * c_b))).sqrt();
+
+    (c_0, c_mid, c_max)
+}
+
+// HSLuv helpers, from the reference implementation (https://www.hsluv.org/implementations/)
+
+/// u' & v' of the D65 white point
+const REF_U: f64 = 0.19783000664283;
+const REF_V: f64 = 0.46831999493879;
+
+fn y_to_l(y: f64) -> f64 {
+    if y <= LAB_EPSILON {
+        y * LAB_KAPPA
+    } else {
+        116.0 * y.cbrt() - 16.0
+    }
+}
+
+fn l_to_y(l: f64) -> f64 {
+    if l <= 8.0 {
+        l / LAB_KAPPA
+    } else {
+        ((l + 16.0) / 116.0).powi(3)
+    }
+}
+
+/// CIE LCh(uv) : lightness, chroma & hue of CIELUV
+fn xyz_to_lchuv(xyz: [f64; 3]) -> (f64, f64, f64) {
+    let [x, y, z] = xyz;
+    let l = y_to_l(y);
+    let divider = x + 15.0 * y + 3.0 * z;
+    if l == 0.0 || divider == 0.0 {
+        return (0.0, 0.0, 0.0);
+    }
+    let u = 13.0 * l * (4.0 * x / divider - REF_U);
+    let v = 13.0 * l * (9.0 * y / divider - REF_V);
+    let (c, h) = to_polar(u, v);
+    (l, c, h)
+}
+
+fn lchuv_to_xyz(l: f64, c: f64, h: f64) -> [f64; 3] {
+    if l == 0.0 {
+        return [0.0, 0.0, 0.0];
+    }
+    let (u, v) = from_polar(c, h);
+    let var_u = u / (13.0 * l) + REF_U;
+    let var_v = v / (13.0 * l) + REF_V;
+    let y = l_to_y(l);
+    let x = -(9.0 * y * var_u) / ((var_u - 4.0) * var_v - var_u * var_v);
+    let z = (9.0 * y - 15.0 * var_v * y - var_v * x) / (3.0 * var_v);
+    [x, y, z]
+}
+
+/// Lines (slope, intercept) bounding the sRGB gamut in the chroma / hue plane of a lightness
+fn get_bounds(l: f64) -> Vec<(f64, f64)> {
+    let sub1 = (l + 16.0).powi(3) / 1560896.0;
+    let sub2 = if sub1 > LAB_EPSILON {
+        sub1
+    } else {
+        l / LAB_KAPPA
+    };
+    let mut bounds: Vec<(f64, f64)> = vec![];
+    for [m1, m2, m3] in XYZ_TO_LINEAR_SRGB {
+        for t in [0.0, 1.0] {
+            let top1 = (284517.0 * m1 - 94839.0 * m3) * sub2;
+            let top2 =
+                (838422.0 * m3 + 769860.0 * m2 + 731718.0 * m1) * l * sub2 - 769860.0 * t * l;
+            let bottom = (632260.0 * m3 - 126452.0 * m2) * sub2 + 126452.0 * t;
+            bounds.push((top1 / bottom, top2 / bottom));
+        }
+    }
+    bounds
+}
+
+fn max_chroma_for_lh(l: f64, h: f64) -> f64 {
+    let hue = h.to_radians();
+    get_bounds(l)
+        .into_iter()
+        .map(|(slope, intercept)| intercept / (hue.sin() - slope * hue.cos()))
+        .filter(|length| *length >= 0.0)
+        .fold(f64::MAX, f64::min)
+}
+
+/// CSS named colors (https://www.w3.org/TR/css-color-4/#named-colors)
+const NAMED_COLORS: [(&str, &str); 148] = [
+    ("aliceblue", "f0f8ff"),
+    ("antiquewhite", "faebd7"),
+    ("aqua", "00ffff"),
+    ("aquamarine", "7fffd4"),
+    ("azure", "f0ffff"),
+    ("beige", "f5f5dc"),
+    ("bisque", "ffe4c4"),
+    ("black", "000000"),
+    ("blanchedalmond", "ffebcd"),
+    ("blue", "0000ff"),
+    ("blueviolet", "8a2be2"),
+    ("brown", "a52a2a"),
+    ("burlywood", "deb887"),
+    ("cadetblue", "5f9ea0"),
+    ("chartreuse", "7fff00"),
+    ("chocolate", "d2691e"),
+    ("coral", "ff7f50"),
+    ("cornflowerblue", "6495ed"),
+    ("cornsilk", "fff8dc"),
+    ("crimson", "dc143c"),
+    ("cyan", "00ffff"),
+    ("darkblue", "00008b"),
+    ("darkcyan", "008b8b"),
+    ("darkgoldenrod", "b8860b"),
+    ("darkgray", "a9a9a9"),
+    ("darkgreen", "006400"),
+    ("darkgrey", "a9a9a9"),
+    ("darkkhaki", "bdb76b"),
+    ("darkmagenta", "8b008b"),
+    ("darkolivegreen", "556b2f"),
+    ("darkorange", "ff8c00"),
+    ("darkorchid", "9932cc"),
+    ("darkred", "8b0000"),
+    ("darksalmon", "e9967a"),
+    ("darkseagreen", "8fbc8f"),
+    ("darkslateblue", "483d8b"),
+    ("darkslategray", "2f4f4f"),
+    ("darkslategrey", "2f4f4f"),
+    ("darkturquoise", "00ced1"),
+    ("darkviolet", "9400d3"),
+    ("deeppink", "ff1493"),
+    ("deepskyblue", "00bfff"),
+    ("dimgray", "696969"),
+    ("dimgrey", "696969"),
+    ("dodgerblue", "1e90ff"),
+    ("firebrick", "b22222"),
+    ("floralwhite", "fffaf0"),
+    ("forestgreen", "228b22"),
+    ("fuchsia", "ff00ff"),
+    ("gainsboro", "dcdcdc"),
+    ("ghostwhite", "f8f8ff"),
+    ("gold", "ffd700"),
+    ("goldenrod", "daa520"),
+    ("gray", "808080"),
+    ("green", "008000"),
+    ("greenyellow", "adff2f"),
+    ("grey", "808080"),
+    ("honeydew", "f0fff0"),
+    ("hotpink", "ff69b4"),
+    ("indianred", "cd5c5c"),
+    ("indigo", "4b0082"),
+    ("ivory", "fffff0"),
+    ("khaki", "f0e68c"),
+    ("lavender", "e6e6fa"),
+    ("lavenderblush", "fff0f5"),
+    ("lawngreen", "7cfc00"),
+    ("lemonchiffon", "fffacd"),
+    ("lightblue", "add8e6"),
+    ("lightcoral", "f08080"),
+    ("lightcyan", "e0ffff"),
+    ("lightgoldenrodyellow", "fafad2"),
+    ("lightgray", "d3d3d3"),
+    ("lightgreen", "90ee90"),
+    ("lightgrey", "d3d3d3"),
+    ("lightpink", "ffb6c1"),
+    ("lightsalmon", "ffa07a"),
+    ("lightseagreen", "20b2aa"),
+    ("lightskyblue", "87cefa"),
+    ("lightslategray", "778899"),
+    ("lightslategrey", "778899"),
+    ("lightsteelblue", "b0c4de"),
+    ("lightyellow", "ffffe0"),
+    ("lime", "00ff00"),
+    ("limegreen", "32cd32"),
+    ("linen", "faf0e6"),
+    ("magenta", "ff00ff"),
+    ("maroon", "800000"),
+    ("mediumaquamarine", "66cdaa"),
+    ("mediumblue", "0000cd"),
+    ("mediumorchid", "ba55d3"),
+    ("mediumpurple", "9370db"),
+    ("mediumseagreen", "3cb371"),
+    ("mediumslateblue", "7b68ee"),
+    ("mediumspringgreen", "00fa9a"),
+    ("mediumturquoise", "48d1cc"),
+    ("mediumvioletred", "c71585"),
+    ("midnightblue", "191970"),
+    ("mintcream", "f5fffa"),
+    ("mistyrose", "ffe4e1"),
+    ("moccasin", "ffe4b5"),
+    ("navajowhite", "ffdead"),
+    ("navy", "000080"),
+    ("oldlace", "fdf5e6"),
+    ("olive", "808000"),
+    ("olivedrab", "6b8e23"),
+    ("orange", "ffa500"),
+    ("orangered", "ff4500"),
+    ("orchid", "da70d6"),
+    ("palegoldenrod", "eee8aa"),
+    ("palegreen", "98fb98"),
+    ("paleturquoise", "afeeee"),
+    ("palevioletred", "db7093"),
+    ("papayawhip", "ffefd5"),
+    ("peachpuff", "ffdab9"),
+    ("peru", "cd853f"),
+    ("pink", "ffc0cb"),
+    ("plum", "dda0dd"),
+    ("powderblue", "b0e0e6"),
+    ("purple", "800080"),
+    ("rebeccapurple", "663399"),
+    ("red", "ff0000"),
+    ("rosybrown", "bc8f8f"),
+    ("royalblue", "4169e1"),
+    ("saddlebrown", "8b4513"),
+    ("salmon", "fa8072"),
+    ("sandybrown", "f4a460"),
+    ("seagreen", "2e8b57"),
+    ("seashell", "fff5ee"),
+    ("sienna", "a0522d"),
+    ("silver", "c0c0c0"),
+    ("skyblue", "87ceeb"),
+    ("slateblue", "6a5acd"),
+    ("slategray", "708090"),
+    ("slategrey", "708090"),
+    ("snow", "fffafa"),
+    ("springgreen", "00ff7f"),
+    ("steelblue", "4682b4"),
+    ("tan", "d2b48c"),
+    ("teal", "008080"),
+    ("thistle", "d8bfd8"),
+    ("tomato", "ff6347"),
+    ("turquoise", "40e0d0"),
+    ("violet", "ee82ee"),
+    ("wheat", "f5deb3"),
+    ("white", "ffffff"),
+    ("whitesmoke", "f5f5f5"),
+    ("yellow", "ffff00"),
+    ("yellowgreen", "9acd32"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(Rgb::parse("#3b82f6").unwrap().to_hex(), "#3b82f6");
+        assert_eq!(Rgb::parse("#FFF").unwrap().to_hex(), "#ffffff");
+        assert_eq!(Rgb::parse("#0008").unwrap().to_hex(), "#00000088");
+        assert_eq!(Rgb::parse(" #11223380 ").unwrap().to_hex(), "#11223380");
+    }
+
+    #[test]
+    fn rejects_invalid_hex_colors() {
+        for color in [
+            "#", "#12", "#12345", "#1234567", "#ggg", "#+f+f+f", "#aaaéa", "#ééé",
+        ] {
+            assert!(Rgb::parse(color).is_err(), "{}", color);
+        }
+    }
+
+    fn assert_close(first: &Rgb, second: &Rgb) {
+        for (channel, expected) in [first.r, first.g, first.b, first.alpha].iter().zip([
+            second.r,
+            second.g,
+            second.b,
+            second.alpha,
+        ]) {
+            assert!(
+                (channel - expected).abs() < 1e-6,
+                "{:?} != {:?}",
+                first,
+                second
+            );
+        }
+    }
+
+    #[test]
+    fn parses_css_colors() {
+        for (color, hex) in [
+            ("rebeccapurple", "#663399"),
+            ("transparent", "#00000000"),
+            ("rgb(59, 130, 246)", "#3b82f6"),
+            ("rgb(255 0 0 / 50%)", "#ff000080"),
+            ("rgba(100%, 0%, 0%, 0.5)", "#ff000080"),
+            ("hsl(120 100% 25%)", "#008000"),
+            ("hsla(120deg, 100%, 25%, 1)", "#008000"),
+            ("hwb(240 0% 0%)", "#0000ff"),
+            ("lab(54.29 80.8 69.89)", "#ff0000"),
+            ("lch(54.29 106.84 40.85)", "#ff0000"),
+            ("oklab(62.8% 0.2249 0.1258)", "#ff0000"),
+            ("oklch(0.628 0.2577 29.23)", "#ff0000"),
+            ("color(srgb 1 0.5 0)", "#ff8000"),
+            ("color(srgb-linear 1 0 0)", "#ff0000"),
+            ("color(display-p3 0.9175 0.2003 0.1386)", "#ff0000"),
+        ] {
+            assert_eq!(Rgb::parse(color).unwrap().to_hex(), hex, "{}", color);
+        }
+    }
+
+    #[test]
+    fn wide_gamut_colors_are_kept_until_mapped() {
+        let color = Rgb::parse("color(display-p3 1 0 0)").unwrap();
+        assert!(!color.is_in_gamut());
+        assert!(color.r > 1.0);
+        let mapped = color.to_gamut();
+        assert!(mapped.is_in_gamut());
+        assert!(mapped.r > mapped.g && mapped.r > mapped.b);
+    }
+
+    #[test]
+    fn rejects_invalid_css_colors() {
+        for color in [
+            "",
+            "blurple",
+            "rgb(1 2)",
+            "rgb(a b c)",
+            "hsl(1 2 3",
+            "foo(1 2 3)",
+        ] {
+            assert!(Rgb::parse(color).is_err(), "{}", color);
+        }
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        for hex in [
+            "#3b82f6", "#10b981", "#111827", "#ffffff", "#000000", "#ff00ff",
+        ] {
+            let color = Rgb::parse(hex).unwrap();
+            assert_close(&Rgb::from_oklch(&color.to_oklch(), 1.0), &color);
+            assert_close(&Rgb::from_lab(&color.to_lab(), 1.0), &color);
+            assert_close(&Rgb::from_lch(&color.to_lch(), 1.0), &color);
+            assert_close(&Rgb::from_hsl(&color.to_hsl(), 1.0), &color);
+            assert_close(&Rgb::from_display_p3(&color.to_display_p3(), 1.0), &color);
+            assert_close(&Rgb::from_hsluv(&color.to_hsluv(), 1.0), &color);
+        }
+    }
+
+    #[test]
+    fn converts_to_reference_values() {
+        let red = Rgb::new(1.0, 0.0, 0.0);
+        let oklch = red.to_oklch();
+        assert!((oklch.l - 0.62796).abs() < 1e-4);
+        assert!((oklch.c - 0.25768).abs() < 1e-4);
+        assert!((oklch.h - 29.2339).abs() < 1e-2);
+        let lab = red.to_lab();
+        assert!((lab.l - 54.2905).abs() < 1e-2);
+        assert!((lab.a - 80.8049).abs() < 1e-2);
+        assert!((lab.b - 69.8910).abs() < 1e-2);
+        let hsluv = red.to_hsluv();
+        assert!((hsluv.h - 12.1770).abs() < 1e-2);
+        assert!((hsluv.s - 100.0).abs() < 1e-2);
+        assert!((hsluv.l - 53.2371).abs() < 1e-2);
+    }
+
+    #[test]
+    fn wcag_contrast_ratios() {
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        assert!((contrast_ratio(&black, &white) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(&white, &black) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(&white, &white) - 1.0).abs() < 1e-9);
+        let gray = Rgb::parse("#777777").unwrap();
+        assert!((contrast_ratio(&gray, &white) - 4.48).abs() < 0.01);
+    }
+
+    #[test]
+    fn apca_contrasts() {
+        let black = Rgb::new(0.0, 0.0, 0.0);
+        let white = Rgb::new(1.0, 1.0, 1.0);
+        assert!((apca_contrast(&black, &white) - 106.04).abs() < 0.01);
+        assert!((apca_contrast(&white, &black) + 107.88).abs() < 0.01);
+        assert_eq!(apca_contrast(&white, &white), 0.0);
+        let gray = Rgb::parse("#888888").unwrap();
+        assert!((apca_contrast(&gray, &white) - 63.06).abs() < 0.01);
+    }
+
+    #[test]
+    fn delta_e_2000_reference_pairs() {
+        // Sharma, Wu & Dalal test data
+        for (first, second, expected) in [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([50.0, 2.5, 0.0], [61.0, -5.0, 29.0], 22.8977),
+            ([50.0, 2.5, 0.0], [56.0, -27.0, -3.0], 31.9030),
+            (
+                [60.2574, -34.0099, 36.2677],
+                [60.4626, -34.1751, 39.4387],
+                1.2644,
+            ),
+        ] {
+            let lab = |[l, a, b]: [f64; 3]| Rgb::from_lab(&Lab { l, a, b }, 1.0);
+            let delta_e = delta_e_2000(&lab(first), &lab(second));
+            assert!(
+                (delta_e - expected).abs() < 1e-3,
+                "{} != {}",
+                delta_e,
+                expected
+            );
+            assert!((delta_e_2000(&lab(second), &lab(first)) - delta_e).abs() < 1e-9);
+        }
+        let color = Rgb::parse("#3b82f6").unwrap();
+        assert!(delta_e_2000(&color, &color).abs() < 1e-9);
+    }
+
+    #[test]
+    fn okhsl_round_trip() {
+        for hex in ["#3b82f6", "#10b981", "#111827", "#ff00ff", "#f9fafb"] {
+            let color = Rgb::parse(hex).unwrap();
+            let okhsl = color.to_okhsl();
+            assert!((0.0..360.0).contains(&okhsl.h));
+            assert!((0.0..=1.0).contains(&okhsl.s) && (0.0..=1.0).contains(&okhsl.l));
+            // The gamut boundary is approximated, saturated colors round trip to the same hex
+            assert_eq!(Rgb::from_okhsl(&okhsl, 1.0).to_hex(), hex);
+        }
+        let gray = Rgb::parse("#777777").unwrap().to_okhsl();
+        assert!(gray.s < 1e-6);
+        let white = Rgb::new(1.0, 1.0, 1.0).to_okhsl();
+        assert!((white.l - 1.0).abs() < 1e-6 && white.s == 0.0);
+        assert_eq!(
+            Rgb::from_okhsl(
+                &Okhsl {
+                    h: 120.0,
+                    s: 0.5,
+                    l: 0.0
+                },
+                1.0
+            )
+            .to_hex(),
+            "#000000"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use serde::{Deserialize, Serialize};
+
+use super::color_domain::{transform, Rgb};
+
+/// ΔE 1976 under which two states of a color combination are hard to tell apart (about twice the
+/// just noticeable difference, the states are not seen side by side)
+pub const STATE_DELTA_E_THRESHOLD: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorVisionDeficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Achromatopsia,
+}
+
+impl ColorVisionDeficiency {
+    pub const ALL: [ColorVisionDeficiency; 4] = [
+        ColorVisionDeficiency::Protanopia,
+        ColorVisionDeficiency::Deuteranopia,
+        ColorVisionDeficiency::Tritanopia,
+        ColorVisionDeficiency::Achromatopsia,
+    ];
+}
+
+impl FromStr for ColorVisionDeficiency {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "protanopia" => Ok(ColorVisionDeficiency::Protanopia),
+            "deuteranopia" => Ok(ColorVisionDeficiency::Deuteranopia),
+            "tritanopia" => Ok(ColorVisionDeficiency::Tritanopia),
+            "achromatopsia" => Ok(ColorVisionDeficiency::Achromatopsia),
+            _ => Err(anyhow!("Unknown color vision deficiency : {}", value)),
+        }
+    }
+}
+
+/// Color as seen with a color vision deficiency : Machado et al. 2009 (severity 1) for the protanopia
+/// & deuteranopia, Brettel et al. 1997 for the tritanopia, luminance only for the achromatopsia.
+/// The models work on linear sRGB, the alpha is kept
+pub fn simulate_color_vision(color: &Rgb, deficiency: ColorVisionDeficiency) -> Rgb {
+    let linear = color.clamp().to_linear();
+    let simulated = match deficiency {
+        ColorVisionDeficiency::Protanopia => transform(&MACHADO_PROTANOPIA, linear),
+        ColorVisionDeficiency::Deuteranopia => transform(&MACHADO_DEUTERANOPIA, linear),
+        ColorVisionDeficiency::Tritanopia => {
+            let [r, g, b] = linear;
+            let [x, y, z] = BRETTEL_TRITANOPIA_SEPARATION_PLANE;
+            if r * x + g * y + b * z >= 0.0 {
+                transform(&BRETTEL_TRITANOPIA_FIRST_HALF_PLANE, linear)
+            } else {
+                transform(&BRETTEL_TRITANOPIA_SECOND_HALF_PLANE, linear)
+            }
+        }
+        ColorVisionDeficiency::Achromatopsia => {
+            let luminance = color.relative_luminance();
+            [luminance, luminance, luminance]
+        }
+    };
+    Rgb::from_linear(
+        simulated.map(|channel| channel.clamp(0.0, 1.0)),
+        color.alpha,
+    )
+}
+
+/// Color with its simulation (hex)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedColor {
+    pub color: String,
+    pub simulated: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTint {
+    pub label: String,
+    pub color: SimulatedColor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedPalette {
+    pub palette_name: String,
+    pub tints: Vec<SimulatedTint>,
+}
+
+/// Resolved colors of a color combination state (default, hover, focus, active), the missing
+/// properties inherit the default state then the base semantic tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCombination {
+    pub state: String,
+    pub background: Option<SimulatedColor>,
+    pub text: Option<SimulatedColor>,
+    pub border: Option<SimulatedColor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCombinationCollection {
+    pub combination_name: String,
+    pub states: Vec<SimulatedCombination>,
+}
+
+/// Two states of a combination distinguishable with a normal vision which collapse with the
+/// deficiency (largest ΔE 1976 of the background, text & border)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DistinguishabilityWarning {
+    pub combination_name: String,
+    pub first_state: String,
+    pub second_state: String,
+    pub delta_e: f64,
+    pub simulated_delta_e: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorVisionSimulation {
+    pub deficiency: ColorVisionDeficiency,
+    pub palettes: Vec<SimulatedPalette>,
+    pub combinations: Vec<SimulatedCombinationCollection>,
+    pub warnings: Vec<DistinguishabilityWarning>,
+}
+
+const MACHADO_PROTANOPIA: [[f64; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+const MACHADO_DEUTERANOPIA: [[f64; 3]; 3] = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+
+/// Brettel projection on the two half planes of the tritanope color space (linear sRGB, from
+/// libDaltonLens), the side is given by the normal of the separation plane
+const BRETTEL_TRITANOPIA_FIRST_HALF_PLANE: [[f64; 3]; 3] = [
+    [1.01277, 0.13548, -0.14826],
+    [-0.01243, 0.86812, 0.14431],
+    [0.07589, 0.80500, 0.11911],
+];
+const BRETTEL_TRITANOPIA_SECOND_HALF_PLANE: [[f64; 3]; 3] = [
+    [0.93678, 0.18979, -0.12657],
+    [0.06154, 0.81526, 0.12320],
+    [-0.37562, 1.12767, 0.24796],
+];
+const BRETTEL_TRITANOPIA_SEPARATION_PLANE: [f64; 3] = [0.03901, -0.02788, -0.01113];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_deficiencies() {
+        assert_eq!(
+            "deuteranopia".parse::<ColorVisionDeficiency>().unwrap(),
+            ColorVisionDeficiency::Deuteranopia
+        );
+        assert!("daltonism".parse::<ColorVisionDeficiency>().is_err());
+    }
+
+    #[test]
+    fn neutral_colors_are_unchanged() {
+        for deficiency in ColorVisionDeficiency::ALL {
+            for hex in ["#000000", "#ffffff", "#777777"] {
+                let color = Rgb::parse(hex).unwrap();
+                assert_eq!(
+                    simulate_color_vision(&color, deficiency).to_hex(),
+                    hex,
+                    "{:?}",
+                    deficiency
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn achromatopsia_keeps_the_luminance() {
+        let color = Rgb::parse("#3b82f680").unwrap();
+        let simulated = simulate_color_vision(&color, ColorVisionDeficiency::Achromatopsia);
+        assert!(
+            (simulated.r - simulated.g).abs() < 1e-9 && (simulated.g - simulated.b).abs() < 1e-9
+        );
+        assert!((simulated.relative_luminance() - color.relative_luminance()).abs() < 1e-9);
+        assert_eq!(simulated.alpha, color.alpha);
+    }
+
+    #[test]
+    fn red_and_green_collapse_without_red_or_green_cones() {
+        let red = Rgb::parse("#d62728").unwrap();
+        let green = Rgb::parse("#2ca02c").unwrap();
+        for deficiency in [
+            ColorVisionDeficiency::Protanopia,
+            ColorVisionDeficiency::Deuteranopia,
+        ] {
+            let red = simulate_color_vision(&red, deficiency).to_hsl();
+            let green = simulate_color_vision(&green, deficiency).to_hsl();
+            // Both seen as yellowish
+            assert!((red.h - green.h).abs() < 15.0, "{:?}", deficiency);
+        }
+    }
+}
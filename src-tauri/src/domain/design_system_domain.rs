@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 use crate::domain::FileMetadata;
 
-use super::{image_domain::ImageLocal, FileInfos};
+use super::{image_domain::ImageLocal, reference_domain::RenamedReference, FileInfos};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -92,6 +92,14 @@ pub struct DesignSystemMetadata {
 pub struct ExportsMetadata {
     pub css: Option<FileMetadata>,
     pub figma_token_studio: Option<FileMetadata>,
+    pub dtcg: Option<FileMetadata>,
+    pub style_dictionary: Option<FileMetadata>,
+    pub scss: Option<FileMetadata>,
+    pub less: Option<FileMetadata>,
+    pub tailwind: Option<FileMetadata>,
+    pub android: Option<FileMetadata>,
+    pub ios: Option<FileMetadata>,
+    pub swatches: Option<FileMetadata>,
     pub readme: Option<FileMetadata>,
 }
 
@@ -118,8 +126,14 @@ impl DesignSystemMetadataFile {
             logo,
             ..
         } = design_system_metadata;
-        let banner_filename: &str = Path::new(banner).file_name().unwrap().to_str().unwrap();
-        let logo_filename: &str = Path::new(logo).file_name().unwrap().to_str().unwrap();
+        let banner_filename: &str = Path::new(banner)
+            .file_name()
+            .and_then(|filename| filename.to_str())
+            .unwrap_or_default();
+        let logo_filename: &str = Path::new(logo)
+            .file_name()
+            .and_then(|filename| filename.to_str())
+            .unwrap_or_default();
         DesignSystemMetadataFile {
             design_system_id: design_system_id.to_string(),
             design_system_name: design_system_name.to_string(),
@@ -134,7 +148,7 @@ impl DesignSystemMetadata {
         design_system_file: &DesignSystemMetadataFile,
         path: &PathBuf,
         is_tmp: bool,
-        image_pathbuf: &PathBuf,
+        image_pathbuf: &Path,
         exports: ExportsMetadata,
         update_date: String,
     ) -> DesignSystemMetadata {
@@ -181,6 +195,12 @@ pub struct IndependantColors {
     pub independant_colors: Vec<Tint>,
 }
 
+impl Default for IndependantColors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl IndependantColors {
     pub fn new() -> IndependantColors {
         IndependantColors {
@@ -190,6 +210,13 @@ impl IndependantColors {
     }
 }
 
+/// Journal of a save being swapped in, the entries without previous version are removed when the
+/// save is rolled back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveJournalFile {
+    pub new_entries: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PalettesMetadataFile {
     pub palettes_order: Vec<String>,
@@ -204,6 +231,15 @@ pub struct SemanticColorTokens {
     pub text_default: Option<String>,
     pub text_dark: Option<String>,
     pub color_combination_collections: Vec<ColorCombinationCollection>,
+    /// Values of the other themes (theme name -> tokens), the missing tokens keep the main value
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub theme_overrides: IndexMap<String, ThemeSemanticColorTokens>,
+}
+
+impl Default for SemanticColorTokens {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SemanticColorTokens {
@@ -215,10 +251,67 @@ impl SemanticColorTokens {
             text_dark: None,
             border: None,
             color_combination_collections: vec![],
+            theme_overrides: IndexMap::new(),
+        }
+    }
+
+    /// Tokens of a theme : the main values with the overrides of the theme, the combinations are
+    /// matched by name and overridden property by property
+    pub fn for_theme(&self, theme_name: &str) -> SemanticColorTokens {
+        let mut tokens = SemanticColorTokens {
+            theme_overrides: IndexMap::new(),
+            ..self.clone()
+        };
+        let overrides = match self.theme_overrides.get(theme_name) {
+            Some(overrides) => overrides,
+            None => return tokens,
+        };
+        for (token, value) in [
+            (&mut tokens.background, &overrides.background),
+            (&mut tokens.border, &overrides.border),
+            (&mut tokens.text_light, &overrides.text_light),
+            (&mut tokens.text_default, &overrides.text_default),
+            (&mut tokens.text_dark, &overrides.text_dark),
+        ] {
+            if value.is_some() {
+                *token = value.clone();
+            }
         }
+        for collection_override in &overrides.color_combination_collections {
+            let name = &collection_override.combination_name;
+            match tokens
+                .color_combination_collections
+                .iter_mut()
+                .find(|collection| collection.combination_name == *name)
+            {
+                Some(collection) => collection.apply_override(collection_override),
+                None => tokens
+                    .color_combination_collections
+                    .push(collection_override.clone()),
+            }
+        }
+        tokens
     }
 }
 
+/// Semantic tokens overridden by a theme (ex: dark, high-contrast)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeSemanticColorTokens {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_light: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_default: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_dark: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub color_combination_collections: Vec<ColorCombinationCollection>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ColorCombinationCollection {
@@ -231,6 +324,37 @@ pub struct ColorCombinationCollection {
     pub default_combination: Option<bool>,
 }
 
+impl ColorCombinationCollection {
+    fn apply_override(&mut self, collection_override: &ColorCombinationCollection) {
+        for (combination, combination_override) in [
+            (&mut self.default, &collection_override.default),
+            (&mut self.hover, &collection_override.hover),
+            (&mut self.active, &collection_override.active),
+            (&mut self.focus, &collection_override.focus),
+        ] {
+            if let Some(combination_override) = combination_override {
+                let combination = combination.get_or_insert(ColorCombination {
+                    background: None,
+                    border: None,
+                    text: None,
+                });
+                for (property, value) in [
+                    (
+                        &mut combination.background,
+                        &combination_override.background,
+                    ),
+                    (&mut combination.border, &combination_override.border),
+                    (&mut combination.text, &combination_override.text),
+                ] {
+                    if value.is_some() {
+                        *property = value.clone();
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ColorCombination {
@@ -292,6 +416,12 @@ pub struct AdditionalFont {
     pub value: String,
 }
 
+impl Default for Fonts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Fonts {
     pub fn new() -> Fonts {
         Fonts {
@@ -304,17 +434,17 @@ impl Fonts {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Typographies {
-    root: TypographyScale,
-    paragraph: TypographyScale,
-    h1: TypographyScale,
-    h2: TypographyScale,
-    h3: TypographyScale,
-    h4: TypographyScale,
-    h5: TypographyScale,
-    h6: TypographyScale,
-    small: TypographyScale,
-    strong: TypographyScale,
-    custom_scales: Vec<CustomTypographyScale>,
+    pub root: TypographyScale,
+    pub paragraph: TypographyScale,
+    pub h1: TypographyScale,
+    pub h2: TypographyScale,
+    pub h3: TypographyScale,
+    pub h4: TypographyScale,
+    pub h5: TypographyScale,
+    pub h6: TypographyScale,
+    pub small: TypographyScale,
+    pub strong: TypographyScale,
+    pub custom_scales: Vec<CustomTypographyScale>,
 }
 impl Typographies {
     /// Standard web‑app typographic scale — pixel‑based, multiples of 4 px
@@ -472,6 +602,76 @@ impl Typographies {
             custom_scales: vec![],
         }
     }
+
+    /// Every scale with its name : root, paragraph, h1 ... strong, then the custom scales
+    pub fn scales(&self) -> Vec<(&str, &TypographyScale)> {
+        let mut scales: Vec<(&str, &TypographyScale)> = vec![
+            ("root", &self.root),
+            ("paragraph", &self.paragraph),
+            ("h1", &self.h1),
+            ("h2", &self.h2),
+            ("h3", &self.h3),
+            ("h4", &self.h4),
+            ("h5", &self.h5),
+            ("h6", &self.h6),
+            ("small", &self.small),
+            ("strong", &self.strong),
+        ];
+        scales.extend(
+            self.custom_scales
+                .iter()
+                .map(|custom_scale| (custom_scale.scale_name.as_str(), &custom_scale.scale)),
+        );
+        scales
+    }
+
+    /// Same as `scales`, mutable
+    pub fn scales_mut(&mut self) -> Vec<(&str, &mut TypographyScale)> {
+        let mut scales: Vec<(&str, &mut TypographyScale)> = vec![
+            ("root", &mut self.root),
+            ("paragraph", &mut self.paragraph),
+            ("h1", &mut self.h1),
+            ("h2", &mut self.h2),
+            ("h3", &mut self.h3),
+            ("h4", &mut self.h4),
+            ("h5", &mut self.h5),
+            ("h6", &mut self.h6),
+            ("small", &mut self.small),
+            ("strong", &mut self.strong),
+        ];
+        scales.extend(
+            self.custom_scales
+                .iter_mut()
+                .map(|custom_scale| (custom_scale.scale_name.as_str(), &mut custom_scale.scale)),
+        );
+        scales
+    }
+
+    pub fn scale_mut(&mut self, scale_name: &str) -> Option<&mut TypographyScale> {
+        match scale_name {
+            "root" => Some(&mut self.root),
+            "paragraph" => Some(&mut self.paragraph),
+            "h1" => Some(&mut self.h1),
+            "h2" => Some(&mut self.h2),
+            "h3" => Some(&mut self.h3),
+            "h4" => Some(&mut self.h4),
+            "h5" => Some(&mut self.h5),
+            "h6" => Some(&mut self.h6),
+            "small" => Some(&mut self.small),
+            "strong" => Some(&mut self.strong),
+            _ => self
+                .custom_scales
+                .iter_mut()
+                .find(|custom_scale| custom_scale.scale_name == scale_name)
+                .map(|custom_scale| &mut custom_scale.scale),
+        }
+    }
+}
+
+impl Default for Typographies {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // Provide sensible defaults for fields we leave unchanged
@@ -509,32 +709,32 @@ impl Default for TypographyScale {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CustomTypographyScale {
-    scale_name: String,
-    scale: TypographyScale,
+    pub scale_name: String,
+    pub scale: TypographyScale,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AdditionalFontWeight {
-    weight_name: String,
-    font_weight: FontWeight,
+    pub weight_name: String,
+    pub font_weight: FontWeight,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TypographyScale {
-    font_size: Measurement,
-    line_height: Measurement,
-    font_weight: FontWeight,
-    letter_spacing: TypographySpacing,
-    word_spacing: TypographySpacing,
-    font_style: FontStyle,
-    text_transform: TextTransform,
-    text_decoration: TextDecoration,
-    padding: Measurement,
-    margin: Measurement,
-    font: Option<String>,
-    color: Option<String>,
+    pub font_size: Measurement,
+    pub line_height: Measurement,
+    pub font_weight: FontWeight,
+    pub letter_spacing: TypographySpacing,
+    pub word_spacing: TypographySpacing,
+    pub font_style: FontStyle,
+    pub text_transform: TextTransform,
+    pub text_decoration: TextDecoration,
+    pub padding: Measurement,
+    pub margin: Measurement,
+    pub font: Option<String>,
+    pub color: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -746,11 +946,23 @@ impl SpacesFile {
     }
 }
 
+impl Default for SpacesFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Radius {
-    default: Measurement,
-    additionals_radius: Vec<RadiusItem>,
+    pub default: Measurement,
+    pub additionals_radius: Vec<RadiusItem>,
+}
+
+impl Default for Radius {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Radius {
@@ -779,6 +991,12 @@ pub struct Shadows {
     pub shadows_array: Vec<Shadow>,
 }
 
+impl Default for Shadows {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Shadows {
     pub fn new() -> Shadows {
         Shadows {
@@ -830,3 +1048,21 @@ pub struct ExportPayload {
     pub value: String,
     pub extension: String,
 }
+
+/// Rename of a palette, or of one of its tints when `tint_label` is given
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteRenamePayload {
+    pub design_system: DesignSystem,
+    pub palette_name: String,
+    pub tint_label: Option<String>,
+    pub new_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteRenameReport {
+    pub design_system: DesignSystem,
+    pub renamed_references: Vec<RenamedReference>,
+    pub palette_builder_paths: Vec<PathBuf>,
+}
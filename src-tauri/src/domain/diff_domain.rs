@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffCategory {
+    /// Palette added or removed with all its tints
+    Palette,
+    Tint,
+    IndependantColor,
+    SemanticToken,
+    ThemeBackground,
+    Font,
+    Space,
+    Radius,
+    Shadow,
+    /// Typography scale added or removed, or one of its properties changed
+    TypographyScale,
+}
+
+impl DiffCategory {
+    pub const ALL: [DiffCategory; 10] = [
+        DiffCategory::Palette,
+        DiffCategory::Tint,
+        DiffCategory::IndependantColor,
+        DiffCategory::SemanticToken,
+        DiffCategory::ThemeBackground,
+        DiffCategory::Font,
+        DiffCategory::Space,
+        DiffCategory::Radius,
+        DiffCategory::Shadow,
+        DiffCategory::TypographyScale,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            DiffCategory::Palette => "Palettes",
+            DiffCategory::Tint => "Tints",
+            DiffCategory::IndependantColor => "Independant colors",
+            DiffCategory::SemanticToken => "Semantic tokens",
+            DiffCategory::ThemeBackground => "Theme backgrounds",
+            DiffCategory::Font => "Fonts",
+            DiffCategory::Space => "Spaces",
+            DiffCategory::Radius => "Radius",
+            DiffCategory::Shadow => "Shadows",
+            DiffCategory::TypographyScale => "Typography scales",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Change of a token value, the values are the exported ones (css measurements, semantic tokens
+/// with the referenced token & its color)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenChange {
+    pub category: DiffCategory,
+    pub token_name: String,
+    pub kind: TokenChangeKind,
+    pub previous_value: Option<String>,
+    pub value: Option<String>,
+    /// ΔE 2000 between the previous & new color of a changed color token
+    pub delta_e: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignSystemDiff {
+    pub changes: Vec<TokenChange>,
+}
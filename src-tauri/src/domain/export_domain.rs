@@ -0,0 +1,185 @@
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum ExportFormat {
+    #[serde(rename = "css")]
+    Css,
+    #[serde(rename = "tokens-studio")]
+    TokensStudio,
+    #[serde(rename = "dtcg")]
+    Dtcg,
+    #[serde(rename = "style-dictionary")]
+    StyleDictionary,
+    #[serde(rename = "scss")]
+    Scss,
+    #[serde(rename = "less")]
+    Less,
+    #[serde(rename = "tailwind")]
+    Tailwind,
+    #[serde(rename = "android")]
+    Android,
+    #[serde(rename = "ios")]
+    Ios,
+    #[serde(rename = "swatches")]
+    Swatches,
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "css" => Ok(ExportFormat::Css),
+            "tokens-studio" => Ok(ExportFormat::TokensStudio),
+            "dtcg" => Ok(ExportFormat::Dtcg),
+            "style-dictionary" => Ok(ExportFormat::StyleDictionary),
+            "scss" => Ok(ExportFormat::Scss),
+            "less" => Ok(ExportFormat::Less),
+            "tailwind" => Ok(ExportFormat::Tailwind),
+            "android" => Ok(ExportFormat::Android),
+            "ios" => Ok(ExportFormat::Ios),
+            "swatches" => Ok(ExportFormat::Swatches),
+            _ => Err(anyhow!("Unknown export format : {}", value)),
+        }
+    }
+}
+
+/// A generated file, with a path relative to the export folder
+#[derive(Debug, Clone)]
+pub struct ExportFile {
+    pub path: PathBuf,
+    pub content: Vec<u8>,
+}
+
+impl ExportFile {
+    pub fn from_string(path: &str, content: String) -> ExportFile {
+        ExportFile {
+            path: PathBuf::from(path),
+            content: content.into_bytes(),
+        }
+    }
+}
+
+/// Token of a Figma Tokens Studio file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TokensStudioToken {
+    #[serde(rename = "type")]
+    pub token_type: String,
+    pub value: serde_json::Value,
+}
+
+/// Token set of a Figma Tokens Studio file : group name -> token name -> token
+pub type TokensStudioSet = IndexMap<String, IndexMap<String, TokensStudioToken>>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokensStudioMetadata {
+    pub token_set_order: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TokensStudioFile {
+    #[serde(flatten)]
+    pub token_sets: IndexMap<String, TokensStudioSet>,
+    #[serde(rename = "$metadata")]
+    pub metadata: TokensStudioMetadata,
+}
+
+/// Token of a W3C Design Tokens Community Group file (`$value` is required)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DtcgToken {
+    #[serde(rename = "$type", default, skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    #[serde(rename = "$value")]
+    pub value: serde_json::Value,
+    #[serde(
+        rename = "$description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(
+        rename = "$extensions",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub extensions: Option<serde_json::Value>,
+}
+
+/// Group of a DTCG file, `$type` is inherited by the nested tokens
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DtcgGroup {
+    #[serde(rename = "$type", default, skip_serializing_if = "Option::is_none")]
+    pub group_type: Option<String>,
+    #[serde(
+        rename = "$description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(
+        rename = "$extensions",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub extensions: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub children: IndexMap<String, DtcgNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum DtcgNode {
+    Token(DtcgToken),
+    Group(DtcgGroup),
+    /// Anything else ($schema, unknown $ properties...), ignored
+    Other(serde_json::Value),
+}
+
+/// Token of a Style Dictionary source file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StyleDictionaryToken {
+    pub value: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Style Dictionary source tree : category -> type -> item... -> token
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum StyleDictionaryNode {
+    Token(StyleDictionaryToken),
+    Group(IndexMap<String, StyleDictionaryNode>),
+}
+
+/// `theme.extend` of a tailwind.config.js
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TailwindTheme {
+    pub colors: IndexMap<String, TailwindColor>,
+    pub spacing: IndexMap<String, String>,
+    pub border_radius: IndexMap<String, String>,
+    pub box_shadow: IndexMap<String, String>,
+    pub font_family: IndexMap<String, Vec<String>>,
+    pub font_size: IndexMap<String, (String, TailwindFontSize)>,
+}
+
+/// A single color or the shades of a palette (`primary-500` -> colors.primary.500)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TailwindColor {
+    Color(String),
+    Shades(IndexMap<String, String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TailwindFontSize {
+    pub line_height: String,
+    pub font_weight: String,
+    pub letter_spacing: String,
+}
@@ -20,7 +20,6 @@ pub enum RecentFileCategory {
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-
 pub struct RemoveRecentFilesPayload {
     pub file_path: PathBuf,
     pub is_delete_from_computer: bool,
@@ -0,0 +1,107 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error};
+use serde::{Deserialize, Serialize};
+
+use super::design_system_domain::{DesignSystemCreationPayload, DesignSystemMetadata};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum ImportFormat {
+    #[serde(rename = "dtcg")]
+    Dtcg,
+    #[serde(rename = "css")]
+    Css,
+    #[serde(rename = "tokens-studio")]
+    TokensStudio,
+}
+
+impl FromStr for ImportFormat {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "dtcg" => Ok(ImportFormat::Dtcg),
+            "css" => Ok(ImportFormat::Css),
+            "tokens-studio" => Ok(ImportFormat::TokensStudio),
+            _ => Err(anyhow!("Unknown import format : {}", value)),
+        }
+    }
+}
+
+/// Create a new design system (same fields as the creation) from a tokens file
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignSystemImportPayload {
+    pub name: String,
+    pub folder_path: String,
+    pub banner: String,
+    pub logo: String,
+    pub file_path: PathBuf,
+    pub format: ImportFormat,
+}
+
+impl DesignSystemImportPayload {
+    pub fn to_creation_payload(&self) -> DesignSystemCreationPayload {
+        DesignSystemCreationPayload {
+            name: self.name.clone(),
+            folder_path: self.folder_path.clone(),
+            banner: self.banner.clone(),
+            logo: self.logo.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub design_system_metadata: DesignSystemMetadata,
+    /// Tokens of the file which have no equivalent in uitea (path of the token)
+    pub unmapped_tokens: Vec<String>,
+}
+
+/// Swatch file of a design tool, detected from its extension
+#[derive(Debug, Clone, PartialEq)]
+pub enum SwatchFormat {
+    /// Adobe Swatch Exchange
+    Ase,
+    /// Adobe Photoshop color swatches
+    Aco,
+    /// GIMP / Inkscape palette
+    Gpl,
+    /// Sketch Palettes plugin
+    SketchPalette,
+}
+
+impl SwatchFormat {
+    pub fn from_path(path: &Path) -> Result<SwatchFormat, Error> {
+        let extension: String = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        match extension.as_str() {
+            "ase" => Ok(SwatchFormat::Ase),
+            "aco" => Ok(SwatchFormat::Aco),
+            "gpl" => Ok(SwatchFormat::Gpl),
+            "sketchpalette" => Ok(SwatchFormat::SketchPalette),
+            _ => Err(anyhow!("Unknown swatch file : {}", path.display())),
+        }
+    }
+}
+
+/// Named color of a swatch file (hex color)
+#[derive(Debug, Clone)]
+pub struct Swatch {
+    pub name: String,
+    pub color: String,
+}
+
+/// Group of swatches (a palette of the swatch file)
+#[derive(Debug, Clone)]
+pub struct SwatchGroup {
+    pub name: String,
+    pub swatches: Vec<Swatch>,
+}
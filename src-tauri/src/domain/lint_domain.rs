@@ -0,0 +1,120 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintRule {
+    /// Color referencing a missing palette tint or independant color
+    UnresolvedColorReference,
+    /// Palette tint or independant color which is not a css color
+    InvalidColor,
+    DuplicateSpaceKey,
+    /// Space values not increasing along the spacing scale
+    NonMonotonicSpacing,
+    LineHeightBelowFontSize,
+    /// Additional font without file under `exports/fonts`
+    MissingFontFile,
+    /// Palette without tint referenced by the semantic tokens, typographies or shadows
+    UnusedPalette,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 7] = [
+        LintRule::UnresolvedColorReference,
+        LintRule::InvalidColor,
+        LintRule::DuplicateSpaceKey,
+        LintRule::NonMonotonicSpacing,
+        LintRule::LineHeightBelowFontSize,
+        LintRule::MissingFontFile,
+        LintRule::UnusedPalette,
+    ];
+
+    pub fn default_level(&self) -> LintLevel {
+        match self {
+            LintRule::UnresolvedColorReference
+            | LintRule::InvalidColor
+            | LintRule::DuplicateSpaceKey => LintLevel::Error,
+            LintRule::NonMonotonicSpacing
+            | LintRule::LineHeightBelowFontSize
+            | LintRule::MissingFontFile
+            | LintRule::UnusedPalette => LintLevel::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintLevel {
+    Off,
+    Warning,
+    Error,
+}
+
+/// `lint.yaml` of a design system : level of the rules, the missing rules keep their default level
+/// ```yaml
+/// rules:
+///   unusedPalette: off
+///   nonMonotonicSpacing: error
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: IndexMap<LintRule, LintLevel>,
+}
+
+impl LintConfig {
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.rules
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub rule: LintRule,
+    pub level: LintLevel,
+    /// Token or file concerned (ex: base-text-default, spaces.md)
+    pub location: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.level == LintLevel::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_rules_keep_their_default_level() {
+        let config: LintConfig =
+            serde_yaml::from_str("rules:\n  unusedPalette: off\n  nonMonotonicSpacing: error\n")
+                .unwrap();
+        assert_eq!(config.level(LintRule::UnusedPalette), LintLevel::Off);
+        assert_eq!(
+            config.level(LintRule::NonMonotonicSpacing),
+            LintLevel::Error
+        );
+        assert_eq!(config.level(LintRule::MissingFontFile), LintLevel::Warning);
+        assert_eq!(config.level(LintRule::InvalidColor), LintLevel::Error);
+
+        let empty: LintConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(empty.rules.is_empty());
+        assert!(serde_yaml::from_str::<LintConfig>("rules:\n  unknownRule: off\n").is_err());
+    }
+}
@@ -45,6 +45,9 @@ pub struct PalettesStoreSettings {
 pub struct PaletteBuilderFile {
     pub palettes: Vec<PaletteBuild>,
     pub settings: PalettesStoreSettings,
+    /// Files saved into a design system folder, kept when the file is rewritten
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PaletteBuilderMetadata>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -70,8 +73,42 @@ impl PaletteBuilderFile {
         PaletteBuilderFile {
             palettes: palettes.clone(),
             settings: settings.clone(),
+            metadata: None,
         }
     }
+
+    /// Rename a palette, or one of its tints when `tint_name` is given. Returns whether the file changed
+    pub fn rename_palette(
+        &mut self,
+        palette_name: &str,
+        tint_name: Option<&str>,
+        new_name: &str,
+    ) -> bool {
+        let mut renamed = false;
+        for palette in self
+            .palettes
+            .iter_mut()
+            .filter(|palette| palette.name == palette_name)
+        {
+            match tint_name {
+                None => {
+                    palette.name = new_name.to_string();
+                    renamed = true;
+                }
+                Some(tint_name) => {
+                    for tint in palette
+                        .tints
+                        .iter_mut()
+                        .filter(|tint| tint.name == tint_name)
+                    {
+                        tint.name = new_name.to_string();
+                        renamed = true;
+                    }
+                }
+            }
+        }
+        renamed
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -90,3 +127,61 @@ pub struct PaletteBuilderRenamePayload {
     pub new_name: String,
     pub design_system_path: PathBuf,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette_builder_file() -> PaletteBuilderFile {
+        serde_json::from_value(serde_json::json!({
+            "palettes": [{
+                "id": "1",
+                "name": "primary",
+                "tints": [
+                    { "name": "50", "isAnchor": false, "isCenter": false, "color": "#eff6ff" },
+                    { "name": "500", "isAnchor": true, "isCenter": true, "color": "#3b82f6" }
+                ],
+                "settings": {
+                    "lightnessMax": 0.97,
+                    "lightnessMin": 0.2,
+                    "satChromaGapLeft": 0.0,
+                    "satChromaGapRight": 0.0,
+                    "hueGapLeft": 0.0,
+                    "hueGapRight": 0.0
+                }
+            }],
+            "settings": {
+                "steps": 2,
+                "tintNamingMode": "50-950",
+                "interpolationColorSpace": "oklch",
+                "paletteSettings": {
+                    "lightnessMax": 0.97,
+                    "lightnessMin": 0.2,
+                    "satChromaGapLeft": 0.0,
+                    "satChromaGapRight": 0.0,
+                    "hueGapLeft": 0.0,
+                    "hueGapRight": 0.0
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn renames_palettes_and_tints() {
+        let mut file = palette_builder_file();
+        assert!(file.rename_palette("primary", None, "brand"));
+        assert_eq!(file.palettes[0].name, "brand");
+        assert!(file.rename_palette("brand", Some("500"), "main"));
+        assert_eq!(file.palettes[0].tints[1].name, "main");
+        assert_eq!(file.palettes[0].tints[0].name, "50");
+    }
+
+    #[test]
+    fn unknown_names_leave_the_file_unchanged() {
+        let mut file = palette_builder_file();
+        assert!(!file.rename_palette("gray", None, "neutral"));
+        assert!(!file.rename_palette("primary", Some("900"), "darkest"));
+        assert_eq!(file.palettes[0].name, "primary");
+    }
+}
@@ -0,0 +1,584 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Error, Result};
+
+use super::{
+    color_domain::{Hsl, Lch, Oklch, Rgb},
+    palette_builder_domain::{PaletteBuild, PaletteSettings, PalettesStoreSettings, TintBuild},
+};
+
+/// Bounds of `PalettesStoreSettings.steps`, same as the settings of the palette builder
+pub const MIN_STEPS: i32 = 3;
+pub const MAX_STEPS: i32 = 20;
+
+const DEFAULT_TINT_NAMING_MODE: &str = "50,100,200...900,950";
+
+/// Hue range of the hue gap settings, around the hue of the lightest & darkest tints
+const HUE_GAP_LEFT_RANGE: f64 = 30.0;
+const HUE_GAP_RIGHT_RANGE: f64 = 20.0;
+
+/// Color space in which the tints are interpolated (`interpolationColorSpace` of the settings)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterpolationColorSpace {
+    Oklch,
+    Lch,
+    Hsl,
+}
+
+impl FromStr for InterpolationColorSpace {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<InterpolationColorSpace, Error> {
+        match value {
+            "oklch" => Ok(InterpolationColorSpace::Oklch),
+            "lch" => Ok(InterpolationColorSpace::Lch),
+            "hsl" => Ok(InterpolationColorSpace::Hsl),
+            _ => Err(anyhow!("Unknown interpolation color space : {}", value)),
+        }
+    }
+}
+
+/// Cylindrical coordinates of a color, with the scale of the pickers of the webview (hsl saturation
+/// & lightness in %, lch chroma up to 150, oklch chroma up to 0.4). An achromatic color has no hue
+#[derive(Debug, Clone, Copy)]
+struct PolarColor {
+    lightness: f64,
+    chroma: f64,
+    hue: Option<f64>,
+}
+
+impl InterpolationColorSpace {
+    /// Maximum of the saturation / chroma axis
+    fn chroma_max(&self) -> f64 {
+        match self {
+            InterpolationColorSpace::Oklch => 0.4,
+            InterpolationColorSpace::Lch => 150.0,
+            InterpolationColorSpace::Hsl => 100.0,
+        }
+    }
+
+    /// Chroma under which the hue is powerless
+    fn achromatic_chroma(&self) -> f64 {
+        match self {
+            InterpolationColorSpace::Oklch => 0.0002,
+            InterpolationColorSpace::Lch => 0.02,
+            InterpolationColorSpace::Hsl => 1e-6,
+        }
+    }
+
+    fn polar_coordinates(&self, color: &Rgb) -> PolarColor {
+        let (lightness, chroma, hue) = match self {
+            InterpolationColorSpace::Oklch => {
+                let Oklch { l, c, h } = color.to_oklch();
+                (l, c, h)
+            }
+            InterpolationColorSpace::Lch => {
+                let Lch { l, c, h } = color.to_lch();
+                (l, c, h)
+            }
+            InterpolationColorSpace::Hsl => {
+                let Hsl { h, s, l } = color.to_hsl();
+                (l * 100.0, s * 100.0, h)
+            }
+        };
+        PolarColor {
+            lightness,
+            chroma,
+            hue: if chroma < self.achromatic_chroma() {
+                None
+            } else {
+                Some(hue)
+            },
+        }
+    }
+
+    fn polar_color(&self, color: &PolarColor) -> Rgb {
+        let PolarColor {
+            lightness,
+            chroma,
+            hue,
+        } = *color;
+        let h = hue.unwrap_or(0.0);
+        match self {
+            InterpolationColorSpace::Oklch => Rgb::from_oklch(
+                &Oklch {
+                    l: lightness,
+                    c: chroma,
+                    h,
+                },
+                1.0,
+            ),
+            InterpolationColorSpace::Lch => Rgb::from_lch(
+                &Lch {
+                    l: lightness,
+                    c: chroma,
+                    h,
+                },
+                1.0,
+            ),
+            InterpolationColorSpace::Hsl => Rgb::from_hsl(
+                &Hsl {
+                    h,
+                    s: chroma / 100.0,
+                    l: lightness / 100.0,
+                },
+                1.0,
+            ),
+        }
+    }
+
+    /// `length` colors from `start` to `end` (both included). The hue takes the shorter arc,
+    /// a powerless hue takes the hue of the other color
+    fn interpolate(&self, start: &Rgb, end: &Rgb, length: usize) -> Vec<Rgb> {
+        let start = self.polar_coordinates(start);
+        let end = self.polar_coordinates(end);
+        let (start_hue, end_hue) = match (start.hue, end.hue) {
+            (Some(start_hue), Some(end_hue)) => {
+                let delta = end_hue - start_hue;
+                if delta > 180.0 {
+                    (start_hue + 360.0, end_hue)
+                } else if delta < -180.0 {
+                    (start_hue, end_hue + 360.0)
+                } else {
+                    (start_hue, end_hue)
+                }
+            }
+            (Some(hue), None) | (None, Some(hue)) => (hue, hue),
+            (None, None) => (0.0, 0.0),
+        };
+        let mix = |from: f64, to: f64, ratio: f64| from + (to - from) * ratio;
+        (0..length)
+            .map(|index| {
+                let ratio = if length <= 1 {
+                    0.0
+                } else {
+                    index as f64 / (length - 1) as f64
+                };
+                self.polar_color(&PolarColor {
+                    lightness: mix(start.lightness, end.lightness, ratio),
+                    chroma: mix(start.chroma, end.chroma, ratio),
+                    hue: Some(mix(start_hue, end_hue, ratio).rem_euclid(360.0)),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Name of a tint from its position, `manual` keeps the existing name
+pub fn tint_name(index: usize, length: usize, mode: &str, existing_name: Option<&str>) -> String {
+    if let ("manual", Some(name)) = (mode, existing_name) {
+        return name.to_string();
+    }
+    match mode {
+        "5,10,20...90,95" | "50,100,200...900,950" => {
+            let (step, offset) = if mode == "5,10,20...90,95" {
+                (10, 5)
+            } else {
+                (100, 50)
+            };
+            if length == 1 || index == 0 {
+                offset.to_string()
+            } else if index == length - 1 {
+                (index * step - offset).to_string()
+            } else {
+                (index * step).to_string()
+            }
+        }
+        "0,10,20..." => (index * 10).to_string(),
+        "0,100,200..." => (index * 100).to_string(),
+        "10,20,30..." => ((index + 1) * 10).to_string(),
+        "100,200,300..." => ((index + 1) * 100).to_string(),
+        _ => tint_name(index, length, DEFAULT_TINT_NAMING_MODE, None),
+    }
+}
+
+/// Value of a slider with 3 points : the axis minimum at 0, the current value at 0.5 and the axis
+/// maximum at 1
+fn linear_piece_interpolation(min: f64, center: f64, max: f64, position: f64) -> f64 {
+    if position <= 0.5 {
+        min + position / 0.5 * (center - min)
+    } else {
+        center + (position - 0.5) / 0.5 * (max - center)
+    }
+}
+
+fn parse_tint_color(tint: &TintBuild) -> Result<Rgb> {
+    Rgb::parse(&tint.color).with_context(|| format!("Invalid color of the tint {}", tint.name))
+}
+
+/// Lightest & darkest tints of a palette : the center mixed with white (`lightness_max`) and black
+/// (`lightness_min`) in OKLCH. With the existing tints of the palette, the ends take the hue of the
+/// anchors and the saturation / chroma & hue gaps of the settings are applied
+pub fn ends_tints(
+    center: &Rgb,
+    settings: &PaletteSettings,
+    existing_tints: Option<&[TintBuild]>,
+    interpolation_color_space: InterpolationColorSpace,
+) -> Result<(Rgb, Rgb)> {
+    let center_oklch = center.to_oklch();
+    let mut start = Oklch {
+        l: center_oklch.l + (1.0 - center_oklch.l) * settings.lightness_max,
+        c: center_oklch.c * (1.0 - settings.lightness_max),
+        h: center_oklch.h,
+    };
+    let mut end = Oklch {
+        l: center_oklch.l * settings.lightness_min,
+        c: center_oklch.c * settings.lightness_min,
+        h: center_oklch.h,
+    };
+
+    let tints = match existing_tints {
+        Some(tints) => tints,
+        None => return Ok((Rgb::from_oklch(&start, 1.0), Rgb::from_oklch(&end, 1.0))),
+    };
+    let center_index = match tints.iter().position(|tint| tint.is_center == Some(true)) {
+        Some(center_index) => center_index,
+        None => return Ok((Rgb::from_oklch(&start, 1.0), Rgb::from_oklch(&end, 1.0))),
+    };
+    let is_anchor = |tint: &&TintBuild| tint.is_anchor == Some(true);
+    if let Some(left_anchor) = tints[..center_index].iter().find(is_anchor) {
+        start.h = parse_tint_color(left_anchor)?.to_oklch().h;
+    }
+    if let Some(right_anchor) = tints[center_index + 1..].iter().rev().find(is_anchor) {
+        end.h = parse_tint_color(right_anchor)?.to_oklch().h;
+    }
+
+    let space = interpolation_color_space;
+    let apply_gaps = |color: &Oklch, chroma_gap: f64, hue_gap: f64, hue_range: f64| -> Rgb {
+        let mut polar = space.polar_coordinates(&Rgb::from_oklch(color, 1.0));
+        if chroma_gap != 0.5 {
+            polar.chroma =
+                linear_piece_interpolation(0.0, polar.chroma, space.chroma_max(), chroma_gap);
+        }
+        if hue_gap != 0.5 {
+            if let Some(hue) = polar.hue {
+                polar.hue = Some(linear_piece_interpolation(
+                    hue - hue_range,
+                    hue,
+                    hue + hue_range,
+                    hue_gap,
+                ));
+            }
+        }
+        space.polar_color(&polar)
+    };
+    Ok((
+        apply_gaps(
+            &start,
+            settings.sat_chroma_gap_left,
+            settings.hue_gap_left,
+            HUE_GAP_LEFT_RANGE,
+        ),
+        apply_gaps(
+            &end,
+            settings.sat_chroma_gap_right,
+            settings.hue_gap_right,
+            HUE_GAP_RIGHT_RANGE,
+        ),
+    ))
+}
+
+/// `settings.steps` tints : the first, center, last and anchor tints are kept, the others are
+/// interpolated between them. The tints are mapped into the sRGB gamut
+pub fn construct_tints(
+    original_tints: &[TintBuild],
+    settings: &PalettesStoreSettings,
+) -> Result<Vec<TintBuild>> {
+    let space = InterpolationColorSpace::from_str(&settings.interpolation_color_space)?;
+    let steps = settings.steps.clamp(MIN_STEPS, MAX_STEPS) as usize;
+    let (first, last) = match (original_tints.first(), original_tints.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(anyhow!("A palette needs at least one tint")),
+    };
+
+    // Step 1 : the fixed tints at their position, the other positions are empty
+    let center_index = steps / 2;
+    let anchor_tints: Vec<Option<TintBuild>> = (0..steps)
+        .map(|index| {
+            if index == 0 {
+                Some(first.clone())
+            } else if index == center_index {
+                Some(
+                    original_tints
+                        .iter()
+                        .find(|tint| tint.is_center == Some(true))
+                        .cloned()
+                        .unwrap_or_else(|| TintBuild {
+                            is_center: Some(true),
+                            ..original_tints[original_tints.len() / 2].clone()
+                        }),
+                )
+            } else if index == steps - 1 {
+                Some(last.clone())
+            } else {
+                original_tints
+                    .get(index)
+                    .filter(|tint| tint.is_anchor == Some(true) && tint.is_center != Some(true))
+                    .cloned()
+            }
+        })
+        .collect();
+    let anchor_colors: Vec<Option<Rgb>> = anchor_tints
+        .iter()
+        .map(|tint| tint.as_ref().map(parse_tint_color).transpose())
+        .collect::<Result<Vec<Option<Rgb>>>>()?;
+
+    // Step 2 : interpolate the empty positions between the previous & next fixed tints
+    let mut colors: Vec<Rgb> = vec![];
+    let mut previous: (usize, Rgb) = (0, Rgb::new(0.0, 0.0, 0.0));
+    let mut index = 0;
+    while index < steps {
+        match anchor_colors[index] {
+            Some(color) => {
+                colors.push(color);
+                previous = (index, color);
+                index += 1;
+            }
+            None => {
+                let (next_index, next_color) = anchor_colors
+                    .iter()
+                    .enumerate()
+                    .skip(index)
+                    .find_map(|(next_index, color)| color.map(|color| (next_index, color)))
+                    .ok_or_else(|| anyhow!("Missing last tint"))?;
+                let between =
+                    space.interpolate(&previous.1, &next_color, next_index - previous.0 + 1);
+                colors.extend_from_slice(&between[1..between.len() - 1]);
+                index = next_index;
+            }
+        }
+    }
+
+    // Step 3 : name the tints
+    let keep_names = original_tints.len() == steps;
+    Ok(colors
+        .iter()
+        .enumerate()
+        .map(|(index, color)| {
+            let anchor_tint = anchor_tints[index].as_ref();
+            TintBuild {
+                name: tint_name(
+                    index,
+                    steps,
+                    &settings.tint_naming_mode,
+                    if keep_names {
+                        Some(original_tints[index].name.as_str())
+                    } else {
+                        None
+                    },
+                ),
+                is_anchor: anchor_tint.and_then(|tint| tint.is_anchor),
+                is_center: anchor_tint.and_then(|tint| tint.is_center),
+                color: color.to_gamut().to_hex(),
+            }
+        })
+        .collect())
+}
+
+/// Tints of a new palette around a center color
+pub fn create_palette_tints(
+    center: &Rgb,
+    settings: &PalettesStoreSettings,
+) -> Result<Vec<TintBuild>> {
+    let space = InterpolationColorSpace::from_str(&settings.interpolation_color_space)?;
+    let (start, end) = ends_tints(center, &settings.palette_settings, None, space)?;
+    let tints: Vec<TintBuild> = [start, *center, end]
+        .iter()
+        .enumerate()
+        .map(|(index, color)| TintBuild {
+            name: tint_name(index, 3, &settings.tint_naming_mode, None),
+            is_anchor: None,
+            is_center: Some(index == 1),
+            color: color.to_gamut().to_hex(),
+        })
+        .collect();
+    construct_tints(&tints, settings)
+}
+
+/// Regenerate the tints of a palette : the ends are computed again from the center & the settings
+/// of the palette (unless they are anchors), the anchors are kept
+pub fn regenerate_palette_tints(
+    palette: &PaletteBuild,
+    settings: &PalettesStoreSettings,
+) -> Result<Vec<TintBuild>> {
+    let space = InterpolationColorSpace::from_str(&settings.interpolation_color_space)?;
+    let mut tints = palette.tints.clone();
+    if let Some(center) = tints.iter().find(|tint| tint.is_center == Some(true)) {
+        let (start, end) = ends_tints(
+            &parse_tint_color(center)?,
+            &palette.settings,
+            Some(&palette.tints),
+            space,
+        )?;
+        let last_index = tints.len() - 1;
+        if tints[0].is_anchor != Some(true) {
+            tints[0].color = start.to_gamut().to_hex();
+        }
+        if tints[last_index].is_anchor != Some(true) {
+            tints[last_index].color = end.to_gamut().to_hex();
+        }
+    }
+    construct_tints(&tints, settings)
+        .with_context(|| format!("Fail to regenerate the palette {}", palette.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Settings without saturation / chroma & hue gaps
+    fn settings(steps: i32, interpolation_color_space: &str) -> PalettesStoreSettings {
+        PalettesStoreSettings {
+            steps,
+            tint_naming_mode: String::from(DEFAULT_TINT_NAMING_MODE),
+            interpolation_color_space: String::from(interpolation_color_space),
+            palette_settings: PaletteSettings {
+                lightness_max: 0.9,
+                lightness_min: 0.3,
+                sat_chroma_gap_left: 0.5,
+                sat_chroma_gap_right: 0.5,
+                hue_gap_left: 0.5,
+                hue_gap_right: 0.5,
+            },
+        }
+    }
+
+    fn tint(name: &str, color: &str, is_anchor: bool, is_center: bool) -> TintBuild {
+        TintBuild {
+            name: String::from(name),
+            is_anchor: Some(is_anchor),
+            is_center: Some(is_center),
+            color: String::from(color),
+        }
+    }
+
+    fn lightness(tint: &TintBuild) -> f64 {
+        Rgb::parse(&tint.color).unwrap().to_oklch().l
+    }
+
+    #[test]
+    fn names_the_tints_from_their_position() {
+        let names = |mode: &str, length: usize| -> Vec<String> {
+            (0..length)
+                .map(|index| tint_name(index, length, mode, None))
+                .collect()
+        };
+        assert_eq!(
+            names("50,100,200...900,950", 11),
+            ["50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950"]
+        );
+        assert_eq!(names("5,10,20...90,95", 3), ["5", "10", "15"]);
+        assert_eq!(names("0,10,20...", 3), ["0", "10", "20"]);
+        assert_eq!(names("100,200,300...", 3), ["100", "200", "300"]);
+        assert_eq!(names("unknown", 3), ["50", "100", "150"]);
+        assert_eq!(tint_name(1, 3, "manual", Some("brand")), "brand");
+        assert_eq!(tint_name(1, 3, "manual", None), "100");
+    }
+
+    #[test]
+    fn new_palettes_go_from_light_to_dark_around_the_center() {
+        for space in ["oklch", "lch", "hsl"] {
+            let tints = create_palette_tints(&Rgb::parse("#3b82f6").unwrap(), &settings(11, space))
+                .unwrap();
+            assert_eq!(tints.len(), 11);
+            assert_eq!(tints[5].color, "#3b82f6");
+            assert_eq!(tints[5].is_center, Some(true));
+            assert_eq!(tints[10].name, "950");
+            assert!(
+                tints
+                    .windows(2)
+                    .all(|pair| lightness(&pair[0]) > lightness(&pair[1])),
+                "{}",
+                space
+            );
+        }
+    }
+
+    #[test]
+    fn anchors_are_kept_at_their_position() {
+        let original_tints = vec![
+            tint("50", "#eff6ff", false, false),
+            tint("100", "#ff0000", true, false),
+            tint("200", "#bfdbfe", false, false),
+            tint("300", "#3b82f6", false, true),
+            tint("400", "#1e3a8a", false, false),
+        ];
+        let tints = construct_tints(&original_tints, &settings(5, "oklch")).unwrap();
+        let colors: Vec<&str> = tints.iter().map(|tint| tint.color.as_str()).collect();
+        // The center moves to the middle, the tint after it is interpolated
+        assert_eq!(colors[..3], ["#eff6ff", "#ff0000", "#3b82f6"]);
+        assert_eq!(colors[4], "#1e3a8a");
+        assert!(lightness(&tints[2]) > lightness(&tints[3]));
+        assert!(lightness(&tints[3]) > lightness(&tints[4]));
+        assert_eq!(tints[1].is_anchor, Some(true));
+        assert_eq!(tints[2].is_center, Some(true));
+        assert_eq!(tints[3].is_center, None);
+    }
+
+    #[test]
+    fn regenerated_palettes_keep_the_anchored_ends() {
+        let settings = settings(3, "oklch");
+        let palette = PaletteBuild {
+            id: String::from("1"),
+            name: String::from("primary"),
+            tints: vec![
+                tint("50", "#ffffff", false, false),
+                tint("500", "#3b82f6", false, true),
+                tint("950", "#000000", true, false),
+            ],
+            settings: settings.palette_settings.clone(),
+        };
+        let tints = regenerate_palette_tints(&palette, &settings).unwrap();
+        let (start, _) = ends_tints(
+            &Rgb::parse("#3b82f6").unwrap(),
+            &settings.palette_settings,
+            None,
+            InterpolationColorSpace::Oklch,
+        )
+        .unwrap();
+        assert_eq!(tints[0].color, start.to_gamut().to_hex());
+        assert_eq!(tints[1].color, "#3b82f6");
+        assert_eq!(tints[2].color, "#000000");
+    }
+
+    #[test]
+    fn hues_take_the_shorter_arc() {
+        let colors = InterpolationColorSpace::Hsl.interpolate(
+            &Rgb::from_hsl(
+                &Hsl {
+                    h: 350.0,
+                    s: 1.0,
+                    l: 0.5,
+                },
+                1.0,
+            ),
+            &Rgb::from_hsl(
+                &Hsl {
+                    h: 30.0,
+                    s: 1.0,
+                    l: 0.5,
+                },
+                1.0,
+            ),
+            3,
+        );
+        assert!((colors[1].to_hsl().h - 10.0).abs() < 0.5);
+        // Gray has no hue, the blue hue is kept
+        let colors = InterpolationColorSpace::Oklch.interpolate(
+            &Rgb::parse("#3b82f6").unwrap(),
+            &Rgb::parse("#808080").unwrap(),
+            3,
+        );
+        let hue = Rgb::parse("#3b82f6").unwrap().to_oklch().h;
+        assert!((colors[1].to_oklch().h - hue).abs() < 1.0);
+    }
+
+    #[test]
+    fn invalid_settings_are_errors() {
+        let center = Rgb::parse("#3b82f6").unwrap();
+        assert!(create_palette_tints(&center, &settings(11, "rgb")).is_err());
+        assert!(construct_tints(&[], &settings(11, "oklch")).is_err());
+        let invalid_tints = vec![tint("50", "not a color", false, false)];
+        assert!(construct_tints(&invalid_tints, &settings(3, "oklch")).is_err());
+    }
+}
@@ -0,0 +1,706 @@
+use std::{collections::HashMap, fmt};
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    color_domain::Rgb,
+    design_system_domain::{
+        ColorCombination, ColorCombinationCollection, DesignSystem, IndependantColors, Palette,
+        SemanticColorTokens, ThemeSemanticColorTokens,
+    },
+};
+
+/// Independant colors can reference each other, deeper chains are considered as cycles
+const MAX_REFERENCE_DEPTH: usize = 8;
+
+/// Reference to a palette tint (`{palettes.primary.500}`) or an independant color (`{colors.success}`,
+/// `{colors.white}`), usable instead of a color in the semantic tokens, theme backgrounds, typography
+/// & shadow colors and independant colors
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorReference {
+    PaletteTint {
+        palette_name: String,
+        tint_label: String,
+    },
+    IndependantColor {
+        label: String,
+    },
+}
+
+impl ColorReference {
+    pub fn parse(value: &str) -> Option<ColorReference> {
+        let path = value.trim().strip_prefix('{')?.strip_suffix('}')?.trim();
+        if let Some(tint_path) = path.strip_prefix("palettes.") {
+            let (palette_name, tint_label) = tint_path.split_once('.')?;
+            if palette_name.is_empty() || tint_label.is_empty() {
+                return None;
+            }
+            Some(ColorReference::PaletteTint {
+                palette_name: palette_name.to_string(),
+                tint_label: tint_label.to_string(),
+            })
+        } else {
+            let label = path.strip_prefix("colors.")?;
+            if label.is_empty() {
+                return None;
+            }
+            Some(ColorReference::IndependantColor {
+                label: label.to_string(),
+            })
+        }
+    }
+
+    /// Token name of the exports & the webview (palette-primary-500, color-white)
+    pub fn token_name(&self) -> String {
+        match self {
+            ColorReference::PaletteTint {
+                palette_name,
+                tint_label,
+            } => format!("palette-{}-{}", palette_name, tint_label),
+            ColorReference::IndependantColor { label } => format!("color-{}", label),
+        }
+    }
+
+    /// Referenced color, following the references between independant colors
+    pub fn find_color(
+        &self,
+        palettes: &[Palette],
+        independant_colors: &IndependantColors,
+    ) -> Option<String> {
+        let mut reference = self.clone();
+        for _ in 0..MAX_REFERENCE_DEPTH {
+            let color = match &reference {
+                ColorReference::PaletteTint {
+                    palette_name,
+                    tint_label,
+                } => palettes
+                    .iter()
+                    .find(|palette| &palette.palette_name == palette_name)?
+                    .tints
+                    .iter()
+                    .find(|tint| &tint.label == tint_label)?
+                    .color
+                    .clone(),
+                ColorReference::IndependantColor { label } if label == "white" => {
+                    independant_colors.white.clone()
+                }
+                ColorReference::IndependantColor { label } => independant_colors
+                    .independant_colors
+                    .iter()
+                    .find(|tint| &tint.label == label)?
+                    .color
+                    .clone(),
+            };
+            match ColorReference::parse(&color) {
+                Some(next_reference) => reference = next_reference,
+                None => return Some(color),
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for ColorReference {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorReference::PaletteTint {
+                palette_name,
+                tint_label,
+            } => write!(formatter, "{{palettes.{}.{}}}", palette_name, tint_label),
+            ColorReference::IndependantColor { label } => {
+                write!(formatter, "{{colors.{}}}", label)
+            }
+        }
+    }
+}
+
+/// What a field holding a reference is resolved to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReferenceTarget {
+    /// Fields already holding token names (semantic tokens, typography & shadow colors)
+    TokenName,
+    /// Fields holding colors (theme backgrounds, independant colors)
+    Color,
+}
+
+/// Replace the references by token names or colors (for the exports & the webview), the unresolved
+/// references are kept
+pub fn resolve_references(design_system: &mut DesignSystem) {
+    let palettes: Vec<Palette> = design_system.palettes.clone();
+    let independant_colors: IndependantColors = design_system.independant_colors.clone();
+    for (_, target, value) in reference_fields(design_system) {
+        if let Some(reference) = ColorReference::parse(value) {
+            if let Some(color) = reference.find_color(&palettes, &independant_colors) {
+                *value = match target {
+                    ReferenceTarget::TokenName => reference.token_name(),
+                    ReferenceTarget::Color => color,
+                };
+            }
+        }
+    }
+}
+
+/// Put back the references of the stored design system on the resolved values left unchanged by the
+/// webview, a color still matches its reference when a palette has been regenerated meanwhile
+pub fn restore_references(design_system: &mut DesignSystem, stored_design_system: &DesignSystem) {
+    let mut stored_design_system = stored_design_system.clone();
+    let stored_palettes: Vec<Palette> = stored_design_system.palettes.clone();
+    let stored_independant_colors: IndependantColors =
+        stored_design_system.independant_colors.clone();
+    let stored_references: HashMap<String, ColorReference> =
+        reference_fields(&mut stored_design_system)
+            .into_iter()
+            .filter_map(|(key, _, value)| {
+                ColorReference::parse(value).map(|reference| (key, reference))
+            })
+            .collect();
+    if stored_references.is_empty() {
+        return;
+    }
+
+    let palettes: Vec<Palette> = design_system.palettes.clone();
+    let independant_colors: IndependantColors = design_system.independant_colors.clone();
+    let same_color = |first: &str, second: &str| match (Rgb::parse(first), Rgb::parse(second)) {
+        (Ok(first), Ok(second)) => first.to_hex() == second.to_hex(),
+        _ => false,
+    };
+    for (key, target, value) in reference_fields(design_system) {
+        let reference = match stored_references.get(&key) {
+            Some(reference) => reference,
+            None => continue,
+        };
+        let unchanged = match target {
+            ReferenceTarget::TokenName => *value == reference.token_name(),
+            ReferenceTarget::Color => [
+                reference.find_color(&stored_palettes, &stored_independant_colors),
+                reference.find_color(&palettes, &independant_colors),
+            ]
+            .iter()
+            .flatten()
+            .any(|color| same_color(color, value)),
+        };
+        if unchanged {
+            *value = reference.to_string();
+        }
+    }
+}
+
+/// Reference field rewritten by a rename
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedReference {
+    /// Field of the design system (ex: themes.dark.background, base-text-default)
+    pub location: String,
+    pub previous_value: String,
+    pub value: String,
+}
+
+/// A palette or tint name is a segment of the references (`{palettes.primary.500}`) and of the
+/// palette builder file paths
+fn validate_name(kind: &str, name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("The {} name can not be empty", kind);
+    }
+    if name.contains(['.', '/', '\\', '{', '}']) {
+        bail!(
+            "The {} name can't contain '.', '/', '{{' or '}}' : {}",
+            kind,
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Rename a palette and the references to its tints (references & token names)
+pub fn rename_palette(
+    design_system: &mut DesignSystem,
+    palette_name: &str,
+    new_palette_name: &str,
+) -> Result<Vec<RenamedReference>> {
+    let new_palette_name = new_palette_name.trim();
+    validate_name("palette", new_palette_name)?;
+    if design_system
+        .palettes
+        .iter()
+        .any(|palette| palette.palette_name == new_palette_name)
+    {
+        bail!("A palette is already named {}", new_palette_name);
+    }
+    let palette = design_system
+        .palettes
+        .iter_mut()
+        .find(|palette| palette.palette_name == palette_name)
+        .ok_or_else(|| anyhow!("Unknown palette : {}", palette_name))?;
+    palette.palette_name = new_palette_name.to_string();
+    let tint_labels: Vec<String> = palette
+        .tints
+        .iter()
+        .map(|tint| tint.label.clone())
+        .collect();
+
+    Ok(rename_references(
+        design_system,
+        tint_labels
+            .into_iter()
+            .map(|tint_label| {
+                (
+                    ColorReference::PaletteTint {
+                        palette_name: palette_name.to_string(),
+                        tint_label: tint_label.clone(),
+                    },
+                    ColorReference::PaletteTint {
+                        palette_name: new_palette_name.to_string(),
+                        tint_label,
+                    },
+                )
+            })
+            .collect(),
+    ))
+}
+
+/// Rename a tint of a palette and the references to it
+pub fn rename_tint(
+    design_system: &mut DesignSystem,
+    palette_name: &str,
+    tint_label: &str,
+    new_tint_label: &str,
+) -> Result<Vec<RenamedReference>> {
+    let new_tint_label = new_tint_label.trim();
+    validate_name("tint", new_tint_label)?;
+    let palette = design_system
+        .palettes
+        .iter_mut()
+        .find(|palette| palette.palette_name == palette_name)
+        .ok_or_else(|| anyhow!("Unknown palette : {}", palette_name))?;
+    if palette
+        .tints
+        .iter()
+        .any(|tint| tint.label == new_tint_label)
+    {
+        bail!(
+            "A tint of the palette {} is already named {}",
+            palette_name,
+            new_tint_label
+        );
+    }
+    let tint = palette
+        .tints
+        .iter_mut()
+        .find(|tint| tint.label == tint_label)
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown tint {} in the palette {}",
+                tint_label,
+                palette_name
+            )
+        })?;
+    tint.label = new_tint_label.to_string();
+
+    Ok(rename_references(
+        design_system,
+        vec![(
+            ColorReference::PaletteTint {
+                palette_name: palette_name.to_string(),
+                tint_label: tint_label.to_string(),
+            },
+            ColorReference::PaletteTint {
+                palette_name: palette_name.to_string(),
+                tint_label: new_tint_label.to_string(),
+            },
+        )],
+    ))
+}
+
+/// Rewrite the references (`{palettes.primary.500}`) and token names (`palette-primary-500`) of the
+/// renamed tints
+fn rename_references(
+    design_system: &mut DesignSystem,
+    renames: Vec<(ColorReference, ColorReference)>,
+) -> Vec<RenamedReference> {
+    let mut renamed_references: Vec<RenamedReference> = vec![];
+    for (location, _, value) in reference_fields(design_system) {
+        let new_value = renames.iter().find_map(|(previous, new)| {
+            if ColorReference::parse(value).as_ref() == Some(previous) {
+                Some(new.to_string())
+            } else if *value == previous.token_name() {
+                Some(new.token_name())
+            } else {
+                None
+            }
+        });
+        if let Some(new_value) = new_value {
+            renamed_references.push(RenamedReference {
+                location,
+                previous_value: std::mem::replace(value, new_value.clone()),
+                value: new_value,
+            });
+        }
+    }
+    renamed_references
+}
+
+/// Fields which can hold a reference, with a stable key (ex: themes.dark.background, base-text-default)
+fn reference_fields(
+    design_system: &mut DesignSystem,
+) -> Vec<(String, ReferenceTarget, &mut String)> {
+    let mut fields: Vec<(String, ReferenceTarget, &mut String)> = vec![];
+
+    let themes = &mut design_system.themes;
+    if let Some(theme) = &mut themes.main_theme {
+        fields.push((
+            String::from("themes.main.background"),
+            ReferenceTarget::Color,
+            &mut theme.background,
+        ));
+    }
+    for theme in &mut themes.other_themes {
+        fields.push((
+            format!("themes.{}.background", theme.name),
+            ReferenceTarget::Color,
+            &mut theme.background,
+        ));
+    }
+
+    let independant_colors = &mut design_system.independant_colors;
+    fields.push((
+        String::from("color-white"),
+        ReferenceTarget::Color,
+        &mut independant_colors.white,
+    ));
+    for tint in &mut independant_colors.independant_colors {
+        fields.push((
+            format!("color-{}", tint.label),
+            ReferenceTarget::Color,
+            &mut tint.color,
+        ));
+    }
+
+    let SemanticColorTokens {
+        background,
+        border,
+        text_light,
+        text_default,
+        text_dark,
+        color_combination_collections,
+        theme_overrides,
+    } = &mut design_system.semantic_color_tokens;
+    fields.extend(semantic_token_fields(
+        "",
+        [background, border, text_light, text_default, text_dark],
+        color_combination_collections,
+    ));
+    for (theme_name, overrides) in theme_overrides {
+        let ThemeSemanticColorTokens {
+            background,
+            border,
+            text_light,
+            text_default,
+            text_dark,
+            color_combination_collections,
+        } = overrides;
+        fields.extend(semantic_token_fields(
+            &format!("themes.{}.", theme_name),
+            [background, border, text_light, text_default, text_dark],
+            color_combination_collections,
+        ));
+    }
+
+    for (scale_name, scale) in design_system.typography.scales_mut() {
+        if let Some(color) = &mut scale.color {
+            fields.push((
+                format!("typography.{}", scale_name),
+                ReferenceTarget::TokenName,
+                color,
+            ));
+        }
+    }
+
+    for shadows in &mut design_system.shadows {
+        for (index, shadow) in shadows.shadows_array.iter_mut().enumerate() {
+            fields.push((
+                format!("shadow-{}.{}", shadows.shadow_name, index),
+                ReferenceTarget::TokenName,
+                &mut shadow.color,
+            ));
+        }
+    }
+
+    fields
+}
+
+/// Reference fields of the semantic tokens (background, border, text light, default & dark), keyed
+/// by token name with a prefix
+fn semantic_token_fields<'a>(
+    prefix: &str,
+    base: [&'a mut Option<String>; 5],
+    color_combination_collections: &'a mut [ColorCombinationCollection],
+) -> Vec<(String, ReferenceTarget, &'a mut String)> {
+    let mut fields: Vec<(String, ReferenceTarget, &mut String)> = vec![];
+    let [background, border, text_light, text_default, text_dark] = base;
+    for (name, value) in [
+        ("base-background", background),
+        ("base-text-light", text_light),
+        ("base-text-default", text_default),
+        ("base-text-dark", text_dark),
+        ("base-border", border),
+    ] {
+        if let Some(value) = value {
+            fields.push((
+                format!("{}{}", prefix, name),
+                ReferenceTarget::TokenName,
+                value,
+            ));
+        }
+    }
+    for collection in color_combination_collections {
+        let combination_name: String = collection
+            .combination_name
+            .clone()
+            .unwrap_or_else(|| String::from("collection"));
+        for (state, combination) in [
+            ("", &mut collection.default),
+            ("-hover", &mut collection.hover),
+            ("-focus", &mut collection.focus),
+            ("-active", &mut collection.active),
+        ] {
+            if let Some(ColorCombination {
+                background,
+                text,
+                border,
+            }) = combination
+            {
+                for (property, value) in [
+                    ("background", background),
+                    ("text", text),
+                    ("border", border),
+                ] {
+                    if let Some(value) = value {
+                        fields.push((
+                            format!("{}{}{}-{}", prefix, combination_name, state, property),
+                            ReferenceTarget::TokenName,
+                            value,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn locations(renamed_references: &[RenamedReference]) -> Vec<&str> {
+        renamed_references
+            .iter()
+            .map(|renamed| renamed.location.as_str())
+            .collect()
+    }
+
+    /// Sample design system as stored : references instead of the token names & colors
+    fn stored_design_system() -> DesignSystem {
+        let mut design_system = sample_design_system();
+        design_system.semantic_color_tokens.background = Some(String::from("{palettes.gray.50}"));
+        design_system.independant_colors.independant_colors[0].color =
+            String::from("{palettes.primary.500}");
+        design_system.themes.other_themes[0].background = String::from("{colors.success}");
+        design_system.shadows[0].shadows_array[0].color = String::from("{palettes.gray.900}");
+        design_system
+    }
+
+    #[test]
+    fn parses_references() {
+        for (value, reference) in [
+            (
+                "{palettes.primary.500}",
+                ColorReference::PaletteTint {
+                    palette_name: String::from("primary"),
+                    tint_label: String::from("500"),
+                },
+            ),
+            (
+                " { colors.white } ",
+                ColorReference::IndependantColor {
+                    label: String::from("white"),
+                },
+            ),
+        ] {
+            assert_eq!(ColorReference::parse(value), Some(reference.clone()));
+            assert_eq!(
+                ColorReference::parse(&reference.to_string()),
+                Some(reference)
+            );
+        }
+        for value in [
+            "palette-primary-500",
+            "#3b82f6",
+            "{palettes.primary}",
+            "{palettes..500}",
+            "{colors.}",
+            "{fonts.default}",
+        ] {
+            assert_eq!(ColorReference::parse(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn find_color_follows_the_independant_colors() {
+        let mut design_system = sample_design_system();
+        let independant_colors = &mut design_system.independant_colors;
+        independant_colors.white = String::from("{colors.success}");
+        independant_colors.independant_colors[0].color = String::from("{palettes.primary.50}");
+        let find = |value: &str| {
+            ColorReference::parse(value)
+                .unwrap()
+                .find_color(&design_system.palettes, &design_system.independant_colors)
+        };
+        assert_eq!(find("{colors.white}").as_deref(), Some("#eff6ff"));
+        assert_eq!(find("{palettes.gray.900}").as_deref(), Some("#111827"));
+        assert_eq!(find("{palettes.gray.950}"), None);
+        assert_eq!(find("{colors.danger}"), None);
+
+        // Cycle between independant colors
+        design_system.independant_colors.independant_colors[0].color =
+            String::from("{colors.white}");
+        let cycle = ColorReference::parse("{colors.white}")
+            .unwrap()
+            .find_color(&design_system.palettes, &design_system.independant_colors);
+        assert_eq!(cycle, None);
+    }
+
+    #[test]
+    fn resolve_references_uses_token_names_or_colors() {
+        let mut design_system = stored_design_system();
+        design_system.semantic_color_tokens.text_dark = Some(String::from("{palettes.gray.950}"));
+        resolve_references(&mut design_system);
+
+        let semantic_color_tokens = &design_system.semantic_color_tokens;
+        assert_eq!(
+            semantic_color_tokens.background.as_deref(),
+            Some("palette-gray-50")
+        );
+        assert_eq!(
+            semantic_color_tokens.text_dark.as_deref(),
+            Some("{palettes.gray.950}")
+        );
+        assert_eq!(
+            design_system.independant_colors.independant_colors[0].color,
+            "#3b82f6"
+        );
+        assert_eq!(design_system.themes.other_themes[0].background, "#3b82f6");
+        assert_eq!(
+            design_system.shadows[0].shadows_array[0].color,
+            "palette-gray-900"
+        );
+    }
+
+    #[test]
+    fn restore_references_keeps_the_changed_values() {
+        let stored = stored_design_system();
+        let mut design_system = stored.clone();
+        resolve_references(&mut design_system);
+        design_system.shadows[0].shadows_array[0].color = String::from("palette-primary-900");
+        // The palette is regenerated in the webview, the resolved color is the stored one
+        design_system.palettes[0].tints[1].color = String::from("#2563eb");
+
+        restore_references(&mut design_system, &stored);
+        assert_eq!(
+            design_system.semantic_color_tokens.background.as_deref(),
+            Some("{palettes.gray.50}")
+        );
+        assert_eq!(
+            design_system.independant_colors.independant_colors[0].color,
+            "{palettes.primary.500}"
+        );
+        assert_eq!(
+            design_system.themes.other_themes[0].background,
+            "{colors.success}"
+        );
+        assert_eq!(
+            design_system.shadows[0].shadows_array[0].color,
+            "palette-primary-900"
+        );
+    }
+
+    #[test]
+    fn restore_references_without_stored_references() {
+        let mut design_system = sample_design_system();
+        restore_references(&mut design_system, &sample_design_system());
+        assert_eq!(
+            design_system.semantic_color_tokens.background.as_deref(),
+            Some("palette-gray-50")
+        );
+    }
+
+    #[test]
+    fn rename_palette_rewrites_references_and_token_names() {
+        let mut design_system = sample_design_system();
+        design_system.independant_colors.independant_colors[0].color =
+            String::from("{palettes.gray.500}");
+
+        let renamed = rename_palette(&mut design_system, "gray", " neutral ").unwrap();
+        assert_eq!(design_system.palettes[1].palette_name, "neutral");
+        assert_eq!(
+            locations(&renamed),
+            vec![
+                "color-success",
+                "base-background",
+                "base-text-light",
+                "base-text-default",
+                "themes.dark.base-background",
+                "themes.dark.base-text-default",
+                "shadow-shadow.0",
+            ]
+        );
+        assert_eq!(renamed[0].previous_value, "{palettes.gray.500}");
+        assert_eq!(renamed[0].value, "{palettes.neutral.500}");
+        assert_eq!(renamed[1].previous_value, "palette-gray-50");
+        assert_eq!(renamed[1].value, "palette-neutral-50");
+        assert_eq!(
+            design_system.shadows[0].shadows_array[0].color,
+            "palette-neutral-900"
+        );
+        assert_eq!(
+            design_system
+                .semantic_color_tokens
+                .color_combination_collections[0]
+                .default
+                .as_ref()
+                .unwrap()
+                .background
+                .as_deref(),
+            Some("palette-primary-500")
+        );
+    }
+
+    #[test]
+    fn rename_palette_rejects_invalid_names() {
+        let mut design_system = sample_design_system();
+        assert!(rename_palette(&mut design_system, "gray", " ").is_err());
+        assert!(rename_palette(&mut design_system, "gray", "primary").is_err());
+        assert!(rename_palette(&mut design_system, "blue", "azure").is_err());
+        for new_name in ["a.b", "a/b", "{a}", "..\\a"] {
+            assert!(rename_palette(&mut design_system, "gray", new_name).is_err());
+        }
+        assert_eq!(design_system.palettes[1].palette_name, "gray");
+    }
+
+    #[test]
+    fn rename_tint_rewrites_its_references_only() {
+        let mut design_system = sample_design_system();
+        let renamed = rename_tint(&mut design_system, "primary", "500", "main").unwrap();
+        assert_eq!(design_system.palettes[0].tints[1].label, "main");
+        assert_eq!(locations(&renamed), vec!["button-background"]);
+        assert_eq!(renamed[0].value, "palette-primary-main");
+
+        assert!(rename_tint(&mut design_system, "primary", "50", "900").is_err());
+        assert!(rename_tint(&mut design_system, "primary", "500", "600").is_err());
+        assert!(rename_tint(&mut design_system, "accent", "50", "100").is_err());
+        assert!(rename_tint(&mut design_system, "primary", "50", "").is_err());
+        assert!(rename_tint(&mut design_system, "primary", "50", "5.0").is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use super::{
+    color_domain::{delta_e_76, Okhsl, Rgb},
+    design_system_domain::{IndependantColors, Palette, Tint},
+};
+
+/// Fallback background, same as the webview when a theme has no background
+pub const DEFAULT_THEME_BACKGROUND: &str = "#DDDDDD";
+
+const ACHROMATIC_SATURATION: f64 = 1e-4;
+
+#[derive(Debug, Clone)]
+pub struct RecolorPaletteResult {
+    pub palettes: Vec<Palette>,
+    pub independant_colors: IndependantColors,
+}
+
+/// Keep the relative position of a value around a center, when the center moves (values between min and max)
+fn compute_value_by_center(
+    min: f64,
+    max: f64,
+    initial_center: f64,
+    initial_value: f64,
+    new_center: f64,
+) -> f64 {
+    let longest_initial = (initial_center - min).max(max - initial_center);
+    if longest_initial == 0.0 {
+        return new_center;
+    }
+
+    let sign = if initial_value > initial_center {
+        1.0
+    } else if initial_value < initial_center {
+        -1.0
+    } else {
+        0.0
+    };
+    let ratio = (initial_value - initial_center).abs() / longest_initial;
+
+    let longest_new = (new_center - min).max(max - new_center);
+    (new_center + sign * ratio * longest_new).clamp(min, max)
+}
+
+/// Apply to the new center the hue shift between the initial value and the initial center.
+/// Achromatic colors have no hue : no shift from a gray center, and a gray value takes the new center hue.
+fn interpolate_hue_relative(
+    initial_center: &Okhsl,
+    initial_value: &Okhsl,
+    new_center: &Okhsl,
+) -> f64 {
+    if initial_value.s < ACHROMATIC_SATURATION {
+        new_center.h
+    } else if initial_center.s < ACHROMATIC_SATURATION {
+        initial_value.h
+    } else {
+        (new_center.h + initial_value.h - initial_center.h).rem_euclid(360.0)
+    }
+}
+
+fn recolor_with_new_background(default_color: &Rgb, default_center: &Rgb, new_center: &Rgb) -> Rgb {
+    let default_color_okhsl = default_color.to_okhsl();
+    let default_center_okhsl = default_center.to_okhsl();
+    let new_center_okhsl = new_center.to_okhsl();
+
+    let okhsl = Okhsl {
+        h: interpolate_hue_relative(
+            &default_center_okhsl,
+            &default_color_okhsl,
+            &new_center_okhsl,
+        ),
+        s: compute_value_by_center(
+            0.0,
+            1.0,
+            default_center_okhsl.s,
+            default_color_okhsl.s,
+            new_center_okhsl.s,
+        ),
+        l: compute_value_by_center(
+            0.0,
+            1.0,
+            default_center_okhsl.l,
+            default_color_okhsl.l,
+            new_center_okhsl.l,
+        ),
+    };
+    Rgb::from_okhsl(&okhsl, 1.0)
+}
+
+/// Parse a theme or tint color, an empty or invalid color is read as the fallback background (as
+/// the webview does)
+fn parse_or_default_background(color: &str) -> Rgb {
+    Rgb::parse(color).unwrap_or_else(|_| Rgb::parse(DEFAULT_THEME_BACKGROUND).unwrap())
+}
+
+fn recolor_hex(color: &str, default_center: &Rgb, new_center: &Rgb) -> String {
+    recolor_with_new_background(
+        &parse_or_default_background(color),
+        default_center,
+        new_center,
+    )
+    .to_hex()
+}
+
+/// Compute a new color theme from original palettes with a default background, to a new background.
+/// Same algorithm as the webview theme generator (ThemeGenerator.ts).
+pub fn recolor_palettes(
+    palettes: &[Palette],
+    default_background: &str,
+    new_background: &str,
+    independant_colors: &IndependantColors,
+) -> RecolorPaletteResult {
+    let original_bg_color = parse_or_default_background(default_background);
+    let mut default_bg_color = original_bg_color;
+    let new_bg_color = parse_or_default_background(new_background);
+    let is_reversed = (default_bg_color.to_okhsl().l >= 0.5) != (new_bg_color.to_okhsl().l >= 0.5);
+
+    // Reverse manipulation: when pass from a light theme to dark theme, then compute differently the background
+    if is_reversed {
+        let mut closest: Option<(f64, Rgb, Rgb)> = None;
+        for palette in palettes {
+            let (first, last) = match (palette.tints.first(), palette.tints.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => continue,
+            };
+            let palette_min = parse_or_default_background(&first.color);
+            let distance = delta_e_76(&default_bg_color, &palette_min);
+            if closest.map_or(true, |(closest_distance, _, _)| closest_distance > distance) {
+                closest = Some((
+                    distance,
+                    palette_min,
+                    parse_or_default_background(&last.color),
+                ));
+            }
+        }
+        if let Some((_, palette_min, palette_max)) = closest {
+            default_bg_color =
+                recolor_with_new_background(&default_bg_color, &palette_min, &palette_max);
+        }
+    }
+
+    let palettes_recolor = palettes
+        .iter()
+        .map(|palette| {
+            let mut colors: Vec<&String> = palette.tints.iter().map(|tint| &tint.color).collect();
+            if is_reversed {
+                colors.reverse();
+            }
+            let tints = palette
+                .tints
+                .iter()
+                .zip(colors)
+                .map(|(tint, color)| Tint {
+                    label: tint.label.clone(),
+                    color: recolor_hex(color, &default_bg_color, &new_bg_color),
+                })
+                .collect();
+            Palette {
+                tints,
+                ..palette.clone()
+            }
+        })
+        .collect();
+
+    let independant_recolor = IndependantColors {
+        white: recolor_hex(&independant_colors.white, &original_bg_color, &new_bg_color),
+        independant_colors: independant_colors
+            .independant_colors
+            .iter()
+            .map(|tint| Tint {
+                label: tint.label.clone(),
+                color: recolor_hex(&tint.color, &original_bg_color, &new_bg_color),
+            })
+            .collect(),
+    };
+
+    RecolorPaletteResult {
+        palettes: palettes_recolor,
+        independant_colors: independant_recolor,
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Named snapshot of the saved design system files, stored under `versions/{name}` with a
+/// `version.yaml` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesignSystemVersion {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Creation date of the version (RFC 3339)
+    pub update_date: String,
+}
+
+/// The version name is a folder name : letters, digits, `.`, `-` & `_` (ex: 1.2.0, v2-beta)
+pub fn validate_version_name(version_name: &str) -> Result<()> {
+    if version_name.is_empty() {
+        bail!("The version name is empty");
+    }
+    if version_name.starts_with('.') {
+        bail!("The version name can't start with a dot : {}", version_name);
+    }
+    if !version_name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    {
+        bail!(
+            "The version name can only contain letters, digits, '.', '-' and '_' : {}",
+            version_name
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_folder_names() {
+        for version_name in ["1.2.0", "v2-beta", "release_3", "2024.01"] {
+            assert!(
+                validate_version_name(version_name).is_ok(),
+                "{}",
+                version_name
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_empty_names() {
+        assert!(validate_version_name("").is_err());
+    }
+
+    #[test]
+    fn rejects_paths() {
+        for version_name in [
+            "..", "../1.0.0", "../../x", "a/b", "a\\b", "/tmp", ".hidden", "1 0",
+        ] {
+            assert!(
+                validate_version_name(version_name).is_err(),
+                "{}",
+                version_name
+            );
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::domain::{
+    color_domain::Rgb,
+    design_system_domain::{
+        ColorCombination, DesignSystem, IndependantColors, Measurement, Palette,
+        SemanticColorTokens, Shadows, Theme, Tint, TypographyScale, UnitOfMeasurement,
+    },
+    export_domain::{ExportFile, ExportFormat},
+    reference_domain::{resolve_references, ColorReference},
+    theme_domain::{recolor_palettes, RecolorPaletteResult, DEFAULT_THEME_BACKGROUND},
+};
+
+pub mod android_export;
+pub mod css_export;
+pub mod dtcg_export;
+pub mod ios_export;
+pub mod preprocessor_export;
+pub mod style_dictionary_export;
+pub mod swatches_export;
+pub mod tailwind_export;
+pub mod tokens_studio_export;
+
+/// Browsers default root font size
+pub const DEFAULT_ROOT_FONT_SIZE: f64 = 16.0;
+
+pub fn generate_export(
+    design_system: &DesignSystem,
+    format: &ExportFormat,
+) -> Result<Vec<ExportFile>> {
+    // The exports only know token names & colors
+    let mut design_system: DesignSystem = design_system.clone();
+    resolve_references(&mut design_system);
+    let design_system: &DesignSystem = &design_system;
+    match format {
+        ExportFormat::Css => Ok(vec![css_export::export_stylesheet(design_system)?]),
+        ExportFormat::TokensStudio => Ok(vec![tokens_studio_export::export_tokens_studio(
+            design_system,
+        )?]),
+        ExportFormat::Dtcg => Ok(vec![dtcg_export::export_dtcg(design_system)?]),
+        ExportFormat::Scss => Ok(vec![preprocessor_export::export_scss(design_system)?]),
+        ExportFormat::Less => Ok(vec![preprocessor_export::export_less(design_system)?]),
+        ExportFormat::Ios => ios_export::export_ios(design_system),
+        ExportFormat::Swatches => swatches_export::export_swatches(design_system),
+        ExportFormat::Android => android_export::export_android(design_system),
+        ExportFormat::Tailwind => tailwind_export::export_tailwind(design_system),
+        ExportFormat::StyleDictionary => {
+            style_dictionary_export::export_style_dictionary(design_system)
+        }
+    }
+}
+
+/// Token name of a tint, same naming as the webview : palette-{palette}-{tint}
+pub fn palette_token_name(palette: &Palette, tint: &Tint) -> String {
+    format!("palette-{}-{}", palette.palette_name, tint.label)
+}
+
+/// Independant colors as (token name, color), white first : color-white, color-{label}
+pub fn independant_color_tokens(independant_colors: &IndependantColors) -> Vec<(String, String)> {
+    let mut tokens = vec![(
+        String::from("color-white"),
+        independant_colors.white.clone(),
+    )];
+    tokens.extend(
+        independant_colors
+            .independant_colors
+            .iter()
+            .map(|tint| (format!("color-{}", tint.label), tint.color.clone())),
+    );
+    tokens
+}
+
+/// Semantic color tokens as (token name, referenced token name), with the webview naming
+pub fn semantic_color_tokens(semantic_color_tokens: &SemanticColorTokens) -> Vec<(String, String)> {
+    let mut tokens: Vec<(String, String)> = [
+        ("base-background", &semantic_color_tokens.background),
+        ("base-text-light", &semantic_color_tokens.text_light),
+        ("base-text-default", &semantic_color_tokens.text_default),
+        ("base-text-dark", &semantic_color_tokens.text_dark),
+        ("base-border", &semantic_color_tokens.border),
+    ]
+    .iter()
+    .filter_map(|(name, value)| {
+        value
+            .as_ref()
+            .map(|value| (String::from(*name), value.clone()))
+    })
+    .collect();
+
+    for collection in &semantic_color_tokens.color_combination_collections {
+        let combination_name = collection
+            .combination_name
+            .as_deref()
+            .unwrap_or("collection");
+        for (state, combination) in [
+            ("", &collection.default),
+            ("-hover", &collection.hover),
+            ("-focus", &collection.focus),
+            ("-active", &collection.active),
+        ] {
+            if let Some(ColorCombination {
+                background,
+                text,
+                border,
+            }) = combination
+            {
+                for (property, value) in [
+                    ("background", background),
+                    ("text", text),
+                    ("border", border),
+                ] {
+                    if let Some(value) = value {
+                        tokens.push((
+                            format!("{}{}-{}", combination_name, state, property),
+                            value.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    tokens
+}
+
+/// Semantic color tokens changed by the overrides of each theme, as (theme name, tokens), for the
+/// exports without recolored palettes. The themes without change are skipped
+pub fn semantic_color_overrides(
+    design_system: &DesignSystem,
+) -> Vec<(String, Vec<(String, String)>)> {
+    let main_tokens = semantic_color_tokens(&design_system.semantic_color_tokens);
+    design_system
+        .semantic_color_tokens
+        .theme_overrides
+        .keys()
+        .map(|theme_name| {
+            let overridden_tokens: Vec<(String, String)> =
+                semantic_color_tokens(&design_system.semantic_color_tokens.for_theme(theme_name))
+                    .into_iter()
+                    .filter(|token| !main_tokens.contains(token))
+                    .collect();
+            (theme_name.clone(), overridden_tokens)
+        })
+        .filter(|(_, overridden_tokens)| !overridden_tokens.is_empty())
+        .collect()
+}
+
+/// Color of a palette or independant token (ex: palette-primary-500, color-white, {palettes.primary.500})
+pub fn find_color_token(design_system: &DesignSystem, token_name: &str) -> Option<String> {
+    if let Some(reference) = ColorReference::parse(token_name) {
+        return reference.find_color(&design_system.palettes, &design_system.independant_colors);
+    }
+    design_system
+        .palettes
+        .iter()
+        .flat_map(|palette| {
+            palette
+                .tints
+                .iter()
+                .map(move |tint| (palette_token_name(palette, tint), &tint.color))
+        })
+        .find(|(name, _)| name == token_name)
+        .map(|(_, color)| color.clone())
+        .or_else(|| {
+            independant_color_tokens(&design_system.independant_colors)
+                .into_iter()
+                .find(|(name, _)| name == token_name)
+                .map(|(_, color)| color)
+        })
+}
+
+/// Resolve a color which can be a token name or a css color
+pub fn resolve_color(design_system: &DesignSystem, color: &str) -> Option<Rgb> {
+    let color = find_color_token(design_system, color).unwrap_or_else(|| color.to_string());
+    Rgb::parse(&color).ok()
+}
+
+/// Recolored palettes & independant colors of every other theme, from the main theme background
+pub fn recolor_themes(design_system: &DesignSystem) -> Vec<(String, RecolorPaletteResult)> {
+    let default_background: &str = main_theme_background(design_system);
+
+    design_system
+        .themes
+        .other_themes
+        .iter()
+        .map(|theme| {
+            (
+                theme.name.clone(),
+                recolor_palettes(
+                    &design_system.palettes,
+                    default_background,
+                    &theme.background,
+                    &design_system.independant_colors,
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Background of the main theme
+pub fn main_theme_background(design_system: &DesignSystem) -> &str {
+    design_system
+        .themes
+        .main_theme
+        .as_ref()
+        .map(|theme| theme.background.as_str())
+        .unwrap_or(DEFAULT_THEME_BACKGROUND)
+}
+
+pub fn is_dark_background(background: &str) -> bool {
+    Rgb::parse(background)
+        .map(|color| color.to_okhsl().l < 0.5)
+        .unwrap_or(false)
+}
+
+/// First other theme with the opposite background luminosity of the main theme, it gives the
+/// dark (or light) appearance of the platforms
+pub fn opposite_theme(design_system: &DesignSystem) -> Option<&Theme> {
+    let main_is_dark = is_dark_background(main_theme_background(design_system));
+    design_system
+        .themes
+        .other_themes
+        .iter()
+        .find(|theme| is_dark_background(&theme.background) != main_is_dark)
+}
+
+/// Root font size in px (root typography scale), used to convert rem for the native platforms
+pub fn root_font_size(design_system: &DesignSystem) -> f64 {
+    let font_size = &design_system.typography.root.font_size;
+    match font_size.unit {
+        UnitOfMeasurement::PX => font_size.value,
+        UnitOfMeasurement::REM => font_size.value * DEFAULT_ROOT_FONT_SIZE,
+    }
+}
+
+/// Measurement in px (dp / sp / pt on the native platforms)
+pub fn measurement_to_px(measurement: &Measurement, root_font_size: f64) -> f64 {
+    match measurement.unit {
+        UnitOfMeasurement::PX => measurement.value,
+        UnitOfMeasurement::REM => measurement.value * root_font_size,
+    }
+}
+
+/// Number rounded to 2 decimals, without trailing `.0` (native platforms sources)
+pub fn format_number(value: f64) -> String {
+    let value = (value * 100.0).round() / 100.0;
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
+
+pub fn measurement_to_css(measurement: &Measurement) -> String {
+    let unit = serde_name(&measurement.unit).to_lowercase();
+    format!("{}{}", measurement.value, unit)
+}
+
+/// box-shadow value, token colors resolved (unknown colors fallback to #DDDDDD as in the webview)
+pub fn shadows_to_css(design_system: &DesignSystem, shadows: &Shadows) -> String {
+    shadows
+        .shadows_array
+        .iter()
+        .map(|shadow| {
+            let mut color = resolve_color(design_system, &shadow.color)
+                .unwrap_or_else(|| Rgb::parse(DEFAULT_THEME_BACKGROUND).unwrap());
+            color.alpha = shadow.color_opacity as f64;
+            format!(
+                "{}px {}px {}px {}px {}{}",
+                shadow.shadow_x,
+                shadow.shadow_y,
+                shadow.blur,
+                shadow.spread,
+                color.to_css_rgba(),
+                if shadow.inset { " inset" } else { "" }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Css properties of a typography scale, `reference` gives the syntax of a variable (ex: var(--font-default))
+pub fn typography_properties(
+    scale_name: &str,
+    scale: &TypographyScale,
+    reference: impl Fn(&str) -> String,
+) -> Vec<(&'static str, String)> {
+    let mut properties: Vec<(&str, String)> = vec![
+        ("font-size", measurement_to_css(&scale.font_size)),
+        ("line-height", measurement_to_css(&scale.line_height)),
+        ("font-weight", serde_name(&scale.font_weight)),
+        ("letter-spacing", serde_name(&scale.letter_spacing)),
+        ("word-spacing", serde_name(&scale.word_spacing)),
+        ("font-style", serde_name(&scale.font_style)),
+        ("text-transform", serde_name(&scale.text_transform)),
+        ("text-decoration", serde_name(&scale.text_decoration)),
+        ("padding", measurement_to_css(&scale.padding)),
+        ("margin", measurement_to_css(&scale.margin)),
+    ];
+    if let Some(font) = &scale.font {
+        properties.push(("font-family", reference(&format!("font-{}", font))));
+    } else if scale_name == "root" {
+        properties.push(("font-family", reference("font-default")));
+    }
+    if let Some(color) = &scale.color {
+        properties.push(("color", reference(color)));
+    }
+    properties
+}
+
+/// Quote a single family name, keep font stacks (`Inter, sans-serif`) as is
+pub fn font_family(value: &str) -> String {
+    if value.contains(',') || value.starts_with('"') || value.starts_with('\'') {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value)
+    }
+}
+
+/// Serialized name of a unit variant (ex: FontWeight::Four -> "400")
+pub fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
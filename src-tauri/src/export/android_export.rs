@@ -0,0 +1,358 @@
+use std::{fmt::Write, path::PathBuf};
+
+use anyhow::Result;
+
+use crate::{
+    domain::{color_domain::Rgb, design_system_domain::DesignSystem, export_domain::ExportFile},
+    repository::design_system_repository::EXPORT_ANDROID_PATH,
+};
+
+use super::{
+    format_number, independant_color_tokens, measurement_to_px, palette_token_name, resolve_color,
+    root_font_size, semantic_color_overrides, semantic_color_tokens, serde_name,
+};
+
+/// Android resources (`values/colors.xml`, `values/dimens.xml`) and Compose theme (`compose/Color.kt`, `compose/Type.kt`).
+/// px are exported as dp / sp, rem are converted with the root font size of the typography.
+/// The semantic colors overridden by a theme are prefixed by the theme (`theme_dark_base_background`)
+pub fn export_android(design_system: &DesignSystem) -> Result<Vec<ExportFile>> {
+    let package = kotlin_package(&design_system.metadata.design_system_name);
+    Ok(vec![
+        android_file(&["values", "colors.xml"], colors_xml(design_system)),
+        android_file(&["values", "dimens.xml"], dimens_xml(design_system)),
+        android_file(&["compose", "Color.kt"], color_kt(design_system, &package)),
+        android_file(&["compose", "Type.kt"], type_kt(design_system, &package)),
+    ])
+}
+
+fn android_file(path: &[&str], content: String) -> ExportFile {
+    let path = path
+        .iter()
+        .fold(PathBuf::from(EXPORT_ANDROID_PATH), |path, part| {
+            path.join(part)
+        });
+    ExportFile {
+        path,
+        content: content.into_bytes(),
+    }
+}
+
+/// Color tokens (palettes, then independant colors) with their value in AARRGGBB
+fn color_tokens(design_system: &DesignSystem) -> Vec<(String, String)> {
+    let mut tokens: Vec<(String, String)> = vec![];
+    for palette in &design_system.palettes {
+        for tint in &palette.tints {
+            tokens.push((palette_token_name(palette, tint), tint.color.clone()));
+        }
+    }
+    tokens.extend(independant_color_tokens(&design_system.independant_colors));
+    tokens
+        .into_iter()
+        .filter_map(|(name, color)| {
+            Rgb::parse(&color)
+                .ok()
+                .map(|color| (name, color.to_argb_hex()))
+        })
+        .collect()
+}
+
+/// Value of a semantic color
+enum SemanticColor {
+    /// Exported color token
+    Token(String),
+    /// Color in AARRGGBB, when the value is not an exported token (ex: css color)
+    Color(String),
+}
+
+/// Semantic colors referencing an exported token, or resolved to their color. The values which
+/// are neither are skipped
+fn semantic_colors(
+    design_system: &DesignSystem,
+    color_tokens: &[(String, String)],
+    semantic_tokens: Vec<(String, String)>,
+) -> Vec<(String, SemanticColor)> {
+    semantic_tokens
+        .into_iter()
+        .filter_map(|(name, value)| {
+            if color_tokens
+                .iter()
+                .any(|(token_name, _)| *token_name == value)
+            {
+                Some((name, SemanticColor::Token(value)))
+            } else {
+                resolve_color(design_system, &value)
+                    .map(|color| (name, SemanticColor::Color(color.to_argb_hex())))
+            }
+        })
+        .collect()
+}
+
+/// Semantic colors of the main theme then the ones overridden by each theme, as (comment, colors)
+fn semantic_color_sections(
+    design_system: &DesignSystem,
+    color_tokens: &[(String, String)],
+) -> Vec<(String, Vec<(String, SemanticColor)>)> {
+    let mut sections = vec![(
+        String::from("Semantic colors"),
+        semantic_colors(
+            design_system,
+            color_tokens,
+            semantic_color_tokens(&design_system.semantic_color_tokens),
+        ),
+    )];
+    for (theme_name, overridden_tokens) in semantic_color_overrides(design_system) {
+        let overridden_tokens: Vec<(String, String)> = overridden_tokens
+            .into_iter()
+            .map(|(name, value)| (format!("theme-{}-{}", theme_name, name), value))
+            .collect();
+        sections.push((
+            format!("Semantic colors of the {} theme", theme_name),
+            semantic_colors(design_system, color_tokens, overridden_tokens),
+        ));
+    }
+    sections
+        .into_iter()
+        .filter(|(_, semantic_colors)| !semantic_colors.is_empty())
+        .collect()
+}
+
+/// Resource name : palette-primary-500 -> palette_primary_500
+fn resource_name(token_name: &str) -> String {
+    let name: String = token_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Kotlin property name : palette-primary-500 -> PalettePrimary500
+fn kotlin_name(token_name: &str) -> String {
+    let name: String = token_name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Package of the generated Kotlin files, from the design system name (My Brand -> mybrand.theme)
+fn kotlin_package(design_system_name: &str) -> String {
+    let name: String = design_system_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("uitea{}.theme", name)
+    } else {
+        format!("{}.theme", name)
+    }
+}
+
+fn colors_xml(design_system: &DesignSystem) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+    let color_tokens = color_tokens(design_system);
+    for (name, argb) in &color_tokens {
+        let _ = writeln!(
+            xml,
+            "    <color name=\"{}\">#{}</color>",
+            resource_name(name),
+            argb
+        );
+    }
+    for (comment, semantic_colors) in semantic_color_sections(design_system, &color_tokens) {
+        let _ = writeln!(xml, "\n    <!-- {} -->", comment);
+        for (name, color) in semantic_colors {
+            let value = match color {
+                SemanticColor::Token(token_name) => {
+                    format!("@color/{}", resource_name(&token_name))
+                }
+                SemanticColor::Color(argb) => format!("#{}", argb),
+            };
+            let _ = writeln!(
+                xml,
+                "    <color name=\"{}\">{}</color>",
+                resource_name(&name),
+                value
+            );
+        }
+    }
+    xml.push_str("</resources>\n");
+    xml
+}
+
+fn dimens_xml(design_system: &DesignSystem) -> String {
+    let root_font_size = root_font_size(design_system);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+    let mut write_dimen = |name: &str, value: f64, unit: &str| {
+        let _ = writeln!(
+            xml,
+            "    <dimen name=\"{}\">{}{}</dimen>",
+            resource_name(name),
+            format_number(value),
+            unit
+        );
+    };
+
+    for space in &design_system.spaces {
+        write_dimen(
+            &format!("space-{}", space.space_key),
+            measurement_to_px(&space.space_value, root_font_size),
+            "dp",
+        );
+    }
+    write_dimen(
+        "radius",
+        measurement_to_px(&design_system.radius.default, root_font_size),
+        "dp",
+    );
+    for radius in &design_system.radius.additionals_radius {
+        write_dimen(
+            &format!("radius-{}", radius.radius_key),
+            measurement_to_px(&radius.radius_value, root_font_size),
+            "dp",
+        );
+    }
+    for (scale_name, scale) in design_system.typography.scales() {
+        write_dimen(
+            &format!("text-{}", scale_name),
+            measurement_to_px(&scale.font_size, root_font_size),
+            "sp",
+        );
+    }
+    xml.push_str("</resources>\n");
+    xml
+}
+
+fn color_kt(design_system: &DesignSystem, package: &str) -> String {
+    let mut kotlin = format!(
+        "package {}\n\nimport androidx.compose.ui.graphics.Color\n\n",
+        package
+    );
+    let color_tokens = color_tokens(design_system);
+    for (name, argb) in &color_tokens {
+        let _ = writeln!(kotlin, "val {} = Color(0x{})", kotlin_name(name), argb);
+    }
+    for (comment, semantic_colors) in semantic_color_sections(design_system, &color_tokens) {
+        let _ = writeln!(kotlin, "\n// {}", comment);
+        for (name, color) in semantic_colors {
+            let value = match color {
+                SemanticColor::Token(token_name) => kotlin_name(&token_name),
+                SemanticColor::Color(argb) => format!("Color(0x{})", argb),
+            };
+            let _ = writeln!(kotlin, "val {} = {}", kotlin_name(&name), value);
+        }
+    }
+    kotlin
+}
+
+fn type_kt(design_system: &DesignSystem, package: &str) -> String {
+    let root_font_size = root_font_size(design_system);
+    let mut kotlin = format!(
+        "package {}
+
+import androidx.compose.ui.text.TextStyle
+import androidx.compose.ui.text.font.FontWeight
+import androidx.compose.ui.unit.em
+import androidx.compose.ui.unit.sp
+",
+        package
+    );
+    for (scale_name, scale) in design_system.typography.scales() {
+        let letter_spacing: f64 = serde_name(&scale.letter_spacing)
+            .trim_end_matches("em")
+            .parse()
+            .unwrap_or(0.0);
+        let _ = write!(
+            kotlin,
+            "
+val {} = TextStyle(
+    fontSize = {}.sp,
+    lineHeight = {}.sp,
+    fontWeight = FontWeight.W{},
+    letterSpacing = ({}).em,
+)
+",
+            kotlin_name(&format!("typography-{}", scale_name)),
+            format_number(measurement_to_px(&scale.font_size, root_font_size)),
+            format_number(measurement_to_px(&scale.line_height, root_font_size)),
+            serde_name(&scale.font_weight),
+            format_number(letter_spacing)
+        );
+    }
+    kotlin
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn design_system() -> DesignSystem {
+        let mut design_system = sample_design_system();
+        design_system.semantic_color_tokens.text_dark = Some(String::from("#123456"));
+        design_system.semantic_color_tokens.border = Some(String::from("palette-missing-500"));
+        design_system
+    }
+
+    #[test]
+    fn colors_xml_references_the_exported_tokens() {
+        let xml = colors_xml(&design_system());
+        assert!(xml.contains("<color name=\"palette_primary_500\">#FF3B82F6</color>"));
+        assert!(xml.contains("<color name=\"base_background\">@color/palette_gray_50</color>"));
+        assert!(xml.contains("<color name=\"base_text_dark\">#FF123456</color>"));
+        assert!(!xml.contains("palette_missing_500"));
+        assert!(!xml.contains("base_border"));
+    }
+
+    #[test]
+    fn color_kt_references_the_exported_tokens() {
+        let kotlin = color_kt(&design_system(), "demo.theme");
+        assert!(kotlin.starts_with("package demo.theme\n"));
+        assert!(kotlin.contains("val PalettePrimary500 = Color(0xFF3B82F6)"));
+        assert!(kotlin.contains("val BaseBackground = PaletteGray50"));
+        assert!(kotlin.contains("val BaseTextDark = Color(0xFF123456)"));
+        assert!(!kotlin.contains("PaletteMissing500"));
+    }
+
+    #[test]
+    fn theme_overrides_are_prefixed_by_the_theme() {
+        let xml = colors_xml(&design_system());
+        assert!(xml.contains(
+            "    <!-- Semantic colors of the dark theme -->\n    \
+             <color name=\"theme_dark_base_background\">@color/palette_gray_900</color>\n"
+        ));
+        assert!(!xml.contains("theme_dark_base_text_light"));
+        let kotlin = color_kt(&design_system(), "demo.theme");
+        assert!(kotlin.contains("val ThemeDarkButtonHoverBackground = PalettePrimary50"));
+    }
+
+    #[test]
+    fn names_are_valid_identifiers() {
+        assert_eq!(resource_name("palette-primary-500"), "palette_primary_500");
+        assert_eq!(resource_name("2xl"), "_2xl");
+        assert_eq!(kotlin_name("palette-primary-500"), "PalettePrimary500");
+        assert_eq!(kotlin_package("My Brand"), "mybrand.theme");
+        assert_eq!(kotlin_package("42"), "uitea42.theme");
+    }
+}
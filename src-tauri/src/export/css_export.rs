@@ -0,0 +1,257 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::{
+    domain::{
+        design_system_domain::{
+            DesignSystem, IndependantColors, Palette, SemanticColorTokens, TypographyScale,
+        },
+        export_domain::ExportFile,
+    },
+    repository::design_system_repository::EXPORT_STYLESHEET_PATH,
+};
+
+use super::{
+    font_family, independant_color_tokens, is_dark_background, main_theme_background,
+    measurement_to_css, opposite_theme, palette_token_name, recolor_themes, semantic_color_tokens,
+    shadows_to_css, typography_properties,
+};
+
+/// Stylesheet with every token as css custom properties : `:root` for the main theme,
+/// `[data-theme="..."]` for the other themes (also applied with `prefers-color-scheme` for the
+/// theme of the opposite luminosity), and a `.typography-...` class per typography scale
+pub fn export_stylesheet(design_system: &DesignSystem) -> Result<ExportFile> {
+    let mut stylesheet = String::from(":root {\n");
+    write_color_variables(
+        &mut stylesheet,
+        &design_system.palettes,
+        &design_system.independant_colors,
+    );
+
+    write_semantic_variables(&mut stylesheet, &design_system.semantic_color_tokens);
+
+    stylesheet.push_str("\n  /* Fonts */\n");
+    write_variable(
+        &mut stylesheet,
+        "font-default",
+        &font_family(&design_system.fonts.default),
+    );
+    for font in &design_system.fonts.additionals {
+        write_variable(
+            &mut stylesheet,
+            &format!("font-{}", font.font_name),
+            &font_family(&font.value),
+        );
+    }
+
+    if !design_system.spaces.is_empty() {
+        stylesheet.push_str("\n  /* Spaces */\n");
+        for space in &design_system.spaces {
+            write_variable(
+                &mut stylesheet,
+                &format!("space-{}", space.space_key),
+                &measurement_to_css(&space.space_value),
+            );
+        }
+    }
+
+    stylesheet.push_str("\n  /* Radius */\n");
+    write_variable(
+        &mut stylesheet,
+        "radius",
+        &measurement_to_css(&design_system.radius.default),
+    );
+    for radius in &design_system.radius.additionals_radius {
+        write_variable(
+            &mut stylesheet,
+            &format!("radius-{}", radius.radius_key),
+            &measurement_to_css(&radius.radius_value),
+        );
+    }
+
+    if !design_system.shadows.is_empty() {
+        stylesheet.push_str("\n  /* Shadows */\n");
+        for shadows in &design_system.shadows {
+            write_variable(
+                &mut stylesheet,
+                &format!("shadow-{}", shadows.shadow_name),
+                &shadows_to_css(design_system, shadows),
+            );
+        }
+    }
+    stylesheet.push_str("}\n");
+
+    let opposite_theme_name: Option<&str> =
+        opposite_theme(design_system).map(|theme| theme.name.as_str());
+    for (theme_name, recolor) in recolor_themes(design_system) {
+        // The semantic colors are declared again so that they use the recolored palettes
+        let mut variables = String::new();
+        write_color_variables(
+            &mut variables,
+            &recolor.palettes,
+            &recolor.independant_colors,
+        );
+        write_semantic_variables(
+            &mut variables,
+            &design_system.semantic_color_tokens.for_theme(&theme_name),
+        );
+
+        let _ = write!(
+            stylesheet,
+            "\n[data-theme=\"{}\"] {{\n{}}}\n",
+            theme_name, variables
+        );
+        if opposite_theme_name == Some(theme_name.as_str()) {
+            let color_scheme = if is_dark_background(main_theme_background(design_system)) {
+                "light"
+            } else {
+                "dark"
+            };
+            let _ = write!(
+                stylesheet,
+                "\n@media (prefers-color-scheme: {}) {{\n  :root:not([data-theme]) {{\n",
+                color_scheme
+            );
+            for line in variables.lines() {
+                if line.is_empty() {
+                    stylesheet.push('\n');
+                } else {
+                    let _ = writeln!(stylesheet, "  {}", line);
+                }
+            }
+            stylesheet.push_str("  }\n}\n");
+        }
+    }
+
+    for (scale_name, scale) in design_system.typography.scales() {
+        // Same class names as the typography tooltips of the webview
+        let class_name = if scale_name == "paragraph" {
+            "p"
+        } else {
+            scale_name
+        };
+        write_typography_class(&mut stylesheet, class_name, scale);
+    }
+
+    Ok(ExportFile::from_string(EXPORT_STYLESHEET_PATH, stylesheet))
+}
+
+fn write_variable(stylesheet: &mut String, name: &str, value: &str) {
+    let _ = writeln!(stylesheet, "  --{}: {};", name, value);
+}
+
+fn write_color_variables(
+    stylesheet: &mut String,
+    palettes: &[Palette],
+    independant_colors: &IndependantColors,
+) {
+    stylesheet.push_str("  /* Palettes */\n");
+    for palette in palettes {
+        for tint in &palette.tints {
+            write_variable(stylesheet, &palette_token_name(palette, tint), &tint.color);
+        }
+    }
+    stylesheet.push_str("\n  /* Independant colors */\n");
+    for (name, color) in independant_color_tokens(independant_colors) {
+        write_variable(stylesheet, &name, &color);
+    }
+}
+
+fn write_semantic_variables(stylesheet: &mut String, semantic_tokens: &SemanticColorTokens) {
+    let semantic_tokens = semantic_color_tokens(semantic_tokens);
+    if !semantic_tokens.is_empty() {
+        stylesheet.push_str("\n  /* Semantic colors */\n");
+        for (name, value) in semantic_tokens {
+            write_variable(stylesheet, &name, &format!("var(--{})", value));
+        }
+    }
+}
+
+fn write_typography_class(stylesheet: &mut String, scale_name: &str, scale: &TypographyScale) {
+    let _ = writeln!(stylesheet, "\n.typography-{} {{", scale_name);
+    for (property, value) in
+        typography_properties(scale_name, scale, |name| format!("var(--{})", name))
+    {
+        let _ = writeln!(stylesheet, "  {}: {};", property, value);
+    }
+    stylesheet.push_str("}\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn stylesheet(design_system: &DesignSystem) -> String {
+        let file = export_stylesheet(design_system).unwrap();
+        assert_eq!(file.path.to_str(), Some(EXPORT_STYLESHEET_PATH));
+        String::from_utf8(file.content).unwrap()
+    }
+
+    #[test]
+    fn declares_every_token_under_root() {
+        let stylesheet = stylesheet(&sample_design_system());
+        assert!(
+            stylesheet.starts_with(":root {\n  /* Palettes */\n  --palette-primary-50: #eff6ff;\n")
+        );
+        for declaration in [
+            "  --color-white: #ffffff;\n",
+            "  --base-background: var(--palette-gray-50);\n",
+            "  --button-hover-background: var(--palette-primary-900);\n",
+            "  --font-default: \"Open Sans\";\n",
+            "  --space-2: 0.5rem;\n",
+            "  --radius: 4px;\n  --radius-lg: 12px;\n",
+            "  --shadow-shadow: 0px 4px 4px 0px rgba(17, 24, 39, 0.25);\n",
+        ] {
+            assert!(stylesheet.contains(declaration), "{}", declaration);
+        }
+    }
+
+    #[test]
+    fn other_themes_recolor_the_palettes_with_their_overrides() {
+        let stylesheet = stylesheet(&sample_design_system());
+        let dark = &stylesheet[stylesheet.find("[data-theme=\"dark\"] {").unwrap()..];
+        let dark = &dark[..dark.find("}\n").unwrap()];
+        assert!(!dark.contains("--palette-gray-50: #f9fafb;"));
+        assert!(dark.contains("  --base-background: var(--palette-gray-900);\n"));
+        assert!(dark.contains("  --base-text-default: var(--palette-gray-50);\n"));
+        assert!(dark.contains("  --base-text-light: var(--palette-gray-500);\n"));
+        assert!(dark.contains("  --button-hover-background: var(--palette-primary-50);\n"));
+        // Dark theme of a light design system
+        assert!(stylesheet.contains(
+            "@media (prefers-color-scheme: dark) {\n  :root:not([data-theme]) {\n    /* Palettes */\n"
+        ));
+    }
+
+    #[test]
+    fn single_theme_has_no_theme_selector() {
+        let mut design_system = sample_design_system();
+        design_system.themes.other_themes.clear();
+        let stylesheet = stylesheet(&design_system);
+        assert!(!stylesheet.contains("[data-theme"));
+        assert!(!stylesheet.contains("prefers-color-scheme"));
+    }
+
+    #[test]
+    fn invalid_theme_colors_use_the_default_background() {
+        let mut design_system = sample_design_system();
+        design_system.themes.other_themes[0].background = String::new();
+        design_system.palettes[0].tints[0].color = String::from("not a color");
+        let stylesheet = stylesheet(&design_system);
+        assert!(stylesheet.contains("[data-theme=\"dark\"] {"));
+        assert!(stylesheet.contains("  --palette-primary-50: not a color;\n"));
+    }
+
+    #[test]
+    fn typography_classes_use_the_webview_names() {
+        let stylesheet = stylesheet(&sample_design_system());
+        assert!(stylesheet.contains(".typography-root {\n  font-size: 16px;\n"));
+        assert!(stylesheet.contains("  font-family: var(--font-default);\n}\n"));
+        assert!(stylesheet.contains(".typography-p {\n"));
+        assert!(stylesheet.contains(
+            ".typography-h1 {\n  font-size: 32px;\n  line-height: 40px;\n  font-weight: 700;\n"
+        ));
+        assert!(!stylesheet.contains(".typography-paragraph"));
+    }
+}
@@ -0,0 +1,432 @@
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+use crate::{
+    domain::{
+        design_system_domain::{DesignSystem, SemanticColorTokens, Shadows, TypographyScale},
+        export_domain::{DtcgGroup, DtcgNode, DtcgToken, ExportFile},
+    },
+    repository::design_system_repository::EXPORT_DTCG_PATH,
+};
+
+use super::{measurement_to_css, palette_token_name, resolve_color, serde_name};
+
+pub const PALETTES_GROUP: &str = "palettes";
+pub const COLORS_GROUP: &str = "color";
+pub const SEMANTIC_GROUP: &str = "semantic";
+pub const SEMANTIC_BASE_GROUP: &str = "base";
+pub const SEMANTIC_COMBINATIONS_GROUP: &str = "combinations";
+pub const SPACES_GROUP: &str = "space";
+pub const RADIUS_GROUP: &str = "radius";
+pub const FONTS_GROUP: &str = "font";
+pub const SHADOWS_GROUP: &str = "shadow";
+pub const TYPOGRAPHY_GROUP: &str = "typography";
+/// Key of the uitea data in `$extensions` (lossless typography & themes)
+pub const EXTENSION_KEY: &str = "app.uitea";
+
+/// Design tokens file following the W3C Design Tokens Community Group format
+pub fn export_dtcg(design_system: &DesignSystem) -> Result<ExportFile> {
+    let mut root: IndexMap<String, DtcgNode> = IndexMap::new();
+
+    root.insert(
+        String::from(PALETTES_GROUP),
+        group(
+            Some("color"),
+            design_system
+                .palettes
+                .iter()
+                .map(|palette| {
+                    (
+                        palette.palette_name.clone(),
+                        group(
+                            None,
+                            palette
+                                .tints
+                                .iter()
+                                .map(|tint| (tint.label.clone(), token(json!(tint.color))))
+                                .collect(),
+                        ),
+                    )
+                })
+                .collect(),
+        ),
+    );
+
+    let mut colors: IndexMap<String, DtcgNode> = IndexMap::new();
+    colors.insert(
+        String::from("white"),
+        token(json!(design_system.independant_colors.white)),
+    );
+    for tint in &design_system.independant_colors.independant_colors {
+        colors.insert(tint.label.clone(), token(json!(tint.color)));
+    }
+    root.insert(String::from(COLORS_GROUP), group(Some("color"), colors));
+
+    root.insert(
+        String::from(SEMANTIC_GROUP),
+        semantic_group(design_system, &design_system.semantic_color_tokens),
+    );
+
+    root.insert(
+        String::from(SPACES_GROUP),
+        group(
+            Some("dimension"),
+            design_system
+                .spaces
+                .iter()
+                .map(|space| {
+                    (
+                        space.space_key.clone(),
+                        token(json!(measurement_to_css(&space.space_value))),
+                    )
+                })
+                .collect(),
+        ),
+    );
+
+    let mut radius: IndexMap<String, DtcgNode> = IndexMap::new();
+    radius.insert(
+        String::from("default"),
+        token(json!(measurement_to_css(&design_system.radius.default))),
+    );
+    for radius_item in &design_system.radius.additionals_radius {
+        radius.insert(
+            radius_item.radius_key.clone(),
+            token(json!(measurement_to_css(&radius_item.radius_value))),
+        );
+    }
+    root.insert(String::from(RADIUS_GROUP), group(Some("dimension"), radius));
+
+    let mut fonts: IndexMap<String, DtcgNode> = IndexMap::new();
+    fonts.insert(
+        String::from("default"),
+        token(json!(design_system.fonts.default)),
+    );
+    for font in &design_system.fonts.additionals {
+        fonts.insert(font.font_name.clone(), token(json!(font.value)));
+    }
+    root.insert(String::from(FONTS_GROUP), group(Some("fontFamily"), fonts));
+
+    root.insert(
+        String::from(SHADOWS_GROUP),
+        group(
+            Some("shadow"),
+            design_system
+                .shadows
+                .iter()
+                .map(|shadows| {
+                    (
+                        shadows.shadow_name.clone(),
+                        DtcgNode::Token(DtcgToken {
+                            token_type: None,
+                            value: shadow_value(design_system, shadows),
+                            description: None,
+                            extensions: Some(json!({ EXTENSION_KEY: shadows.shadows_array })),
+                        }),
+                    )
+                })
+                .collect(),
+        ),
+    );
+
+    root.insert(
+        String::from(TYPOGRAPHY_GROUP),
+        group(
+            Some("typography"),
+            design_system
+                .typography
+                .scales()
+                .into_iter()
+                .map(|(scale_name, scale)| (String::from(scale_name), typography_token(scale)))
+                .collect(),
+        ),
+    );
+
+    let tokens_file = DtcgGroup {
+        extensions: Some(json!({
+            EXTENSION_KEY: {
+                "designSystemName": design_system.metadata.design_system_name,
+                "themes": design_system.themes,
+            }
+        })),
+        children: root,
+        ..Default::default()
+    };
+
+    Ok(ExportFile::from_string(
+        EXPORT_DTCG_PATH,
+        serde_json::to_string_pretty(&tokens_file)?,
+    ))
+}
+
+/// Alias of a palette or independant color token (palette-primary-500 -> {palettes.primary.500})
+pub fn token_alias(design_system: &DesignSystem, token_name: &str) -> String {
+    for palette in &design_system.palettes {
+        for tint in &palette.tints {
+            if palette_token_name(palette, tint) == token_name {
+                return format!(
+                    "{{{}.{}.{}}}",
+                    PALETTES_GROUP, palette.palette_name, tint.label
+                );
+            }
+        }
+    }
+    match token_name.strip_prefix("color-") {
+        Some(label) => format!("{{{}.{}}}", COLORS_GROUP, label),
+        None => format!("{{{}}}", token_name),
+    }
+}
+
+fn token(value: Value) -> DtcgNode {
+    DtcgNode::Token(DtcgToken {
+        token_type: None,
+        value,
+        description: None,
+        extensions: None,
+    })
+}
+
+fn group(group_type: Option<&str>, children: IndexMap<String, DtcgNode>) -> DtcgNode {
+    DtcgNode::Group(DtcgGroup {
+        group_type: group_type.map(String::from),
+        children,
+        ..Default::default()
+    })
+}
+
+fn semantic_group(
+    design_system: &DesignSystem,
+    semantic_color_tokens: &SemanticColorTokens,
+) -> DtcgNode {
+    let alias = |value: &Option<String>| -> Option<DtcgNode> {
+        value
+            .as_ref()
+            .map(|value| token(json!(token_alias(design_system, value))))
+    };
+
+    let mut base: IndexMap<String, DtcgNode> = IndexMap::new();
+    for (name, value) in [
+        ("background", &semantic_color_tokens.background),
+        ("text-light", &semantic_color_tokens.text_light),
+        ("text-default", &semantic_color_tokens.text_default),
+        ("text-dark", &semantic_color_tokens.text_dark),
+        ("border", &semantic_color_tokens.border),
+    ] {
+        if let Some(node) = alias(value) {
+            base.insert(String::from(name), node);
+        }
+    }
+
+    let mut combinations: IndexMap<String, DtcgNode> = IndexMap::new();
+    for collection in &semantic_color_tokens.color_combination_collections {
+        let mut states: IndexMap<String, DtcgNode> = IndexMap::new();
+        for (state, combination) in [
+            ("default", &collection.default),
+            ("hover", &collection.hover),
+            ("focus", &collection.focus),
+            ("active", &collection.active),
+        ] {
+            if let Some(combination) = combination {
+                let properties: IndexMap<String, DtcgNode> = [
+                    ("background", &combination.background),
+                    ("text", &combination.text),
+                    ("border", &combination.border),
+                ]
+                .into_iter()
+                .filter_map(|(property, value)| {
+                    alias(value).map(|node| (String::from(property), node))
+                })
+                .collect();
+                states.insert(String::from(state), group(None, properties));
+            }
+        }
+        let mut combination_group = DtcgGroup {
+            children: states,
+            ..Default::default()
+        };
+        combination_group.extensions = Some(json!({
+            EXTENSION_KEY: {
+                "group": collection.group,
+                "defaultCombination": collection.default_combination,
+            }
+        }));
+        combinations.insert(
+            collection
+                .combination_name
+                .clone()
+                .unwrap_or(String::from("collection")),
+            DtcgNode::Group(combination_group),
+        );
+    }
+
+    let mut semantic: IndexMap<String, DtcgNode> = IndexMap::new();
+    semantic.insert(String::from(SEMANTIC_BASE_GROUP), group(None, base));
+    semantic.insert(
+        String::from(SEMANTIC_COMBINATIONS_GROUP),
+        group(None, combinations),
+    );
+    // The DTCG format has no themes, the overrides are kept as written in uitea
+    let extensions: Option<Value> = if semantic_color_tokens.theme_overrides.is_empty() {
+        None
+    } else {
+        Some(json!({
+            EXTENSION_KEY: { "themeOverrides": semantic_color_tokens.theme_overrides }
+        }))
+    };
+    DtcgNode::Group(DtcgGroup {
+        group_type: Some(String::from("color")),
+        children: semantic,
+        extensions,
+        ..Default::default()
+    })
+}
+
+fn shadow_value(design_system: &DesignSystem, shadows: &Shadows) -> Value {
+    Value::Array(
+        shadows
+            .shadows_array
+            .iter()
+            .map(|shadow| {
+                let color = resolve_color(design_system, &shadow.color)
+                    .map(|mut color| {
+                        color.alpha = shadow.color_opacity as f64;
+                        color.to_hex()
+                    })
+                    .unwrap_or(shadow.color.clone());
+                json!({
+                    "color": color,
+                    "offsetX": format!("{}px", shadow.shadow_x),
+                    "offsetY": format!("{}px", shadow.shadow_y),
+                    "blur": format!("{}px", shadow.blur),
+                    "spread": format!("{}px", shadow.spread),
+                    "inset": shadow.inset,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn typography_token(scale: &TypographyScale) -> DtcgNode {
+    let font_family = format!(
+        "{{{}.{}}}",
+        FONTS_GROUP,
+        scale.font.as_deref().unwrap_or("default")
+    );
+    let line_height: Value = if scale.font_size.value != 0.0
+        && serde_name(&scale.font_size.unit) == serde_name(&scale.line_height.unit)
+    {
+        json!((scale.line_height.value / scale.font_size.value * 1000.0).round() / 1000.0)
+    } else {
+        json!(measurement_to_css(&scale.line_height))
+    };
+    let extension = serde_json::to_value(scale).unwrap_or(Value::Null);
+
+    DtcgNode::Token(DtcgToken {
+        token_type: None,
+        value: json!({
+            "fontFamily": font_family,
+            "fontSize": measurement_to_css(&scale.font_size),
+            "fontWeight": serde_name(&scale.font_weight).parse::<u16>().unwrap_or(400),
+            "letterSpacing": serde_name(&scale.letter_spacing),
+            "lineHeight": line_height,
+        }),
+        description: None,
+        extensions: Some(json!({ EXTENSION_KEY: extension })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn tokens_file(design_system: &DesignSystem) -> Value {
+        let file = export_dtcg(design_system).unwrap();
+        assert_eq!(file.path.to_str(), Some(EXPORT_DTCG_PATH));
+        serde_json::from_slice(&file.content).unwrap()
+    }
+
+    #[test]
+    fn groups_are_typed_and_hold_the_token_values() {
+        let tokens = tokens_file(&sample_design_system());
+        assert_eq!(tokens[PALETTES_GROUP]["$type"], "color");
+        assert_eq!(
+            tokens[PALETTES_GROUP]["primary"]["500"]["$value"],
+            "#3b82f6"
+        );
+        assert_eq!(tokens[COLORS_GROUP]["white"]["$value"], "#ffffff");
+        assert_eq!(tokens[COLORS_GROUP]["success"]["$value"], "#10b981");
+        assert_eq!(tokens[SPACES_GROUP]["$type"], "dimension");
+        assert_eq!(tokens[SPACES_GROUP]["2"]["$value"], "0.5rem");
+        assert_eq!(tokens[RADIUS_GROUP]["default"]["$value"], "4px");
+        assert_eq!(tokens[RADIUS_GROUP]["lg"]["$value"], "12px");
+        assert_eq!(tokens[FONTS_GROUP]["$type"], "fontFamily");
+        assert_eq!(tokens[FONTS_GROUP]["default"]["$value"], "Open Sans");
+    }
+
+    #[test]
+    fn semantic_tokens_are_aliases() {
+        let tokens = tokens_file(&sample_design_system());
+        let semantic = &tokens[SEMANTIC_GROUP];
+        assert_eq!(
+            semantic[SEMANTIC_BASE_GROUP]["background"]["$value"],
+            "{palettes.gray.50}"
+        );
+        assert!(semantic[SEMANTIC_BASE_GROUP].get("border").is_none());
+        let button = &semantic[SEMANTIC_COMBINATIONS_GROUP]["button"];
+        assert_eq!(button["default"]["text"]["$value"], "{color.white}");
+        assert_eq!(
+            button["hover"]["background"]["$value"],
+            "{palettes.primary.900}"
+        );
+        assert_eq!(
+            button["$extensions"][EXTENSION_KEY]["defaultCombination"],
+            true
+        );
+        assert_eq!(
+            semantic["$extensions"][EXTENSION_KEY]["themeOverrides"]["dark"]["background"],
+            "palette-gray-900"
+        );
+    }
+
+    #[test]
+    fn unknown_tokens_keep_their_name_as_alias() {
+        let design_system = sample_design_system();
+        assert_eq!(
+            token_alias(&design_system, "palette-gray-900"),
+            "{palettes.gray.900}"
+        );
+        assert_eq!(
+            token_alias(&design_system, "color-success"),
+            "{color.success}"
+        );
+        assert_eq!(token_alias(&design_system, "missing"), "{missing}");
+    }
+
+    #[test]
+    fn composite_tokens_keep_the_uitea_values_in_extensions() {
+        let tokens = tokens_file(&sample_design_system());
+        let shadow = &tokens[SHADOWS_GROUP]["shadow"];
+        assert_eq!(shadow["$value"][0]["color"], "#11182740");
+        assert_eq!(shadow["$value"][0]["offsetY"], "4px");
+        assert_eq!(
+            shadow["$extensions"][EXTENSION_KEY][0]["color"],
+            "palette-gray-900"
+        );
+
+        let h1 = &tokens[TYPOGRAPHY_GROUP]["h1"];
+        assert_eq!(h1["$value"]["fontFamily"], "{font.default}");
+        assert_eq!(h1["$value"]["fontSize"], "32px");
+        assert_eq!(h1["$value"]["fontWeight"], 700);
+        assert_eq!(h1["$value"]["lineHeight"], 1.25);
+        assert_eq!(
+            h1["$extensions"][EXTENSION_KEY]["lineHeight"]["value"],
+            40.0
+        );
+        assert_eq!(
+            tokens["$extensions"][EXTENSION_KEY]["designSystemName"],
+            "Demo"
+        );
+    }
+}
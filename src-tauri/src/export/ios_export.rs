@@ -0,0 +1,419 @@
+use std::{collections::HashMap, fmt::Write, path::PathBuf};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{
+    domain::{
+        color_domain::Rgb,
+        design_system_domain::{
+            DesignSystem, FontWeight, IndependantColors, Palette, SemanticColorTokens,
+        },
+        export_domain::ExportFile,
+    },
+    repository::design_system_repository::EXPORT_IOS_PATH,
+};
+
+use super::{
+    format_number, independant_color_tokens, is_dark_background, main_theme_background,
+    measurement_to_px, opposite_theme, palette_token_name, recolor_themes, root_font_size,
+    semantic_color_tokens, serde_name,
+};
+
+const ASSET_CATALOG: &str = "DesignTokens.xcassets";
+const SWIFT_FILE: &str = "DesignTokens.swift";
+
+/// Colors of one appearance (token name -> color)
+type Appearance = HashMap<String, Rgb>;
+
+/// Asset catalog with a colorset per color (light / dark appearances from the themes)
+/// and `DesignTokens.swift` with the spacing, radius, font styles and colors
+pub fn export_ios(design_system: &DesignSystem) -> Result<Vec<ExportFile>> {
+    let (light, dark) = appearances(design_system)?;
+    let color_names: Vec<String> = color_names(design_system);
+
+    let mut files: Vec<ExportFile> = vec![ios_file(
+        &[ASSET_CATALOG, "Contents.json"],
+        &json!({ "info": { "author": "xcode", "version": 1 } }),
+    )?];
+    for token_name in &color_names {
+        let light_color = match light.get(token_name) {
+            Some(color) => color,
+            None => continue,
+        };
+        let mut colors = vec![json!({
+            "color": colorset_color(light_color),
+            "idiom": "universal",
+        })];
+        if let Some(dark_color) = dark.as_ref().and_then(|dark| dark.get(token_name)) {
+            colors.push(json!({
+                "appearances": [{ "appearance": "luminosity", "value": "dark" }],
+                "color": colorset_color(dark_color),
+                "idiom": "universal",
+            }));
+        }
+        files.push(ios_file(
+            &[
+                ASSET_CATALOG,
+                &format!("{}.colorset", swift_name(token_name)),
+                "Contents.json",
+            ],
+            &json!({
+                "colors": colors,
+                "info": { "author": "xcode", "version": 1 },
+            }),
+        )?);
+    }
+
+    let available_colors: Vec<String> = color_names
+        .into_iter()
+        .filter(|token_name| light.contains_key(token_name))
+        .collect();
+    files.push(ExportFile {
+        path: PathBuf::from(EXPORT_IOS_PATH).join(SWIFT_FILE),
+        content: design_tokens_swift(design_system, &available_colors).into_bytes(),
+    });
+    Ok(files)
+}
+
+fn ios_file(path: &[&str], content: &Value) -> Result<ExportFile> {
+    Ok(ExportFile {
+        path: path
+            .iter()
+            .fold(PathBuf::from(EXPORT_IOS_PATH), |path, part| path.join(part)),
+        content: serde_json::to_string_pretty(content)?.into_bytes(),
+    })
+}
+
+/// Swift identifier : palette-primary-500 -> palettePrimary500
+fn swift_name(token_name: &str) -> String {
+    let name: String = token_name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .enumerate()
+        .map(|(index, part)| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) if index == 0 => {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                }
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Token names of the palettes, independant colors & semantic colors
+fn color_names(design_system: &DesignSystem) -> Vec<String> {
+    let mut names: Vec<String> =
+        color_tokens(&design_system.palettes, &design_system.independant_colors)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+    names.extend(
+        semantic_color_tokens(&design_system.semantic_color_tokens)
+            .into_iter()
+            .map(|(name, _)| name),
+    );
+    names
+}
+
+fn color_tokens(
+    palettes: &[Palette],
+    independant_colors: &IndependantColors,
+) -> Vec<(String, String)> {
+    let mut tokens: Vec<(String, String)> = palettes
+        .iter()
+        .flat_map(|palette| {
+            palette
+                .tints
+                .iter()
+                .map(move |tint| (palette_token_name(palette, tint), tint.color.clone()))
+        })
+        .collect();
+    tokens.extend(independant_color_tokens(independant_colors));
+    tokens
+}
+
+/// Colors of an appearance, semantic colors resolved (asset catalogs have no alias)
+fn appearance(
+    palettes: &[Palette],
+    independant_colors: &IndependantColors,
+    semantic_tokens: &SemanticColorTokens,
+) -> Appearance {
+    let mut colors: Appearance = color_tokens(palettes, independant_colors)
+        .into_iter()
+        .filter_map(|(name, color)| Rgb::parse(&color).ok().map(|color| (name, color)))
+        .collect();
+    for (name, token_name) in semantic_color_tokens(semantic_tokens) {
+        if let Some(color) = colors.get(&token_name).cloned() {
+            colors.insert(name, color);
+        }
+    }
+    colors
+}
+
+/// (light, dark) appearances : the main theme, and the opposite theme. Without such a theme,
+/// the colorsets have a single appearance
+fn appearances(design_system: &DesignSystem) -> Result<(Appearance, Option<Appearance>)> {
+    let main = appearance(
+        &design_system.palettes,
+        &design_system.independant_colors,
+        &design_system.semantic_color_tokens,
+    );
+    let opposite: Option<Appearance> = match opposite_theme(design_system) {
+        Some(theme) => recolor_themes(design_system)
+            .into_iter()
+            .find(|(name, _)| *name == theme.name)
+            .map(|(_, recolor)| {
+                appearance(
+                    &recolor.palettes,
+                    &recolor.independant_colors,
+                    &design_system.semantic_color_tokens.for_theme(&theme.name),
+                )
+            }),
+        None => None,
+    };
+
+    let main_is_dark = is_dark_background(main_theme_background(design_system));
+    Ok(match (main_is_dark, opposite) {
+        (true, Some(light)) => (light, Some(main)),
+        (_, opposite) => (main, opposite),
+    })
+}
+
+fn colorset_color(color: &Rgb) -> Value {
+    let [red, green, blue] = color.to_bytes();
+    json!({
+        "color-space": "srgb",
+        "components": {
+            "alpha": format!("{:.3}", color.alpha.clamp(0.0, 1.0)),
+            "red": format!("0x{:02X}", red),
+            "green": format!("0x{:02X}", green),
+            "blue": format!("0x{:02X}", blue),
+        }
+    })
+}
+
+fn swift_font_weight(font_weight: &FontWeight) -> &'static str {
+    match serde_name(font_weight).as_str() {
+        "100" => "ultraLight",
+        "200" => "thin",
+        "300" => "light",
+        "500" => "medium",
+        "600" => "semibold",
+        "700" => "bold",
+        "800" => "heavy",
+        "900" => "black",
+        _ => "regular",
+    }
+}
+
+/// First family of a font stack, without quotes (`"Open Sans", sans-serif` -> Open Sans)
+fn font_name(value: &str) -> String {
+    value
+        .split(',')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string()
+}
+
+fn design_tokens_swift(design_system: &DesignSystem, color_names: &[String]) -> String {
+    let root_font_size = root_font_size(design_system);
+    let mut swift = format!(
+        "// Design tokens of {}, generated by uitea
+import SwiftUI
+
+enum DesignTokens {{
+    enum Spacing {{
+",
+        design_system.metadata.design_system_name
+    );
+    for space in &design_system.spaces {
+        let _ = writeln!(
+            swift,
+            "        static let {}: CGFloat = {}",
+            swift_name(&format!("space-{}", space.space_key)),
+            format_number(measurement_to_px(&space.space_value, root_font_size))
+        );
+    }
+
+    swift.push_str("    }\n\n    enum Radius {\n");
+    let _ = writeln!(
+        swift,
+        "        static let radius: CGFloat = {}",
+        format_number(measurement_to_px(
+            &design_system.radius.default,
+            root_font_size
+        ))
+    );
+    for radius in &design_system.radius.additionals_radius {
+        let _ = writeln!(
+            swift,
+            "        static let {}: CGFloat = {}",
+            swift_name(&format!("radius-{}", radius.radius_key)),
+            format_number(measurement_to_px(&radius.radius_value, root_font_size))
+        );
+    }
+
+    swift.push_str("    }\n\n    enum Typography {\n");
+    for (scale_name, scale) in design_system.typography.scales() {
+        let font_value: &str = scale
+            .font
+            .as_ref()
+            .and_then(|font| {
+                design_system
+                    .fonts
+                    .additionals
+                    .iter()
+                    .find(|additional| additional.font_name == *font)
+            })
+            .map(|font| font.value.as_str())
+            .unwrap_or(&design_system.fonts.default);
+        let font_size = measurement_to_px(&scale.font_size, root_font_size);
+        let letter_spacing: f64 = serde_name(&scale.letter_spacing)
+            .trim_end_matches("em")
+            .parse()
+            .unwrap_or(0.0);
+        let _ = writeln!(
+            swift,
+            "        static let {} = FontStyle(fontName: \"{}\", size: {}, lineHeight: {}, weight: .{}, letterSpacing: {})",
+            swift_name(&format!("typography-{}", scale_name)),
+            font_name(font_value),
+            format_number(font_size),
+            format_number(measurement_to_px(&scale.line_height, root_font_size)),
+            swift_font_weight(&scale.font_weight),
+            format_number(letter_spacing * font_size)
+        );
+    }
+
+    let _ = write!(
+        swift,
+        "    }}\n\n    /// Colors of {}\n    enum Colors {{\n",
+        ASSET_CATALOG
+    );
+    for token_name in color_names {
+        let name = swift_name(token_name);
+        let _ = writeln!(
+            swift,
+            "        static let {} = Color(\"{}\", bundle: .main)",
+            name, name
+        );
+    }
+    swift.push_str(
+        "    }
+}
+
+/// Font style of a typography scale (sizes in points, letter spacing for `.tracking`)
+struct FontStyle {
+    let fontName: String
+    let size: CGFloat
+    let lineHeight: CGFloat
+    let weight: Font.Weight
+    let letterSpacing: CGFloat
+
+    var font: Font {
+        Font.custom(fontName, size: size).weight(weight)
+    }
+
+    /// Extra space between lines, to apply with `.lineSpacing`
+    var lineSpacing: CGFloat {
+        max(lineHeight - size, 0)
+    }
+}
+",
+    );
+    swift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn colorset(files: &[ExportFile], name: &str) -> Value {
+        let path = PathBuf::from(EXPORT_IOS_PATH)
+            .join(ASSET_CATALOG)
+            .join(format!("{}.colorset", name))
+            .join("Contents.json");
+        let file = files.iter().find(|file| file.path == path).unwrap();
+        serde_json::from_slice(&file.content).unwrap()
+    }
+
+    #[test]
+    fn colorsets_have_the_light_and_dark_appearances() {
+        let files = export_ios(&sample_design_system()).unwrap();
+        // Catalog, 6 tints, 2 independant colors, 6 semantic colors and the swift file
+        assert_eq!(files.len(), 16);
+        let primary = colorset(&files, "palettePrimary500");
+        let colors = primary["colors"].as_array().unwrap();
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors[0]["color"]["components"]["red"], "0x3B");
+        assert_eq!(colors[0]["color"]["components"]["alpha"], "1.000");
+        assert_eq!(colors[1]["appearances"][0]["value"], "dark");
+
+        // Semantic colors are resolved with the overrides of the dark theme
+        let background = colorset(&files, "baseBackground");
+        let gray_900 = colorset(&files, "paletteGray900");
+        assert_eq!(
+            background["colors"][0],
+            colorset(&files, "paletteGray50")["colors"][0]
+        );
+        assert_eq!(background["colors"][1], gray_900["colors"][1]);
+    }
+
+    #[test]
+    fn single_theme_has_a_single_appearance() {
+        let mut design_system = sample_design_system();
+        design_system.themes.other_themes.clear();
+        let files = export_ios(&design_system).unwrap();
+        let colors = colorset(&files, "colorWhite")["colors"].clone();
+        assert_eq!(colors.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn dark_main_theme_is_the_dark_appearance() {
+        let mut design_system = sample_design_system();
+        let themes = &mut design_system.themes;
+        let light = themes.main_theme.take().unwrap();
+        themes.main_theme = Some(themes.other_themes.remove(0));
+        themes.other_themes.push(light);
+        let files = export_ios(&design_system).unwrap();
+        let colors = colorset(&files, "palettePrimary500")["colors"].clone();
+        assert_eq!(colors[1]["color"]["components"]["red"], "0x3B");
+        assert_ne!(colors[0]["color"]["components"]["red"], "0x3B");
+    }
+
+    #[test]
+    fn swift_file_lists_the_tokens() {
+        let files = export_ios(&sample_design_system()).unwrap();
+        let swift = String::from_utf8(files.last().unwrap().content.clone()).unwrap();
+        assert!(swift.starts_with("// Design tokens of Demo, generated by uitea\n"));
+        assert!(swift.contains("        static let space2: CGFloat = 8\n"));
+        assert!(swift.contains("        static let radiusLg: CGFloat = 12\n"));
+        assert!(swift.contains("static let typographyH1 = FontStyle(fontName: \"Open Sans\", size: 32, lineHeight: 40, weight: .bold, letterSpacing: 0)"));
+        assert!(swift.contains(
+            "        static let buttonHoverBackground = Color(\"buttonHoverBackground\", bundle: .main)\n"
+        ));
+    }
+
+    #[test]
+    fn names_are_valid_swift_identifiers() {
+        assert_eq!(swift_name("palette-primary-500"), "palettePrimary500");
+        assert_eq!(
+            swift_name("button-hover-background"),
+            "buttonHoverBackground"
+        );
+        assert_eq!(swift_name("2xl"), "_2xl");
+        assert_eq!(font_name("\"Open Sans\", sans-serif"), "Open Sans");
+        assert_eq!(swift_font_weight(&FontWeight::Six), "semibold");
+    }
+}
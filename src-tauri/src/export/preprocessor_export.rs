@@ -0,0 +1,314 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+
+use crate::{
+    domain::{design_system_domain::DesignSystem, export_domain::ExportFile},
+    repository::design_system_repository::{EXPORT_LESS_PATH, EXPORT_SCSS_PATH},
+};
+
+use super::{
+    font_family, independant_color_tokens, measurement_to_css, palette_token_name,
+    semantic_color_overrides, semantic_color_tokens, shadows_to_css, typography_properties,
+};
+
+/// Stylesheet preprocessor, only the variable prefix and the maps / mixins syntax differ
+#[derive(Clone, Copy, PartialEq)]
+enum Syntax {
+    Scss,
+    Less,
+}
+
+impl Syntax {
+    fn variable(self, name: &str) -> String {
+        match self {
+            Syntax::Scss => format!("${}", name),
+            Syntax::Less => format!("@{}", name),
+        }
+    }
+}
+
+/// `_tokens.scss` : variables, `$palettes` / `$spaces` / `$radius` / `$typography` maps
+/// and a `typography($scale)` mixin. The semantic colors overridden by a theme are written as
+/// `$theme-{theme}-{token}` variables
+pub fn export_scss(design_system: &DesignSystem) -> Result<ExportFile> {
+    Ok(ExportFile::from_string(
+        EXPORT_SCSS_PATH,
+        write_tokens(design_system, Syntax::Scss),
+    ))
+}
+
+/// `tokens.less` : same variables, maps as detached rulesets and a `.typography(@scale)` mixin
+pub fn export_less(design_system: &DesignSystem) -> Result<ExportFile> {
+    Ok(ExportFile::from_string(
+        EXPORT_LESS_PATH,
+        write_tokens(design_system, Syntax::Less),
+    ))
+}
+
+fn write_tokens(design_system: &DesignSystem, syntax: Syntax) -> String {
+    let mut tokens = format!(
+        "// Design tokens of {}, generated by uitea\n",
+        design_system.metadata.design_system_name
+    );
+    let write_variable = |tokens: &mut String, name: &str, value: &str| {
+        let _ = writeln!(tokens, "{}: {};", syntax.variable(name), value);
+    };
+
+    tokens.push_str("\n// Palettes\n");
+    for palette in &design_system.palettes {
+        for tint in &palette.tints {
+            write_variable(&mut tokens, &palette_token_name(palette, tint), &tint.color);
+        }
+    }
+
+    tokens.push_str("\n// Independant colors\n");
+    for (name, color) in independant_color_tokens(&design_system.independant_colors) {
+        write_variable(&mut tokens, &name, &color);
+    }
+
+    let semantic_tokens = semantic_color_tokens(&design_system.semantic_color_tokens);
+    if !semantic_tokens.is_empty() {
+        tokens.push_str("\n// Semantic colors\n");
+        for (name, value) in semantic_tokens {
+            write_variable(&mut tokens, &name, &syntax.variable(&value));
+        }
+    }
+    for (theme_name, overridden_tokens) in semantic_color_overrides(design_system) {
+        let _ = writeln!(tokens, "\n// Semantic colors of the {} theme", theme_name);
+        for (name, value) in overridden_tokens {
+            write_variable(
+                &mut tokens,
+                &format!("theme-{}-{}", theme_name, name),
+                &syntax.variable(&value),
+            );
+        }
+    }
+
+    tokens.push_str("\n// Fonts\n");
+    write_variable(
+        &mut tokens,
+        "font-default",
+        &font_family(&design_system.fonts.default),
+    );
+    for font in &design_system.fonts.additionals {
+        write_variable(
+            &mut tokens,
+            &format!("font-{}", font.font_name),
+            &font_family(&font.value),
+        );
+    }
+
+    if !design_system.spaces.is_empty() {
+        tokens.push_str("\n// Spaces\n");
+        for space in &design_system.spaces {
+            write_variable(
+                &mut tokens,
+                &format!("space-{}", space.space_key),
+                &measurement_to_css(&space.space_value),
+            );
+        }
+    }
+
+    // `radius` is the name of the map, the default radius is `radius-default`
+    tokens.push_str("\n// Radius\n");
+    write_variable(
+        &mut tokens,
+        "radius-default",
+        &measurement_to_css(&design_system.radius.default),
+    );
+    for radius in &design_system.radius.additionals_radius {
+        write_variable(
+            &mut tokens,
+            &format!("radius-{}", radius.radius_key),
+            &measurement_to_css(&radius.radius_value),
+        );
+    }
+
+    if !design_system.shadows.is_empty() {
+        tokens.push_str("\n// Shadows\n");
+        for shadows in &design_system.shadows {
+            write_variable(
+                &mut tokens,
+                &format!("shadow-{}", shadows.shadow_name),
+                &shadows_to_css(design_system, shadows),
+            );
+        }
+    }
+
+    tokens.push_str("\n// Maps\n");
+    match syntax {
+        Syntax::Scss => write_scss_maps(&mut tokens, design_system),
+        Syntax::Less => write_less_maps(&mut tokens, design_system),
+    }
+    tokens
+}
+
+fn write_scss_maps(tokens: &mut String, design_system: &DesignSystem) {
+    tokens.push_str("$palettes: (\n");
+    for palette in &design_system.palettes {
+        let _ = writeln!(tokens, "  \"{}\": (", palette.palette_name);
+        for tint in &palette.tints {
+            let _ = writeln!(
+                tokens,
+                "    \"{}\": ${},",
+                tint.label,
+                palette_token_name(palette, tint)
+            );
+        }
+        tokens.push_str("  ),\n");
+    }
+    tokens.push_str(");\n");
+
+    tokens.push_str("\n$spaces: (\n");
+    for space in &design_system.spaces {
+        let _ = writeln!(
+            tokens,
+            "  \"{}\": $space-{},",
+            space.space_key, space.space_key
+        );
+    }
+    tokens.push_str(");\n");
+
+    tokens.push_str("\n$radius: (\n  \"default\": $radius-default,\n");
+    for radius in &design_system.radius.additionals_radius {
+        let _ = writeln!(
+            tokens,
+            "  \"{}\": $radius-{},",
+            radius.radius_key, radius.radius_key
+        );
+    }
+    tokens.push_str(");\n");
+
+    tokens.push_str("\n$typography: (\n");
+    for (scale_name, scale) in design_system.typography.scales() {
+        let _ = writeln!(tokens, "  \"{}\": (", scale_name);
+        for (property, value) in
+            typography_properties(scale_name, scale, |name| format!("${}", name))
+        {
+            let _ = writeln!(tokens, "    \"{}\": {},", property, value);
+        }
+        tokens.push_str("  ),\n");
+    }
+    tokens.push_str(");\n");
+
+    tokens.push_str(
+        "
+@mixin typography($scale) {
+  @if not map-has-key($typography, $scale) {
+    @error \"Unknown typography scale `#{$scale}`\";
+  }
+  @each $property, $value in map-get($typography, $scale) {
+    #{$property}: $value;
+  }
+}
+",
+    );
+}
+
+/// Less maps keys are identifiers, so the keys are the variable names (`@spaces[space-1]`)
+fn write_less_maps(tokens: &mut String, design_system: &DesignSystem) {
+    tokens.push_str("@palettes: {\n");
+    for palette in &design_system.palettes {
+        for tint in &palette.tints {
+            let name = palette_token_name(palette, tint);
+            let _ = writeln!(tokens, "  {}: @{};", name, name);
+        }
+    }
+    tokens.push_str("}\n");
+
+    tokens.push_str("\n@spaces: {\n");
+    for space in &design_system.spaces {
+        let _ = writeln!(
+            tokens,
+            "  space-{}: @space-{};",
+            space.space_key, space.space_key
+        );
+    }
+    tokens.push_str("}\n");
+
+    tokens.push_str("\n@radius: {\n  radius-default: @radius-default;\n");
+    for radius in &design_system.radius.additionals_radius {
+        let _ = writeln!(
+            tokens,
+            "  radius-{}: @radius-{};",
+            radius.radius_key, radius.radius_key
+        );
+    }
+    tokens.push_str("}\n");
+
+    tokens.push_str("\n// Typography : .typography(h1);\n");
+    for (scale_name, scale) in design_system.typography.scales() {
+        let _ = writeln!(tokens, ".typography({}) {{", scale_name);
+        for (property, value) in
+            typography_properties(scale_name, scale, |name| format!("@{}", name))
+        {
+            let _ = writeln!(tokens, "  {}: {};", property, value);
+        }
+        tokens.push_str("}\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn content(file: ExportFile) -> String {
+        String::from_utf8(file.content).unwrap()
+    }
+
+    #[test]
+    fn scss_variables_reference_each_other() {
+        let scss = content(export_scss(&sample_design_system()).unwrap());
+        assert!(scss.starts_with("// Design tokens of Demo, generated by uitea\n"));
+        for declaration in [
+            "$palette-primary-50: #eff6ff;\n",
+            "$color-success: #10b981;\n",
+            "$base-background: $palette-gray-50;\n",
+            "$button-text: $color-white;\n",
+            "$font-default: \"Open Sans\";\n",
+            "$space-2: 0.5rem;\n",
+            "$radius-default: 4px;\n",
+            "$shadow-shadow: 0px 4px 4px 0px rgba(17, 24, 39, 0.25);\n",
+        ] {
+            assert!(scss.contains(declaration), "{}", declaration);
+        }
+    }
+
+    #[test]
+    fn scss_maps_and_mixin() {
+        let scss = content(export_scss(&sample_design_system()).unwrap());
+        assert!(scss.contains("$palettes: (\n  \"primary\": (\n    \"50\": $palette-primary-50,\n"));
+        assert!(scss.contains("$spaces: (\n  \"1\": $space-1,\n  \"2\": $space-2,\n);\n"));
+        assert!(scss.contains("$radius: (\n  \"default\": $radius-default,\n"));
+        assert!(scss.contains("    \"font-family\": $font-default,\n"));
+        assert!(scss.contains("@mixin typography($scale) {\n"));
+    }
+
+    #[test]
+    fn less_uses_its_own_syntax() {
+        let less = content(export_less(&sample_design_system()).unwrap());
+        assert!(less.contains("@base-background: @palette-gray-50;\n"));
+        assert!(less.contains("@palettes: {\n  palette-primary-50: @palette-primary-50;\n"));
+        assert!(less.contains(".typography(root) {\n  font-size: 16px;\n"));
+        assert!(less.contains("  font-family: @font-default;\n"));
+        assert!(!less.contains('$'));
+    }
+
+    #[test]
+    fn theme_overrides_are_prefixed_variables() {
+        let scss = content(export_scss(&sample_design_system()).unwrap());
+        assert!(scss.contains("$base-background: $palette-gray-50;\n"));
+        assert!(scss.contains(
+            "// Semantic colors of the dark theme\n\
+             $theme-dark-base-background: $palette-gray-900;\n\
+             $theme-dark-base-text-default: $palette-gray-50;\n\
+             $theme-dark-button-hover-background: $palette-primary-50;\n"
+        ));
+        // The tokens kept by the theme are not repeated
+        assert!(!scss.contains("$theme-dark-base-text-light"));
+        let less = content(export_less(&sample_design_system()).unwrap());
+        assert!(less.contains("@theme-dark-base-background: @palette-gray-900;\n"));
+    }
+}
@@ -0,0 +1,428 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+use crate::{
+    domain::{
+        design_system_domain::{DesignSystem, Measurement, SemanticColorTokens, UnitOfMeasurement},
+        export_domain::{ExportFile, StyleDictionaryNode, StyleDictionaryToken},
+    },
+    repository::design_system_repository::EXPORT_STYLE_DICTIONARY_PATH,
+};
+
+use super::{
+    measurement_to_css, palette_token_name, recolor_themes, semantic_color_overrides, serde_name,
+    shadows_to_css, DEFAULT_ROOT_FONT_SIZE,
+};
+
+type Tree = IndexMap<String, StyleDictionaryNode>;
+
+/// Style Dictionary source tree (tokens/color, tokens/size, tokens/font, tokens/shadow). The
+/// palettes are the ones of the main theme, the recolored palettes of the other themes are only
+/// listed in the comments. The semantic tokens of a theme with overrides are written in
+/// `color/semantic-{theme}.json` under `color.theme.{theme}`
+pub fn export_style_dictionary(design_system: &DesignSystem) -> Result<Vec<ExportFile>> {
+    let mut files = vec![
+        tokens_file("color", "palettes", palettes_tree(design_system))?,
+        tokens_file(
+            "color",
+            "independant",
+            independant_colors_tree(design_system),
+        )?,
+        tokens_file(
+            "color",
+            "semantic",
+            semantic_tree(
+                design_system,
+                &design_system.semantic_color_tokens,
+                &["color", "semantic"],
+            ),
+        )?,
+        tokens_file("size", "space", space_tree(design_system))?,
+        tokens_file("size", "radius", radius_tree(design_system))?,
+        tokens_file("size", "font", font_size_tree(design_system))?,
+        tokens_file("font", "family", font_family_tree(design_system))?,
+        tokens_file("font", "scale", font_scale_tree(design_system))?,
+        tokens_file("shadow", "shadow", shadow_tree(design_system))?,
+    ];
+    for (theme_name, _) in semantic_color_overrides(design_system) {
+        files.push(tokens_file(
+            "color",
+            &format!("semantic-{}", theme_name),
+            semantic_tree(
+                design_system,
+                &design_system.semantic_color_tokens.for_theme(&theme_name),
+                &["color", "theme", &theme_name],
+            ),
+        )?);
+    }
+    Ok(files)
+}
+
+/// Reference to a palette or independant color token (palette-primary-500 -> {color.palette.primary.500.value})
+pub fn token_reference(design_system: &DesignSystem, token_name: &str) -> String {
+    for palette in &design_system.palettes {
+        for tint in &palette.tints {
+            if palette_token_name(palette, tint) == token_name {
+                return format!(
+                    "{{color.palette.{}.{}.value}}",
+                    palette.palette_name, tint.label
+                );
+            }
+        }
+    }
+    match token_name.strip_prefix("color-") {
+        Some(label) => format!("{{color.{}.value}}", label),
+        None => format!("{{{}.value}}", token_name),
+    }
+}
+
+fn tokens_file(category: &str, name: &str, tree: Tree) -> Result<ExportFile> {
+    Ok(ExportFile {
+        path: PathBuf::from(EXPORT_STYLE_DICTIONARY_PATH)
+            .join(category)
+            .join(format!("{}.json", name)),
+        content: serde_json::to_string_pretty(&tree)?.into_bytes(),
+    })
+}
+
+fn token(value: Value, comment: Option<String>) -> StyleDictionaryNode {
+    StyleDictionaryNode::Token(StyleDictionaryToken { value, comment })
+}
+
+/// Nest a tree under the given path (["color", "palette"] -> { color: { palette: tree } })
+fn nest(path: &[&str], tree: Tree) -> Tree {
+    path.iter().rev().fold(tree, |tree, key| {
+        let mut parent = Tree::new();
+        parent.insert(String::from(*key), StyleDictionaryNode::Group(tree));
+        parent
+    })
+}
+
+fn rem_value(measurement: &Measurement) -> Value {
+    let rem = match measurement.unit {
+        UnitOfMeasurement::REM => measurement.value,
+        UnitOfMeasurement::PX => measurement.value / DEFAULT_ROOT_FONT_SIZE,
+    };
+    json!((rem * 10000.0).round() / 10000.0)
+}
+
+fn size_token(measurement: &Measurement) -> StyleDictionaryNode {
+    token(
+        rem_value(measurement),
+        Some(measurement_to_css(measurement)),
+    )
+}
+
+/// Palettes of the main theme, the recolored tints of the other themes are kept in the comment
+fn palettes_tree(design_system: &DesignSystem) -> Tree {
+    let themes = recolor_themes(design_system);
+    let mut palettes = Tree::new();
+    for (palette_index, palette) in design_system.palettes.iter().enumerate() {
+        let mut tints = Tree::new();
+        for (tint_index, tint) in palette.tints.iter().enumerate() {
+            let theme_colors: Vec<String> = themes
+                .iter()
+                .filter_map(|(theme_name, recolor)| {
+                    recolor
+                        .palettes
+                        .get(palette_index)
+                        .and_then(|palette| palette.tints.get(tint_index))
+                        .map(|tint| format!("{} : {}", theme_name, tint.color))
+                })
+                .collect();
+            let comment = if theme_colors.is_empty() {
+                None
+            } else {
+                Some(theme_colors.join(", "))
+            };
+            tints.insert(tint.label.clone(), token(json!(tint.color), comment));
+        }
+        palettes.insert(
+            palette.palette_name.clone(),
+            StyleDictionaryNode::Group(tints),
+        );
+    }
+    nest(&["color", "palette"], palettes)
+}
+
+fn independant_colors_tree(design_system: &DesignSystem) -> Tree {
+    let mut colors = Tree::new();
+    colors.insert(
+        String::from("white"),
+        token(json!(design_system.independant_colors.white), None),
+    );
+    for tint in &design_system.independant_colors.independant_colors {
+        colors.insert(tint.label.clone(), token(json!(tint.color), None));
+    }
+    nest(&["color"], colors)
+}
+
+/// Semantic tokens reference the palettes, the referenced token name is kept in the comment
+fn semantic_tree(
+    design_system: &DesignSystem,
+    semantic_color_tokens: &SemanticColorTokens,
+    path: &[&str],
+) -> Tree {
+    let reference = |token_name: &str| -> StyleDictionaryNode {
+        token(
+            json!(token_reference(design_system, token_name)),
+            Some(String::from(token_name)),
+        )
+    };
+
+    let mut semantic = Tree::new();
+    let mut base = Tree::new();
+    for (name, value) in [
+        ("background", &semantic_color_tokens.background),
+        ("text-light", &semantic_color_tokens.text_light),
+        ("text-default", &semantic_color_tokens.text_default),
+        ("text-dark", &semantic_color_tokens.text_dark),
+        ("border", &semantic_color_tokens.border),
+    ] {
+        if let Some(value) = value {
+            base.insert(String::from(name), reference(value));
+        }
+    }
+    semantic.insert(String::from("base"), StyleDictionaryNode::Group(base));
+
+    for collection in &semantic_color_tokens.color_combination_collections {
+        let mut states = Tree::new();
+        for (state, combination) in [
+            ("default", &collection.default),
+            ("hover", &collection.hover),
+            ("focus", &collection.focus),
+            ("active", &collection.active),
+        ] {
+            if let Some(combination) = combination {
+                let properties: Tree = [
+                    ("background", &combination.background),
+                    ("text", &combination.text),
+                    ("border", &combination.border),
+                ]
+                .into_iter()
+                .filter_map(|(property, value)| {
+                    value
+                        .as_ref()
+                        .map(|value| (String::from(property), reference(value)))
+                })
+                .collect();
+                states.insert(String::from(state), StyleDictionaryNode::Group(properties));
+            }
+        }
+        semantic.insert(
+            collection
+                .combination_name
+                .clone()
+                .unwrap_or(String::from("collection")),
+            StyleDictionaryNode::Group(states),
+        );
+    }
+
+    nest(path, semantic)
+}
+
+fn space_tree(design_system: &DesignSystem) -> Tree {
+    let spaces: Tree = design_system
+        .spaces
+        .iter()
+        .map(|space| (space.space_key.clone(), size_token(&space.space_value)))
+        .collect();
+    nest(&["size", "space"], spaces)
+}
+
+fn radius_tree(design_system: &DesignSystem) -> Tree {
+    let mut radius = Tree::new();
+    radius.insert(
+        String::from("default"),
+        size_token(&design_system.radius.default),
+    );
+    for radius_item in &design_system.radius.additionals_radius {
+        radius.insert(
+            radius_item.radius_key.clone(),
+            size_token(&radius_item.radius_value),
+        );
+    }
+    nest(&["size", "radius"], radius)
+}
+
+fn font_size_tree(design_system: &DesignSystem) -> Tree {
+    let font_sizes: Tree = design_system
+        .typography
+        .scales()
+        .into_iter()
+        .map(|(scale_name, scale)| (String::from(scale_name), size_token(&scale.font_size)))
+        .collect();
+    nest(&["size", "font"], font_sizes)
+}
+
+fn font_family_tree(design_system: &DesignSystem) -> Tree {
+    let mut families = Tree::new();
+    families.insert(
+        String::from("default"),
+        token(json!(design_system.fonts.default), None),
+    );
+    for font in &design_system.fonts.additionals {
+        families.insert(font.font_name.clone(), token(json!(font.value), None));
+    }
+    nest(&["font", "family"], families)
+}
+
+/// Weight, line height (ratio of the font size) & letter spacing of each typography scale
+fn font_scale_tree(design_system: &DesignSystem) -> Tree {
+    let mut weights = Tree::new();
+    let mut line_heights = Tree::new();
+    let mut letter_spacings = Tree::new();
+    for (scale_name, scale) in design_system.typography.scales() {
+        weights.insert(
+            String::from(scale_name),
+            token(json!(serde_name(&scale.font_weight)), None),
+        );
+        let font_size = rem_value(&scale.font_size).as_f64().unwrap_or(0.0);
+        let line_height = rem_value(&scale.line_height).as_f64().unwrap_or(0.0);
+        let ratio = if font_size != 0.0 {
+            (line_height / font_size * 1000.0).round() / 1000.0
+        } else {
+            1.0
+        };
+        line_heights.insert(
+            String::from(scale_name),
+            token(json!(ratio), Some(measurement_to_css(&scale.line_height))),
+        );
+        letter_spacings.insert(
+            String::from(scale_name),
+            token(json!(serde_name(&scale.letter_spacing)), None),
+        );
+    }
+
+    let mut font = Tree::new();
+    font.insert(String::from("weight"), StyleDictionaryNode::Group(weights));
+    font.insert(
+        String::from("line-height"),
+        StyleDictionaryNode::Group(line_heights),
+    );
+    font.insert(
+        String::from("letter-spacing"),
+        StyleDictionaryNode::Group(letter_spacings),
+    );
+    nest(&["font"], font)
+}
+
+fn shadow_tree(design_system: &DesignSystem) -> Tree {
+    let shadows: Tree = design_system
+        .shadows
+        .iter()
+        .map(|shadows| {
+            (
+                shadows.shadow_name.clone(),
+                token(json!(shadows_to_css(design_system, shadows)), None),
+            )
+        })
+        .collect();
+    nest(&["shadow"], shadows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn tokens(files: &[ExportFile], category: &str, name: &str) -> Value {
+        let path = PathBuf::from(EXPORT_STYLE_DICTIONARY_PATH)
+            .join(category)
+            .join(format!("{}.json", name));
+        let file = files.iter().find(|file| file.path == path).unwrap();
+        serde_json::from_slice(&file.content).unwrap()
+    }
+
+    #[test]
+    fn writes_one_file_per_category() {
+        let files = export_style_dictionary(&sample_design_system()).unwrap();
+        assert_eq!(files.len(), 10);
+        let palettes = tokens(&files, "color", "palettes");
+        let primary_50 = &palettes["color"]["palette"]["primary"]["50"];
+        assert_eq!(primary_50["value"], "#eff6ff");
+        assert!(primary_50["comment"]
+            .as_str()
+            .unwrap()
+            .starts_with("dark : #"));
+        assert_eq!(
+            tokens(&files, "color", "independant")["color"]["white"]["value"],
+            "#ffffff"
+        );
+        assert_eq!(
+            tokens(&files, "font", "family")["font"]["family"]["default"]["value"],
+            "Open Sans"
+        );
+        assert_eq!(
+            tokens(&files, "shadow", "shadow")["shadow"]["shadow"]["value"],
+            "0px 4px 4px 0px rgba(17, 24, 39, 0.25)"
+        );
+    }
+
+    #[test]
+    fn single_theme_has_no_palette_comment() {
+        let mut design_system = sample_design_system();
+        design_system.themes.other_themes.clear();
+        let files = export_style_dictionary(&design_system).unwrap();
+        let palettes = tokens(&files, "color", "palettes");
+        assert!(palettes["color"]["palette"]["primary"]["50"]
+            .get("comment")
+            .is_none());
+    }
+
+    #[test]
+    fn semantic_tokens_reference_the_palettes() {
+        let files = export_style_dictionary(&sample_design_system()).unwrap();
+        let semantic = &tokens(&files, "color", "semantic")["color"]["semantic"];
+        assert_eq!(
+            semantic["base"]["background"]["value"],
+            "{color.palette.gray.50.value}"
+        );
+        assert_eq!(semantic["base"]["background"]["comment"], "palette-gray-50");
+        assert_eq!(
+            semantic["button"]["default"]["text"]["value"],
+            "{color.white.value}"
+        );
+        assert_eq!(
+            token_reference(&sample_design_system(), "missing"),
+            "{missing.value}"
+        );
+    }
+
+    #[test]
+    fn theme_overrides_are_written_by_theme() {
+        let files = export_style_dictionary(&sample_design_system()).unwrap();
+        let dark = &tokens(&files, "color", "semantic-dark")["color"]["theme"]["dark"];
+        assert_eq!(
+            dark["base"]["background"]["value"],
+            "{color.palette.gray.900.value}"
+        );
+        // The tokens without override keep the main values
+        assert_eq!(
+            dark["base"]["text-light"]["value"],
+            "{color.palette.gray.500.value}"
+        );
+        assert_eq!(
+            dark["button"]["hover"]["background"]["value"],
+            "{color.palette.primary.50.value}"
+        );
+    }
+
+    #[test]
+    fn sizes_are_in_rem_with_the_css_value_in_comment() {
+        let files = export_style_dictionary(&sample_design_system()).unwrap();
+        let space = &tokens(&files, "size", "space")["size"]["space"];
+        assert_eq!(space["1"]["value"], 0.25);
+        assert_eq!(space["1"]["comment"], "4px");
+        assert_eq!(space["2"]["value"], 0.5);
+        let radius = &tokens(&files, "size", "radius")["size"]["radius"];
+        assert_eq!(radius["lg"]["value"], 0.75);
+        let scale = &tokens(&files, "font", "scale")["font"];
+        assert_eq!(scale["weight"]["h1"]["value"], "700");
+        assert_eq!(scale["line-height"]["h1"]["value"], 1.25);
+        assert_eq!(scale["line-height"]["h1"]["comment"], "40px");
+    }
+}
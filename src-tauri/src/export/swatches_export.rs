@@ -0,0 +1,272 @@
+use std::{fmt::Write, path::PathBuf};
+
+use anyhow::Result;
+use serde_json::json;
+
+use crate::{
+    domain::{color_domain::Rgb, design_system_domain::DesignSystem, export_domain::ExportFile},
+    repository::design_system_repository::EXPORT_SWATCHES_PATH,
+};
+
+const SWATCHES_FILE_NAME: &str = "palettes";
+const INDEPENDANT_COLORS_GROUP: &str = "Independant colors";
+
+const ASE_GROUP_START: u16 = 0xC001;
+const ASE_GROUP_END: u16 = 0xC002;
+const ASE_COLOR_ENTRY: u16 = 0x0001;
+/// Global color (the swatch updates every use of the color in Illustrator)
+const ASE_GLOBAL_COLOR: u16 = 0;
+
+/// Swatches of a group (palette or independant colors) : (swatch name, color)
+struct SwatchGroup {
+    name: String,
+    swatches: Vec<(String, Rgb)>,
+}
+
+/// Swatches of the palettes & independant colors : Adobe Swatch Exchange (`.ase`, a group per palette),
+/// GIMP / Inkscape palette (`.gpl`) and Sketch palette (`.sketchpalette`). The swatch formats have no
+/// alpha channel, the translucent colors are exported opaque
+pub fn export_swatches(design_system: &DesignSystem) -> Result<Vec<ExportFile>> {
+    let groups = swatch_groups(design_system);
+    Ok(vec![
+        swatches_file("ase", ase_swatches(&groups)),
+        swatches_file(
+            "gpl",
+            gpl_swatches(&design_system.metadata.design_system_name, &groups).into_bytes(),
+        ),
+        swatches_file(
+            "sketchpalette",
+            serde_json::to_string_pretty(&sketch_swatches(&groups))?.into_bytes(),
+        ),
+    ])
+}
+
+fn swatches_file(extension: &str, content: Vec<u8>) -> ExportFile {
+    ExportFile {
+        path: PathBuf::from(EXPORT_SWATCHES_PATH)
+            .join(SWATCHES_FILE_NAME)
+            .with_extension(extension),
+        content,
+    }
+}
+
+/// A group per palette (swatch name : palette-tint), then the independant colors
+fn swatch_groups(design_system: &DesignSystem) -> Vec<SwatchGroup> {
+    let mut groups: Vec<SwatchGroup> = design_system
+        .palettes
+        .iter()
+        .map(|palette| SwatchGroup {
+            name: palette.palette_name.clone(),
+            swatches: palette
+                .tints
+                .iter()
+                .filter_map(|tint| {
+                    Rgb::parse(&tint.color)
+                        .ok()
+                        .map(|color| (format!("{}-{}", palette.palette_name, tint.label), color))
+                })
+                .collect(),
+        })
+        .collect();
+
+    let independant_colors = &design_system.independant_colors;
+    let mut independant_swatches: Vec<(String, Rgb)> = vec![];
+    for (label, color) in std::iter::once(("white", &independant_colors.white)).chain(
+        independant_colors
+            .independant_colors
+            .iter()
+            .map(|tint| (tint.label.as_str(), &tint.color)),
+    ) {
+        if let Ok(color) = Rgb::parse(color) {
+            independant_swatches.push((String::from(label), color));
+        }
+    }
+    groups.push(SwatchGroup {
+        name: String::from(INDEPENDANT_COLORS_GROUP),
+        swatches: independant_swatches,
+    });
+
+    groups
+        .into_iter()
+        .filter(|group| !group.swatches.is_empty())
+        .collect()
+}
+
+/// Adobe Swatch Exchange 1.0 : big endian blocks, names in UTF-16 with a null terminator
+fn ase_swatches(groups: &[SwatchGroup]) -> Vec<u8> {
+    let mut blocks: Vec<u8> = vec![];
+    let mut block_count: u32 = 0;
+    let mut write_block = |blocks: &mut Vec<u8>, block_type: u16, body: &[u8]| {
+        blocks.extend_from_slice(&block_type.to_be_bytes());
+        blocks.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        blocks.extend_from_slice(body);
+        block_count += 1;
+    };
+
+    for group in groups {
+        write_block(&mut blocks, ASE_GROUP_START, &ase_name(&group.name));
+        for (name, color) in &group.swatches {
+            let mut body = ase_name(name);
+            body.extend_from_slice(b"RGB ");
+            let color = color.clamp();
+            for channel in [color.r, color.g, color.b] {
+                body.extend_from_slice(&(channel as f32).to_be_bytes());
+            }
+            body.extend_from_slice(&ASE_GLOBAL_COLOR.to_be_bytes());
+            write_block(&mut blocks, ASE_COLOR_ENTRY, &body);
+        }
+        write_block(&mut blocks, ASE_GROUP_END, &[]);
+    }
+
+    let mut ase: Vec<u8> = b"ASEF".to_vec();
+    ase.extend_from_slice(&1u16.to_be_bytes());
+    ase.extend_from_slice(&0u16.to_be_bytes());
+    ase.extend_from_slice(&block_count.to_be_bytes());
+    ase.extend(blocks);
+    ase
+}
+
+/// Length (UTF-16 code units with the null terminator) followed by the UTF-16 BE name
+fn ase_name(name: &str) -> Vec<u8> {
+    let mut units: Vec<u16> = name.encode_utf16().collect();
+    units.push(0);
+    let mut bytes: Vec<u8> = (units.len() as u16).to_be_bytes().to_vec();
+    for unit in units {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    bytes
+}
+
+/// GIMP palette, the groups are written as comments
+fn gpl_swatches(design_system_name: &str, groups: &[SwatchGroup]) -> String {
+    let columns = groups
+        .iter()
+        .map(|group| group.swatches.len())
+        .max()
+        .unwrap_or(0);
+    let mut gpl = format!(
+        "GIMP Palette\nName: {}\nColumns: {}\n#\n",
+        design_system_name, columns
+    );
+    for group in groups {
+        let _ = writeln!(gpl, "# {}", group.name);
+        for (name, color) in &group.swatches {
+            let [red, green, blue] = color.to_bytes();
+            let _ = writeln!(gpl, "{:>3} {:>3} {:>3}\t{}", red, green, blue, name);
+        }
+    }
+    gpl
+}
+
+/// Sketch Palettes plugin file (format 2.0), the swatches of every group in a single list
+fn sketch_swatches(groups: &[SwatchGroup]) -> serde_json::Value {
+    let colors: Vec<serde_json::Value> = groups
+        .iter()
+        .flat_map(|group| &group.swatches)
+        .map(|(name, color)| {
+            let color = color.clamp();
+            json!({
+                "name": name,
+                "red": color.r,
+                "green": color.g,
+                "blue": color.b,
+                "alpha": 1,
+            })
+        })
+        .collect();
+    json!({
+        "compatibleVersion": "2.0",
+        "pluginVersion": "2.22",
+        "colors": colors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    #[test]
+    fn groups_the_palettes_then_the_independant_colors() {
+        let mut design_system = sample_design_system();
+        design_system.palettes[1].tints[0].color = String::from("not a color");
+        let groups = swatch_groups(&design_system);
+        let names: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.name.as_str(),
+                    group
+                        .swatches
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("primary", vec!["primary-50", "primary-500", "primary-900"]),
+                ("gray", vec!["gray-500", "gray-900"]),
+                (INDEPENDANT_COLORS_GROUP, vec!["white", "success"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn ase_blocks_are_big_endian() {
+        let groups = swatch_groups(&sample_design_system());
+        let ase = ase_swatches(&groups);
+        assert_eq!(&ase[..8], b"ASEF\0\x01\0\0");
+        // 3 groups with their start & end blocks, 8 colors
+        assert_eq!(u32::from_be_bytes([ase[8], ase[9], ase[10], ase[11]]), 14);
+        assert_eq!(&ase[12..14], &ASE_GROUP_START.to_be_bytes());
+        let group_name = ase_name("primary");
+        assert_eq!(&group_name[..2], &[0, 8]);
+        assert_eq!(&ase[18..18 + group_name.len()], group_name.as_slice());
+        let color_entry = 18 + group_name.len();
+        assert_eq!(
+            &ase[color_entry..color_entry + 2],
+            &ASE_COLOR_ENTRY.to_be_bytes()
+        );
+        assert_eq!(&ase[ase.len() - 6..], &[0xC0, 0x02, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn gpl_and_sketch_palettes_list_every_swatch() {
+        let files = export_swatches(&sample_design_system()).unwrap();
+        let paths: Vec<PathBuf> = files.iter().map(|file| file.path.clone()).collect();
+        let swatches_path = PathBuf::from(EXPORT_SWATCHES_PATH).join(SWATCHES_FILE_NAME);
+        assert_eq!(
+            paths,
+            vec![
+                swatches_path.with_extension("ase"),
+                swatches_path.with_extension("gpl"),
+                swatches_path.with_extension("sketchpalette"),
+            ]
+        );
+
+        let gpl = String::from_utf8(files[1].content.clone()).unwrap();
+        assert!(gpl.starts_with("GIMP Palette\nName: Demo\nColumns: 3\n#\n# primary\n"));
+        assert!(gpl.contains(" 59 130 246\tprimary-500\n"));
+        assert!(gpl.contains("# Independant colors\n255 255 255\twhite\n"));
+
+        let sketch: serde_json::Value = serde_json::from_slice(&files[2].content).unwrap();
+        let colors = sketch["colors"].as_array().unwrap();
+        assert_eq!(colors.len(), 8);
+        assert_eq!(colors[7]["name"], "success");
+        assert_eq!(colors[6]["red"], 1.0);
+    }
+
+    #[test]
+    fn translucent_colors_are_exported_opaque() {
+        let mut design_system = sample_design_system();
+        design_system.independant_colors.white = String::from("#ffffff80");
+        let groups = swatch_groups(&design_system);
+        let sketch = sketch_swatches(&groups);
+        let white = &sketch["colors"][6];
+        assert_eq!(white["name"], "white");
+        assert_eq!(white["alpha"], 1);
+    }
+}
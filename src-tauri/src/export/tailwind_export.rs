@@ -0,0 +1,308 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::{
+    domain::{
+        design_system_domain::DesignSystem,
+        export_domain::{ExportFile, TailwindColor, TailwindFontSize, TailwindTheme},
+    },
+    repository::design_system_repository::{
+        EXPORT_TAILWIND_CONFIG_PATH, EXPORT_TAILWIND_THEME_PATH,
+    },
+};
+
+use super::{
+    find_color_token, measurement_to_css, semantic_color_overrides, semantic_color_tokens,
+    serde_name, shadows_to_css,
+};
+
+/// `tailwind.config.js` (Tailwind v3, theme.extend) and `tailwind-theme.css` (Tailwind v4 `@theme` block).
+/// The semantic colors overridden by a theme are grouped under `theme-{theme}` (`bg-theme-dark-base-background`)
+pub fn export_tailwind(design_system: &DesignSystem) -> Result<Vec<ExportFile>> {
+    let theme = tailwind_theme(design_system);
+    Ok(vec![
+        ExportFile::from_string(
+            EXPORT_TAILWIND_CONFIG_PATH,
+            tailwind_config(design_system, &theme)?,
+        ),
+        ExportFile::from_string(EXPORT_TAILWIND_THEME_PATH, tailwind_theme_block(&theme)),
+    ])
+}
+
+fn tailwind_theme(design_system: &DesignSystem) -> TailwindTheme {
+    let mut theme = TailwindTheme::default();
+
+    for palette in &design_system.palettes {
+        theme.colors.insert(
+            palette.palette_name.clone(),
+            TailwindColor::Shades(
+                palette
+                    .tints
+                    .iter()
+                    .map(|tint| (tint.label.clone(), tint.color.clone()))
+                    .collect(),
+            ),
+        );
+    }
+    theme.colors.insert(
+        String::from("white"),
+        TailwindColor::Color(design_system.independant_colors.white.clone()),
+    );
+    for tint in &design_system.independant_colors.independant_colors {
+        theme
+            .colors
+            .insert(tint.label.clone(), TailwindColor::Color(tint.color.clone()));
+    }
+    // Semantic colors are resolved, tailwind has no alias between colors
+    for (name, token_name) in semantic_color_tokens(&design_system.semantic_color_tokens) {
+        if let Some(color) = find_color_token(design_system, &token_name) {
+            theme.colors.insert(name, TailwindColor::Color(color));
+        }
+    }
+    for (theme_name, overridden_tokens) in semantic_color_overrides(design_system) {
+        theme.colors.insert(
+            format!("theme-{}", theme_name),
+            TailwindColor::Shades(
+                overridden_tokens
+                    .into_iter()
+                    .filter_map(|(name, token_name)| {
+                        find_color_token(design_system, &token_name).map(|color| (name, color))
+                    })
+                    .collect(),
+            ),
+        );
+    }
+
+    theme.spacing = design_system
+        .spaces
+        .iter()
+        .map(|space| {
+            (
+                space.space_key.clone(),
+                measurement_to_css(&space.space_value),
+            )
+        })
+        .collect();
+
+    theme.border_radius.insert(
+        String::from("DEFAULT"),
+        measurement_to_css(&design_system.radius.default),
+    );
+    for radius in &design_system.radius.additionals_radius {
+        theme.border_radius.insert(
+            radius.radius_key.clone(),
+            measurement_to_css(&radius.radius_value),
+        );
+    }
+
+    theme.box_shadow = design_system
+        .shadows
+        .iter()
+        .map(|shadows| {
+            (
+                shadows.shadow_name.clone(),
+                shadows_to_css(design_system, shadows),
+            )
+        })
+        .collect();
+
+    theme.font_family.insert(
+        String::from("default"),
+        font_stack(&design_system.fonts.default),
+    );
+    for font in &design_system.fonts.additionals {
+        theme
+            .font_family
+            .insert(font.font_name.clone(), font_stack(&font.value));
+    }
+
+    theme.font_size = design_system
+        .typography
+        .scales()
+        .into_iter()
+        .map(|(scale_name, scale)| {
+            (
+                String::from(scale_name),
+                (
+                    measurement_to_css(&scale.font_size),
+                    TailwindFontSize {
+                        line_height: measurement_to_css(&scale.line_height),
+                        font_weight: serde_name(&scale.font_weight),
+                        letter_spacing: serde_name(&scale.letter_spacing),
+                    },
+                ),
+            )
+        })
+        .collect();
+
+    theme
+}
+
+/// Font stack as a tailwind array, family names with spaces are quoted (`"Open Sans"`)
+fn font_stack(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+        .filter(|family| !family.is_empty())
+        .map(|family| {
+            if family.contains(' ') {
+                format!("\"{}\"", family)
+            } else {
+                family.to_string()
+            }
+        })
+        .collect()
+}
+
+fn tailwind_config(design_system: &DesignSystem, theme: &TailwindTheme) -> Result<String> {
+    let extend = serde_json::to_string_pretty(theme)?.replace('\n', "\n    ");
+    Ok(format!(
+        "// Tailwind theme of {}, generated by uitea
+/** @type {{import('tailwindcss').Config}} */
+module.exports = {{
+  theme: {{
+    extend: {},
+  }},
+}};
+",
+        design_system.metadata.design_system_name, extend
+    ))
+}
+
+/// Same theme with the Tailwind v4 namespaces (--color-*, --spacing-*, --radius-*, --shadow-*, --font-*, --text-*)
+fn tailwind_theme_block(theme: &TailwindTheme) -> String {
+    let mut variables: IndexMap<String, String> = IndexMap::new();
+    for (name, color) in &theme.colors {
+        match color {
+            TailwindColor::Color(color) => {
+                variables.insert(format!("color-{}", name), color.clone());
+            }
+            TailwindColor::Shades(shades) => {
+                for (label, color) in shades {
+                    variables.insert(format!("color-{}-{}", name, label), color.clone());
+                }
+            }
+        }
+    }
+    for (key, value) in &theme.spacing {
+        variables.insert(format!("spacing-{}", key), value.clone());
+    }
+    for (key, value) in &theme.border_radius {
+        let name = if key == "DEFAULT" {
+            String::from("radius")
+        } else {
+            format!("radius-{}", key)
+        };
+        variables.insert(name, value.clone());
+    }
+    for (name, value) in &theme.box_shadow {
+        variables.insert(format!("shadow-{}", name), value.clone());
+    }
+    for (name, families) in &theme.font_family {
+        variables.insert(format!("font-{}", name), families.join(", "));
+    }
+    for (name, (font_size, properties)) in &theme.font_size {
+        variables.insert(format!("text-{}", name), font_size.clone());
+        variables.insert(
+            format!("text-{}--line-height", name),
+            properties.line_height.clone(),
+        );
+        variables.insert(
+            format!("text-{}--font-weight", name),
+            properties.font_weight.clone(),
+        );
+        variables.insert(
+            format!("text-{}--letter-spacing", name),
+            properties.letter_spacing.clone(),
+        );
+    }
+
+    let mut stylesheet = String::from("@theme {\n");
+    for (name, value) in variables {
+        let _ = writeln!(stylesheet, "  --{}: {};", name, value);
+    }
+    stylesheet.push_str("}\n");
+    stylesheet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    #[test]
+    fn theme_resolves_the_semantic_colors() {
+        let theme = tailwind_theme(&sample_design_system());
+        match &theme.colors["primary"] {
+            TailwindColor::Shades(shades) => assert_eq!(shades["500"], "#3b82f6"),
+            TailwindColor::Color(_) => panic!("palettes are shades"),
+        }
+        match &theme.colors["button-hover-background"] {
+            TailwindColor::Color(color) => assert_eq!(color, "#1e3a8a"),
+            TailwindColor::Shades(_) => panic!("semantic tokens are colors"),
+        }
+        assert_eq!(theme.spacing["2"], "0.5rem");
+        assert_eq!(theme.border_radius["DEFAULT"], "4px");
+        assert_eq!(theme.border_radius["lg"], "12px");
+        let (font_size, properties) = &theme.font_size["h1"];
+        assert_eq!(font_size, "32px");
+        assert_eq!(properties.line_height, "40px");
+        assert_eq!(properties.font_weight, "700");
+    }
+
+    #[test]
+    fn font_stacks_quote_the_names_with_spaces() {
+        assert_eq!(font_stack("Open Sans"), vec!["\"Open Sans\""]);
+        assert_eq!(
+            font_stack("'JetBrains Mono', monospace,"),
+            vec!["\"JetBrains Mono\"", "monospace"]
+        );
+    }
+
+    #[test]
+    fn writes_the_v3_config_and_the_v4_theme() {
+        let files = export_tailwind(&sample_design_system()).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path.to_str(), Some(EXPORT_TAILWIND_CONFIG_PATH));
+        let config = String::from_utf8(files[0].content.clone()).unwrap();
+        assert!(config.starts_with("// Tailwind theme of Demo, generated by uitea\n"));
+        assert!(config.contains("module.exports = {\n  theme: {\n    extend: {\n"));
+        assert!(config.contains("        \"base-background\": \"#f9fafb\",\n"));
+
+        assert_eq!(files[1].path.to_str(), Some(EXPORT_TAILWIND_THEME_PATH));
+        let theme = String::from_utf8(files[1].content.clone()).unwrap();
+        assert!(theme.starts_with("@theme {\n  --color-primary-50: #eff6ff;\n"));
+        for declaration in [
+            "  --color-button-text: #ffffff;\n",
+            "  --spacing-1: 4px;\n",
+            "  --radius: 4px;\n  --radius-lg: 12px;\n",
+            "  --font-mono: \"JetBrains Mono\", monospace;\n",
+            "  --text-h1: 32px;\n  --text-h1--line-height: 40px;\n",
+        ] {
+            assert!(theme.contains(declaration), "{}", declaration);
+        }
+    }
+
+    #[test]
+    fn theme_overrides_are_grouped_by_theme() {
+        let theme = tailwind_theme(&sample_design_system());
+        match &theme.colors["theme-dark"] {
+            TailwindColor::Shades(colors) => {
+                assert_eq!(
+                    colors.keys().collect::<Vec<&String>>(),
+                    vec![
+                        "base-background",
+                        "base-text-default",
+                        "button-hover-background"
+                    ]
+                );
+                assert_eq!(colors["base-background"], "#111827");
+            }
+            TailwindColor::Color(_) => panic!("theme overrides are grouped"),
+        }
+        let block = tailwind_theme_block(&theme);
+        assert!(block.contains("  --color-theme-dark-base-background: #111827;\n"));
+    }
+}
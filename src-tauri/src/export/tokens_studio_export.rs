@@ -0,0 +1,105 @@
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::{
+    domain::{
+        design_system_domain::{DesignSystem, Palette},
+        export_domain::{
+            ExportFile, TokensStudioFile, TokensStudioMetadata, TokensStudioSet, TokensStudioToken,
+        },
+    },
+    repository::design_system_repository::EXPORT_FIGMA_PATH,
+};
+
+use super::{palette_token_name, recolor_themes};
+
+const DEFAULT_TOKEN_SET_NAME: &str = "main-palettes";
+
+pub fn export_tokens_studio(design_system: &DesignSystem) -> Result<ExportFile> {
+    let main_theme_name: String = design_system
+        .themes
+        .main_theme
+        .as_ref()
+        .map(|theme| theme.name.clone())
+        .unwrap_or(String::from(DEFAULT_TOKEN_SET_NAME));
+
+    let mut token_sets: IndexMap<String, TokensStudioSet> = IndexMap::new();
+    token_sets.insert(
+        main_theme_name,
+        palettes_to_token_set(&design_system.palettes),
+    );
+    for (theme_name, recolor) in recolor_themes(design_system) {
+        token_sets.insert(theme_name, palettes_to_token_set(&recolor.palettes));
+    }
+
+    let tokens_file = TokensStudioFile {
+        token_sets,
+        metadata: TokensStudioMetadata {
+            token_set_order: vec![],
+        },
+    };
+    Ok(ExportFile::from_string(
+        EXPORT_FIGMA_PATH,
+        serde_json::to_string_pretty(&tokens_file)?,
+    ))
+}
+
+pub fn palettes_to_token_set(palettes: &[Palette]) -> TokensStudioSet {
+    palettes
+        .iter()
+        .map(|palette| {
+            let group: IndexMap<String, TokensStudioToken> = palette
+                .tints
+                .iter()
+                .map(|tint| {
+                    (
+                        palette_token_name(palette, tint),
+                        TokensStudioToken {
+                            token_type: String::from("color"),
+                            value: serde_json::Value::String(tint.color.clone()),
+                        },
+                    )
+                })
+                .collect();
+            (palette.palette_name.clone(), group)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    fn tokens_file(design_system: &DesignSystem) -> TokensStudioFile {
+        let file = export_tokens_studio(design_system).unwrap();
+        assert_eq!(file.path.to_str(), Some(EXPORT_FIGMA_PATH));
+        serde_json::from_slice(&file.content).unwrap()
+    }
+
+    #[test]
+    fn a_token_set_per_theme() {
+        let tokens_file = tokens_file(&sample_design_system());
+        let set_names: Vec<&str> = tokens_file.token_sets.keys().map(String::as_str).collect();
+        assert_eq!(set_names, vec!["light", "dark"]);
+
+        let light = &tokens_file.token_sets["light"];
+        let token = &light["primary"]["palette-primary-500"];
+        assert_eq!(token.token_type, "color");
+        assert_eq!(token.value, "#3b82f6");
+        assert_eq!(light["gray"].len(), 3);
+
+        let dark = &tokens_file.token_sets["dark"];
+        assert_ne!(dark["primary"]["palette-primary-50"].value, "#eff6ff");
+    }
+
+    #[test]
+    fn without_theme_the_set_has_the_default_name() {
+        let mut design_system = sample_design_system();
+        design_system.themes.main_theme = None;
+        design_system.themes.other_themes.clear();
+        let tokens_file = tokens_file(&design_system);
+        let set_names: Vec<&str> = tokens_file.token_sets.keys().map(String::as_str).collect();
+        assert_eq!(set_names, vec![DEFAULT_TOKEN_SET_NAME]);
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow_tauri::{IntoTAResult, TAResult};
 use tauri::State;
 
@@ -19,6 +21,14 @@ pub fn save_color_picker_store(
     color_picker_application::save_color_picker_store(&state, &color_store).into_ta_result()
 }
 
+#[tauri::command]
+pub fn import_swatches_into_color_picker(
+    state: State<AppState>,
+    path: PathBuf,
+) -> TAResult<ColorPickerStore> {
+    color_picker_application::import_swatches_into_color_picker(&state, &path).into_ta_result()
+}
+
 #[tauri::command]
 pub fn undo_color_picker(state: State<AppState>) -> TAResult<ColorPickerStore> {
     color_picker_application::undo_color_picker(state).into_ta_result()
@@ -5,8 +5,19 @@ use tauri::{AppHandle, State};
 
 use crate::{
     application::design_system_application,
-    domain::design_system_domain::{
-        DesignSystem, DesignSystemCreationPayload, DesignSystemMetadata, ExportPayload,
+    domain::{
+        audit_domain::ContrastAudit,
+        changelog_domain::Changelog,
+        color_vision_domain::ColorVisionSimulation,
+        design_system_domain::{
+            DesignSystem, DesignSystemCreationPayload, DesignSystemMetadata, ExportPayload,
+            PaletteRenamePayload, PaletteRenameReport,
+        },
+        diff_domain::DesignSystemDiff,
+        export_domain::ExportFormat,
+        import_domain::{DesignSystemImportPayload, ImportReport},
+        lint_domain::LintReport,
+        version_domain::DesignSystemVersion,
     },
     AppState,
 };
@@ -18,6 +29,11 @@ pub fn create_design_system(
     design_system_application::create_design_system(payload).into_ta_result()
 }
 
+#[tauri::command]
+pub fn import_design_system(payload: DesignSystemImportPayload) -> TAResult<ImportReport> {
+    design_system_application::import_design_system(payload).into_ta_result()
+}
+
 #[tauri::command]
 pub fn find_design_system(
     app: AppHandle,
@@ -61,6 +77,104 @@ pub fn register_export(payload: ExportPayload) -> TAResult<()> {
     design_system_application::register_export(payload).into_ta_result()
 }
 
+#[tauri::command]
+pub fn generate_export(
+    design_system_path: PathBuf,
+    format: ExportFormat,
+) -> TAResult<Vec<PathBuf>> {
+    design_system_application::generate_export(&design_system_path, &format, None, true)
+        .into_ta_result()
+}
+
+#[tauri::command]
+pub fn rename_palette(
+    app: AppHandle,
+    state: State<AppState>,
+    payload: PaletteRenamePayload,
+) -> TAResult<PaletteRenameReport> {
+    design_system_application::rename_palette(app, &state, payload).into_ta_result()
+}
+
+#[tauri::command]
+pub fn audit_contrast(design_system_path: PathBuf) -> TAResult<ContrastAudit> {
+    design_system_application::audit_contrast(design_system_path).into_ta_result()
+}
+
+#[tauri::command]
+pub fn simulate_color_vision(design_system: DesignSystem) -> TAResult<Vec<ColorVisionSimulation>> {
+    design_system_application::simulate_color_vision(&design_system).into_ta_result()
+}
+
+#[tauri::command]
+pub fn lint_design_system(design_system_path: PathBuf) -> TAResult<LintReport> {
+    design_system_application::lint_design_system(design_system_path).into_ta_result()
+}
+
+#[tauri::command]
+pub fn create_design_system_version(
+    design_system_path: PathBuf,
+    version_name: String,
+    description: Option<String>,
+) -> TAResult<DesignSystemVersion> {
+    design_system_application::create_design_system_version(
+        design_system_path,
+        &version_name,
+        description,
+    )
+    .into_ta_result()
+}
+
+#[tauri::command]
+pub fn fetch_design_system_versions(
+    design_system_path: PathBuf,
+) -> TAResult<Vec<DesignSystemVersion>> {
+    design_system_application::fetch_design_system_versions(design_system_path).into_ta_result()
+}
+
+#[tauri::command]
+pub fn diff_design_system_versions(
+    design_system_path: PathBuf,
+    from_version: String,
+    to_version: Option<String>,
+) -> TAResult<DesignSystemDiff> {
+    design_system_application::diff_design_system_versions(
+        design_system_path,
+        &from_version,
+        to_version.as_deref(),
+    )
+    .into_ta_result()
+}
+
+#[tauri::command]
+pub fn diff_design_systems(
+    previous_design_system_path: PathBuf,
+    design_system_path: PathBuf,
+) -> TAResult<DesignSystemDiff> {
+    design_system_application::diff_design_systems(previous_design_system_path, design_system_path)
+        .into_ta_result()
+}
+
+#[tauri::command]
+pub fn generate_design_system_changelog(design_system_path: PathBuf) -> TAResult<Changelog> {
+    design_system_application::generate_design_system_changelog(design_system_path).into_ta_result()
+}
+
+#[tauri::command]
+pub fn restore_design_system_version(
+    app: AppHandle,
+    state: State<AppState>,
+    design_system_path: String,
+    version_name: String,
+) -> TAResult<DesignSystem> {
+    design_system_application::restore_design_system_version(
+        app,
+        &state,
+        &design_system_path,
+        &version_name,
+    )
+    .into_ta_result()
+}
+
 #[tauri::command]
 pub fn save_readme(metadata: DesignSystemMetadata) -> TAResult<()> {
     design_system_application::save_readme(metadata).into_ta_result()
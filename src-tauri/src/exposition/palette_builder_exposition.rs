@@ -26,6 +26,22 @@ pub fn load_palette_builder(state: State<AppState>, path: PathBuf) -> TAResult<P
     palette_builder_application::load_palette_builder(state, &path).into_ta_result()
 }
 
+#[tauri::command]
+pub fn import_swatches_into_palette_builder(
+    palette_builder: PaletteBuilderFile,
+    path: PathBuf,
+) -> TAResult<PaletteBuilderFile> {
+    palette_builder_application::import_swatches_into_palette_builder(palette_builder, &path)
+        .into_ta_result()
+}
+
+#[tauri::command]
+pub fn regenerate_palette_builder(
+    palette_builder: PaletteBuilderFile,
+) -> TAResult<PaletteBuilderFile> {
+    palette_builder_application::regenerate_palette_builder(palette_builder).into_ta_result()
+}
+
 #[tauri::command]
 pub fn save_palette_builder_into_design_system(
     design_system_path: PathBuf,
@@ -59,22 +75,32 @@ pub fn rename_palette_builder(payload: PaletteBuilderRenamePayload) -> TAResult<
 #[tauri::command]
 pub fn do_palette_builder(
     state: State<AppState>,
+    path: Option<PathBuf>,
     palette_builder: PaletteBuilderUndoRedo,
 ) -> TAResult<()> {
-    palette_builder_application::do_palette_builder(state, palette_builder).into_ta_result()
+    palette_builder_application::do_palette_builder(state, path, palette_builder).into_ta_result()
 }
 
 #[tauri::command]
-pub fn undo_palette_builder(state: State<AppState>) -> TAResult<PaletteBuilderUndoRedo> {
-    palette_builder_application::undo_palette_builder(state).into_ta_result()
+pub fn undo_palette_builder(
+    state: State<AppState>,
+    path: Option<PathBuf>,
+) -> TAResult<PaletteBuilderUndoRedo> {
+    palette_builder_application::undo_palette_builder(state, path).into_ta_result()
 }
 
 #[tauri::command]
-pub fn redo_palette_builder(state: State<AppState>) -> TAResult<PaletteBuilderUndoRedo> {
-    palette_builder_application::redo_palette_builder(state).into_ta_result()
+pub fn redo_palette_builder(
+    state: State<AppState>,
+    path: Option<PathBuf>,
+) -> TAResult<PaletteBuilderUndoRedo> {
+    palette_builder_application::redo_palette_builder(state, path).into_ta_result()
 }
 
 #[tauri::command]
-pub fn can_undo_redo_palette_builder(state: State<AppState>) -> TAResult<UndoRedoActions> {
-    palette_builder_application::can_undo_redo_palette_builder(state).into_ta_result()
+pub fn can_undo_redo_palette_builder(
+    state: State<AppState>,
+    path: Option<PathBuf>,
+) -> TAResult<UndoRedoActions> {
+    palette_builder_application::can_undo_redo_palette_builder(state, path).into_ta_result()
 }
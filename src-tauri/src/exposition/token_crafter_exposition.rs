@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow_tauri::{IntoTAResult, TAResult};
 use tauri::State;
 
@@ -10,22 +12,34 @@ use crate::{
 #[tauri::command]
 pub fn do_token_crafter(
     state: State<AppState>,
+    design_system_path: Option<PathBuf>,
     token_crafter: ColorCombinationCollection,
 ) -> TAResult<()> {
-    token_crafter_application::do_token_crafter(&state, &token_crafter).into_ta_result()
+    token_crafter_application::do_token_crafter(&state, design_system_path, &token_crafter)
+        .into_ta_result()
 }
 
 #[tauri::command]
-pub fn undo_token_crafter(state: State<AppState>) -> TAResult<ColorCombinationCollection> {
-    token_crafter_application::undo_token_crafter(state).into_ta_result()
+pub fn undo_token_crafter(
+    state: State<AppState>,
+    design_system_path: Option<PathBuf>,
+) -> TAResult<ColorCombinationCollection> {
+    token_crafter_application::undo_token_crafter(state, design_system_path).into_ta_result()
 }
 
 #[tauri::command]
-pub fn redo_token_crafter(state: State<AppState>) -> TAResult<ColorCombinationCollection> {
-    token_crafter_application::redo_token_crafter(state).into_ta_result()
+pub fn redo_token_crafter(
+    state: State<AppState>,
+    design_system_path: Option<PathBuf>,
+) -> TAResult<ColorCombinationCollection> {
+    token_crafter_application::redo_token_crafter(state, design_system_path).into_ta_result()
 }
 
 #[tauri::command]
-pub fn can_undo_redo_token_crafter(state: State<AppState>) -> TAResult<UndoRedoActions> {
-    token_crafter_application::can_undo_redo_token_crafter(state).into_ta_result()
+pub fn can_undo_redo_token_crafter(
+    state: State<AppState>,
+    design_system_path: Option<PathBuf>,
+) -> TAResult<UndoRedoActions> {
+    token_crafter_application::can_undo_redo_token_crafter(state, design_system_path)
+        .into_ta_result()
 }
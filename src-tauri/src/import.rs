@@ -0,0 +1,351 @@
+use std::mem;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::domain::{
+    color_domain::Rgb,
+    design_system_domain::{
+        AdditionalFont, ColorCombination, ColorCombinationCollection, CustomTypographyScale,
+        DesignSystem, Measurement, Palette, RadiusItem, SemanticColorTokens, Shadow, Shadows,
+        Space, ThemeSemanticColorTokens, Tint, TypographyScale, UnitOfMeasurement,
+    },
+    import_domain::ImportFormat,
+};
+
+pub mod css_import;
+pub mod dtcg_import;
+pub mod swatches_import;
+pub mod tokens_studio_import;
+
+/// Fill a design system (created with the default values) from the content of a tokens file.
+/// Return the tokens of the file which can't be mapped.
+pub fn import_design_system(
+    design_system: &mut DesignSystem,
+    format: &ImportFormat,
+    content: &str,
+) -> Result<Vec<String>> {
+    match format {
+        ImportFormat::Dtcg => dtcg_import::import_dtcg(design_system, content),
+        ImportFormat::Css => css_import::import_css(design_system, content),
+        ImportFormat::TokensStudio => {
+            tokens_studio_import::import_tokens_studio(design_system, content)
+        }
+    }
+}
+
+/// Parse a css length in px or rem (`4px`, `0.5rem`, `4` is read as px)
+pub fn parse_measurement(value: &str) -> Option<Measurement> {
+    let value = value.trim().to_lowercase();
+    let (number, unit) = if let Some(number) = value.strip_suffix("px") {
+        (number, UnitOfMeasurement::PX)
+    } else if let Some(number) = value.strip_suffix("rem") {
+        (number, UnitOfMeasurement::REM)
+    } else {
+        (value.as_str(), UnitOfMeasurement::PX)
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|value| Measurement { unit, value })
+}
+
+/// Normalize a css color to the hex notation used by the palettes
+pub fn parse_color(value: &str) -> Option<String> {
+    Rgb::parse(value).ok().map(|color| color.to_hex())
+}
+
+const COLOR_PREFIXES: [&str; 3] = ["palette-", "colors-", "color-"];
+const BASE_PROPERTIES: [&str; 5] = [
+    "background",
+    "text-light",
+    "text-default",
+    "text-dark",
+    "border",
+];
+
+/// Semantic token named like the uitea stylesheet
+enum SemanticName<'a> {
+    /// `base-<property>`
+    Base(&'a str),
+    /// `<combination>[-hover|-focus|-active]-<background|text|border>`
+    Combination {
+        name: &'a str,
+        state: &'a str,
+        property: &'a str,
+    },
+}
+
+fn semantic_name(name: &str) -> Option<SemanticName<'_>> {
+    if let Some(property) = name.strip_prefix("base-") {
+        if BASE_PROPERTIES.contains(&property) {
+            return Some(SemanticName::Base(property));
+        }
+    }
+    let (rest, property) = name.rsplit_once('-')?;
+    if !["background", "text", "border"].contains(&property) {
+        return None;
+    }
+    let (combination_name, state) = match rest.rsplit_once('-') {
+        Some((combination_name, state)) if ["hover", "focus", "active"].contains(&state) => {
+            (combination_name, state)
+        }
+        _ => (rest, "default"),
+    };
+    if combination_name.is_empty() {
+        None
+    } else {
+        Some(SemanticName::Combination {
+            name: combination_name,
+            state,
+            property,
+        })
+    }
+}
+
+fn strip_prefixes<'a>(name: &'a str, prefixes: &[&str]) -> &'a str {
+    prefixes
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name)
+}
+
+/// Tokens read from an imported file, applied on the design system once the whole file is read
+#[derive(Default)]
+struct ImportedTokens {
+    palettes: IndexMap<String, Vec<Tint>>,
+    white: Option<String>,
+    independant_colors: Vec<Tint>,
+    semantic_base: IndexMap<String, String>,
+    combinations: IndexMap<String, ColorCombinationCollection>,
+    spaces: Vec<Space>,
+    default_radius: Option<Measurement>,
+    additionals_radius: Vec<RadiusItem>,
+    default_font: Option<String>,
+    additional_fonts: Vec<AdditionalFont>,
+    shadows: Vec<Shadows>,
+    typography: Vec<(String, TypographyScale)>,
+    theme_overrides: IndexMap<String, ThemeSemanticColorTokens>,
+}
+
+impl ImportedTokens {
+    fn add_palette_tint(&mut self, palette: &str, tint: &str, color: String) {
+        self.palettes
+            .entry(String::from(palette))
+            .or_default()
+            .push(Tint {
+                label: String::from(tint),
+                color,
+            });
+    }
+
+    /// Add a color from its token name and return its uitea token name : palette tint when the
+    /// name ends with a number (`primary-500`, `palette-primary-500`), independant color otherwise
+    fn add_color(&mut self, name: &str, color: String) -> String {
+        let name = strip_prefixes(name, &COLOR_PREFIXES);
+        match name.rsplit_once('-') {
+            Some((palette, tint))
+                if !palette.is_empty()
+                    && !tint.is_empty()
+                    && tint.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                self.add_palette_tint(palette, tint, color);
+                format!("palette-{}-{}", palette, tint)
+            }
+            _ => {
+                self.add_independant_color(name, color);
+                format!("color-{}", name)
+            }
+        }
+    }
+
+    /// `white` replaces the white of the design system
+    fn add_independant_color(&mut self, label: &str, color: String) {
+        if label == "white" {
+            self.white = Some(color);
+        } else {
+            self.independant_colors.push(Tint {
+                label: String::from(label),
+                color,
+            });
+        }
+    }
+
+    /// Base semantic token (background, text-light, text-default, text-dark, border)
+    fn add_semantic_base(&mut self, property: &str, token_name: String) {
+        self.semantic_base
+            .insert(String::from(property), token_name);
+    }
+
+    /// Property (background, text, border) of a combination state (default, hover, focus, active)
+    fn add_combination(
+        &mut self,
+        name: &str,
+        state: &str,
+        property: &str,
+        token_name: String,
+    ) -> bool {
+        let collection = self
+            .combinations
+            .entry(String::from(name))
+            .or_insert_with(|| ColorCombinationCollection {
+                combination_name: Some(String::from(name)),
+                default: None,
+                hover: None,
+                active: None,
+                focus: None,
+                group: None,
+                default_combination: None,
+            });
+        let combination = match state {
+            "default" => &mut collection.default,
+            "hover" => &mut collection.hover,
+            "focus" => &mut collection.focus,
+            "active" => &mut collection.active,
+            _ => return false,
+        }
+        .get_or_insert(ColorCombination {
+            background: None,
+            border: None,
+            text: None,
+        });
+        match property {
+            "background" => combination.background = Some(token_name),
+            "text" => combination.text = Some(token_name),
+            "border" => combination.border = Some(token_name),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Semantic token from its name (see `SemanticName`)
+    fn add_semantic(&mut self, name: &str, token_name: String) -> bool {
+        match semantic_name(name) {
+            Some(SemanticName::Base(property)) => {
+                self.add_semantic_base(property, token_name);
+                true
+            }
+            Some(SemanticName::Combination {
+                name,
+                state,
+                property,
+            }) => self.add_combination(name, state, property, token_name),
+            None => false,
+        }
+    }
+
+    fn add_space(&mut self, key: &str, space_value: Measurement) {
+        self.spaces.push(Space {
+            space_key: String::from(key),
+            space_value,
+        });
+    }
+
+    /// `default` replaces the default radius
+    fn add_radius(&mut self, key: &str, radius_value: Measurement) {
+        if key == "default" {
+            self.default_radius = Some(radius_value);
+        } else {
+            self.additionals_radius.push(RadiusItem {
+                radius_key: String::from(key),
+                radius_value,
+            });
+        }
+    }
+
+    /// `default` replaces the default font
+    fn add_font(&mut self, name: &str, font: String) {
+        if name == "default" {
+            self.default_font = Some(font);
+        } else {
+            self.additional_fonts.push(AdditionalFont {
+                font_name: String::from(name),
+                value: font,
+            });
+        }
+    }
+
+    fn add_shadows(&mut self, name: &str, shadows_array: Vec<Shadow>) {
+        self.shadows.push(Shadows {
+            shadow_name: String::from(name),
+            shadows_array,
+        });
+    }
+
+    fn add_typography(&mut self, name: &str, scale: TypographyScale) {
+        self.typography.push((String::from(name), scale));
+    }
+
+    /// Replace each part of the design system present in the file
+    fn apply(self, design_system: &mut DesignSystem) {
+        if !self.palettes.is_empty() {
+            design_system.palettes = self
+                .palettes
+                .into_iter()
+                .map(|(palette_name, tints)| Palette {
+                    palette_name,
+                    palette_path: None,
+                    tints,
+                })
+                .collect();
+        }
+
+        if let Some(white) = self.white {
+            design_system.independant_colors.white = white;
+        }
+        if !self.independant_colors.is_empty() {
+            design_system.independant_colors.independant_colors = self.independant_colors;
+        }
+
+        if !self.semantic_base.is_empty() || !self.combinations.is_empty() {
+            let mut semantic_base = self.semantic_base;
+            design_system.semantic_color_tokens = SemanticColorTokens {
+                background: semantic_base.remove("background"),
+                border: semantic_base.remove("border"),
+                text_light: semantic_base.remove("text-light"),
+                text_default: semantic_base.remove("text-default"),
+                text_dark: semantic_base.remove("text-dark"),
+                color_combination_collections: self.combinations.into_values().collect(),
+                theme_overrides: mem::take(
+                    &mut design_system.semantic_color_tokens.theme_overrides,
+                ),
+            };
+        }
+        if !self.theme_overrides.is_empty() {
+            design_system.semantic_color_tokens.theme_overrides = self.theme_overrides;
+        }
+
+        if !self.spaces.is_empty() {
+            design_system.spaces = self.spaces;
+        }
+
+        if let Some(default_radius) = self.default_radius {
+            design_system.radius.default = default_radius;
+        }
+        if !self.additionals_radius.is_empty() {
+            design_system.radius.additionals_radius = self.additionals_radius;
+        }
+
+        if let Some(default_font) = self.default_font {
+            design_system.fonts.default = default_font;
+        }
+        if !self.additional_fonts.is_empty() {
+            design_system.fonts.additionals = self.additional_fonts;
+        }
+
+        if !self.shadows.is_empty() {
+            design_system.shadows = self.shadows;
+        }
+
+        for (scale_name, scale) in self.typography {
+            match design_system.typography.scale_mut(&scale_name) {
+                Some(existing_scale) => *existing_scale = scale,
+                None => design_system
+                    .typography
+                    .custom_scales
+                    .push(CustomTypographyScale { scale_name, scale }),
+            }
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+
+use crate::domain::design_system_domain::DesignSystem;
+
+use super::{parse_color, parse_measurement, semantic_name, strip_prefixes, ImportedTokens};
+
+/// Max depth when following var() aliases (--a: var(--b) -> --b: var(--c) -> value)
+const MAX_ALIAS_DEPTH: usize = 10;
+const SPACE_PREFIXES: [&str; 3] = ["spacing-", "spaces-", "space-"];
+
+/// Import a stylesheet of css custom properties (`--name: value`). Colors (hex, rgb(), hsl()) become
+/// palette tints when their name ends with a number (`--primary-500`), independant colors otherwise.
+/// px / rem lengths become radius or spaces from their name, and `var()` references with the names
+/// of the uitea stylesheet (`--base-background`, `--button-hover-text`) become semantic colors.
+pub fn import_css(design_system: &mut DesignSystem, content: &str) -> Result<Vec<String>> {
+    let properties = custom_properties(content);
+    if properties.is_empty() {
+        return Err(anyhow!("No css custom property found"));
+    }
+
+    let mut imported = ImportedTokens::default();
+    let mut unmapped_tokens: Vec<String> = vec![];
+    // css variable name -> uitea token name, to map the semantic references
+    let mut color_tokens: HashMap<&str, String> = HashMap::new();
+    let mut references: Vec<(&str, &str)> = vec![];
+
+    for (name, value) in &properties {
+        if let Some(reference) = var_reference(value) {
+            if semantic_name(name).is_some() {
+                references.push((name.as_str(), reference));
+                continue;
+            }
+        }
+        let value = resolve_value(&properties, value);
+        if let Some(color) = parse_color(&value) {
+            color_tokens.insert(name, imported.add_color(name, color));
+        } else if !imported.import_value(name, &value) {
+            unmapped_tokens.push(format!("--{}", name));
+        }
+    }
+
+    for (name, reference) in references {
+        let mapped = match color_tokens.get(reference) {
+            Some(token_name) => imported.add_semantic(name, token_name.clone()),
+            None => false,
+        };
+        if !mapped {
+            unmapped_tokens.push(format!("--{}", name));
+        }
+    }
+
+    imported.apply(design_system);
+    Ok(unmapped_tokens)
+}
+
+/// Custom properties of the stylesheet (the first declaration of a name is kept, so the `:root`
+/// values win over the `[data-theme]` overrides of the uitea stylesheet)
+fn custom_properties(content: &str) -> IndexMap<String, String> {
+    let mut properties: IndexMap<String, String> = IndexMap::new();
+    for declaration in strip_comments(content).split([';', '{', '}']) {
+        let declaration = match declaration.trim().strip_prefix("--") {
+            Some(declaration) => declaration,
+            None => continue,
+        };
+        if let Some((name, value)) = declaration.split_once(':') {
+            let value = value.trim().trim_end_matches("!important").trim();
+            if !name.trim().is_empty() && !value.is_empty() {
+                properties
+                    .entry(name.trim().to_string())
+                    .or_insert_with(|| value.to_string());
+            }
+        }
+    }
+    properties
+}
+
+fn strip_comments(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Referenced variable of `var(--name)` or `var(--name, fallback)`
+fn var_reference(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("var(")?
+        .strip_suffix(')')?
+        .split(',')
+        .next()?
+        .trim()
+        .strip_prefix("--")
+}
+
+/// Value of a property, following the var() aliases (with their fallback when the variable is missing)
+fn resolve_value(properties: &IndexMap<String, String>, value: &str) -> String {
+    let mut value = value.to_string();
+    for _ in 0..MAX_ALIAS_DEPTH {
+        let arguments = match value
+            .strip_prefix("var(")
+            .and_then(|value| value.strip_suffix(')'))
+        {
+            Some(arguments) => arguments,
+            None => break,
+        };
+        let (name, fallback) = match arguments.split_once(',') {
+            Some((name, fallback)) => (name.trim(), Some(fallback.trim())),
+            None => (arguments.trim(), None),
+        };
+        value = match (
+            name.strip_prefix("--")
+                .and_then(|name| properties.get(name)),
+            fallback,
+        ) {
+            (Some(referenced), _) => referenced.clone(),
+            (None, Some(fallback)) => fallback.to_string(),
+            (None, None) => break,
+        };
+    }
+    value
+}
+
+/// Length with an explicit px / rem unit (or 0), unitless numbers are line heights or weights
+fn parse_length(value: &str) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    if value.ends_with("px") || value.ends_with("rem") || value.parse::<f64>() == Ok(0.0) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Unquoted single family, font stacks are kept as is
+fn font_value(value: &str) -> String {
+    if value.contains(',') {
+        value.to_string()
+    } else {
+        value.trim_matches(|c| c == '"' || c == '\'').to_string()
+    }
+}
+
+impl ImportedTokens {
+    /// Radius, space or font from the name of the property
+    fn import_value(&mut self, name: &str, value: &str) -> bool {
+        if let Some(length) = parse_length(value) {
+            let measurement = match parse_measurement(&length) {
+                Some(measurement) => measurement,
+                None => return false,
+            };
+            if name.contains("radius") {
+                let key = name
+                    .split('-')
+                    .skip_while(|part| *part != "radius")
+                    .skip(1)
+                    .collect::<Vec<&str>>()
+                    .join("-");
+                let key = if key.is_empty() { "default" } else { &key };
+                self.add_radius(key, measurement);
+                return true;
+            }
+            let key = strip_prefixes(name, &SPACE_PREFIXES);
+            if key != name && !key.is_empty() {
+                self.add_space(key, measurement);
+                return true;
+            }
+            return false;
+        }
+
+        if let Some(font_name) = name.strip_prefix("font-") {
+            let font_name = font_name.strip_prefix("family-").unwrap_or(font_name);
+            if !font_name.is_empty() && value.parse::<f64>().is_err() {
+                self.add_font(font_name, font_value(value));
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{export::css_export::export_stylesheet, test_fixtures::sample_design_system};
+
+    fn to_json<T: serde::Serialize>(value: &T) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn round_trip_of_the_uitea_stylesheet() {
+        let design_system = sample_design_system();
+        let content =
+            String::from_utf8(export_stylesheet(&design_system).unwrap().content).unwrap();
+        let mut imported = sample_design_system();
+        imported.palettes.clear();
+        imported.semantic_color_tokens.theme_overrides = IndexMap::new();
+
+        let unmapped_tokens = import_css(&mut imported, &content).unwrap();
+
+        // Shadows are written resolved, the dark theme values are ignored
+        assert_eq!(unmapped_tokens, vec!["--shadow-shadow"]);
+        assert_eq!(
+            to_json(&imported.palettes),
+            to_json(&design_system.palettes)
+        );
+        assert_eq!(
+            to_json(&imported.independant_colors),
+            to_json(&design_system.independant_colors)
+        );
+        let (semantic, expected) = (
+            &imported.semantic_color_tokens,
+            &design_system.semantic_color_tokens,
+        );
+        assert_eq!(semantic.background, expected.background);
+        assert_eq!(semantic.text_default, expected.text_default);
+        assert_eq!(
+            to_json(&semantic.color_combination_collections[0].hover),
+            to_json(&expected.color_combination_collections[0].hover)
+        );
+        assert!(semantic.theme_overrides.is_empty());
+        assert_eq!(to_json(&imported.spaces), to_json(&design_system.spaces));
+        assert_eq!(to_json(&imported.radius), to_json(&design_system.radius));
+        assert_eq!(to_json(&imported.fonts), to_json(&design_system.fonts));
+    }
+
+    #[test]
+    fn maps_the_properties_from_their_names() {
+        let content = "
+            /* Brand colors */
+            :root {
+                --primary-500: rgb(59, 130, 246);
+                --primary-900: #1e3a8a !important;
+                --accent: hsl(160, 84%, 39%);
+                --brand: var(--missing, var(--accent));
+                --spacing-sm: 8px;
+                --card-radius-lg: 0.75rem;
+                --border-radius: 4px;
+                --font-family-body: 'Inter';
+                --font-weight-bold: 700;
+                --line-height: 1.5;
+                --base-background: var(--primary-500);
+                --button-hover-text: var(--accent);
+                --base-border: var(--missing);
+            }
+            .dark { --primary-500: #000000; }
+        ";
+        let mut design_system = sample_design_system();
+        let unmapped_tokens = import_css(&mut design_system, content).unwrap();
+
+        assert_eq!(
+            unmapped_tokens,
+            vec!["--font-weight-bold", "--line-height", "--base-border"]
+        );
+        assert_eq!(
+            to_json(&design_system.palettes[0].tints),
+            json!([
+                { "label": "500", "color": "#3b82f6" },
+                { "label": "900", "color": "#1e3a8a" },
+            ])
+        );
+        let labels: Vec<&str> = design_system
+            .independant_colors
+            .independant_colors
+            .iter()
+            .map(|tint| tint.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["accent", "brand"]);
+        assert_eq!(design_system.spaces[0].space_key, "sm");
+        assert_eq!(design_system.radius.default.value, 4.0);
+        assert_eq!(design_system.radius.additionals_radius[0].radius_key, "lg");
+        assert_eq!(design_system.fonts.additionals[0].font_name, "body");
+        assert_eq!(design_system.fonts.additionals[0].value, "Inter");
+
+        let semantic = &design_system.semantic_color_tokens;
+        assert_eq!(semantic.background.as_deref(), Some("palette-primary-500"));
+        assert_eq!(semantic.border, None);
+        let button = &semantic.color_combination_collections[0];
+        assert_eq!(
+            button.hover.as_ref().unwrap().text.as_deref(),
+            Some("color-accent")
+        );
+    }
+
+    #[test]
+    fn stylesheets_without_custom_property_are_errors() {
+        let mut design_system = sample_design_system();
+        assert!(import_css(&mut design_system, "body { color: red; }").is_err());
+    }
+}
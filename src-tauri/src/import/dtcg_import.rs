@@ -0,0 +1,578 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::{
+    domain::{
+        color_domain::Rgb,
+        design_system_domain::{
+            DesignSystem, FontWeight, Measurement, Shadow, Themes, TypographyScale,
+            TypographySpacing, UnitOfMeasurement,
+        },
+        export_domain::{DtcgGroup, DtcgNode},
+    },
+    export::dtcg_export::{
+        COLORS_GROUP, EXTENSION_KEY, FONTS_GROUP, PALETTES_GROUP, RADIUS_GROUP,
+        SEMANTIC_BASE_GROUP, SEMANTIC_COMBINATIONS_GROUP, SEMANTIC_GROUP, SHADOWS_GROUP,
+        SPACES_GROUP, TYPOGRAPHY_GROUP,
+    },
+};
+
+use super::{parse_color, parse_measurement, ImportedTokens};
+
+/// Max depth when following aliases ({a.b} -> {c.d} -> value)
+const MAX_ALIAS_DEPTH: usize = 10;
+
+struct FlatToken {
+    path: Vec<String>,
+    token_type: Option<String>,
+    value: Value,
+    extensions: Option<Value>,
+}
+
+/// Import a W3C Design Tokens Community Group file. The groups written by the uitea export are
+/// mapped exactly, other files are mapped from the `$type` of their tokens.
+pub fn import_dtcg(design_system: &mut DesignSystem, content: &str) -> Result<Vec<String>> {
+    let root: DtcgGroup =
+        serde_json::from_str(content).context("Invalid design tokens (DTCG) file")?;
+    let mut tokens: Vec<FlatToken> = vec![];
+    flatten_group(&root, &[], None, &mut tokens);
+
+    let mut imported = ImportedTokens::default();
+    let mut unmapped_tokens: Vec<String> = vec![];
+    for token in &tokens {
+        if !imported.import_token(token, &tokens) {
+            unmapped_tokens.push(token.path.join("."));
+        }
+    }
+    imported.read_combination_extensions(&root);
+    imported.read_theme_overrides(&root);
+    imported.apply(design_system);
+
+    if let Some(themes) = root
+        .extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get(EXTENSION_KEY))
+        .and_then(|extension| extension.get("themes"))
+        .and_then(|themes| serde_json::from_value::<Themes>(themes.clone()).ok())
+    {
+        design_system.themes = themes;
+    }
+
+    Ok(unmapped_tokens)
+}
+
+fn flatten_group(
+    group: &DtcgGroup,
+    path: &[String],
+    inherited_type: Option<&str>,
+    tokens: &mut Vec<FlatToken>,
+) {
+    let group_type: Option<&str> = group.group_type.as_deref().or(inherited_type);
+    for (name, node) in &group.children {
+        let mut node_path: Vec<String> = path.to_vec();
+        node_path.push(name.clone());
+        match node {
+            DtcgNode::Token(token) => tokens.push(FlatToken {
+                path: node_path,
+                token_type: token.token_type.clone().or(group_type.map(String::from)),
+                value: token.value.clone(),
+                extensions: token.extensions.clone(),
+            }),
+            DtcgNode::Group(child) => flatten_group(child, &node_path, group_type, tokens),
+            DtcgNode::Other(_) => {}
+        }
+    }
+}
+
+/// Path of an alias : "{palettes.primary.500}" -> ["palettes", "primary", "500"]
+fn alias_path(value: &Value) -> Option<Vec<String>> {
+    let alias = value.as_str()?.trim();
+    let alias = alias.strip_prefix('{')?.strip_suffix('}')?;
+    Some(alias.split('.').map(String::from).collect())
+}
+
+/// Follow the aliases until a concrete value
+fn resolve_value(value: &Value, tokens: &[FlatToken]) -> Option<Value> {
+    let mut value = value.clone();
+    for _ in 0..MAX_ALIAS_DEPTH {
+        match alias_path(&value) {
+            None => return Some(value),
+            Some(path) => {
+                value = tokens
+                    .iter()
+                    .find(|token| token.path == path)?
+                    .value
+                    .clone();
+            }
+        }
+    }
+    None
+}
+
+/// Alias to an uitea color token name : {palettes.primary.500} -> palette-primary-500
+fn alias_to_token_name(value: &Value) -> Option<String> {
+    let path = alias_path(value)?;
+    match path.as_slice() {
+        [group, palette, tint] if group == PALETTES_GROUP => {
+            Some(format!("palette-{}-{}", palette, tint))
+        }
+        [group, label] if group == COLORS_GROUP => Some(format!("color-{}", label)),
+        _ => None,
+    }
+}
+
+fn color_value(value: &Value) -> Option<Rgb> {
+    match value {
+        Value::String(color) => Rgb::parse(color).ok(),
+        // DTCG 2025 color object : { colorSpace, components, alpha, hex }
+        Value::Object(color) => {
+            let mut rgb = Rgb::parse(color.get("hex")?.as_str()?).ok()?;
+            if let Some(alpha) = color.get("alpha").and_then(|alpha| alpha.as_f64()) {
+                rgb.alpha = alpha;
+            }
+            Some(rgb)
+        }
+        _ => None,
+    }
+}
+
+fn color_hex(value: &Value) -> Option<String> {
+    match value {
+        Value::String(color) => parse_color(color),
+        _ => color_value(value).map(|color| color.to_hex()),
+    }
+}
+
+/// Dimension as "4px" / "0.5rem" or { value, unit }
+fn dimension_value(value: &Value) -> Option<Measurement> {
+    match value {
+        Value::String(dimension) => parse_measurement(dimension),
+        Value::Number(number) => Some(Measurement {
+            unit: UnitOfMeasurement::PX,
+            value: number.as_f64()?,
+        }),
+        Value::Object(dimension) => {
+            let value = dimension.get("value")?.as_f64()?;
+            match dimension.get("unit")?.as_str()? {
+                "px" => Some(Measurement {
+                    unit: UnitOfMeasurement::PX,
+                    value,
+                }),
+                "rem" => Some(Measurement {
+                    unit: UnitOfMeasurement::REM,
+                    value,
+                }),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Shadow offsets are stored in px
+fn pixels_value(value: Option<&Value>) -> f32 {
+    match value.and_then(dimension_value) {
+        Some(Measurement {
+            unit: UnitOfMeasurement::REM,
+            value,
+        }) => (value * 16.0) as f32,
+        Some(Measurement { value, .. }) => value as f32,
+        None => 0.0,
+    }
+}
+
+fn font_family_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(font) => Some(font.clone()),
+        Value::Array(fonts) => Some(
+            fonts
+                .iter()
+                .filter_map(|font| font.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+        ),
+        _ => None,
+    }
+}
+
+impl ImportedTokens {
+    fn import_token(&mut self, token: &FlatToken, tokens: &[FlatToken]) -> bool {
+        let path: Vec<&str> = token.path.iter().map(|part| part.as_str()).collect();
+        match path.as_slice() {
+            [group, palette, tint] if *group == PALETTES_GROUP => {
+                self.import_palette_tint(palette, tint, &token.value, tokens)
+            }
+            [group, label] if *group == COLORS_GROUP => {
+                self.import_independant_color(label, &token.value, tokens)
+            }
+            [group, base, property] if *group == SEMANTIC_GROUP && *base == SEMANTIC_BASE_GROUP => {
+                match alias_to_token_name(&token.value) {
+                    Some(token_name) => {
+                        self.add_semantic_base(property, token_name);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            [group, combinations, name, state, property]
+                if *group == SEMANTIC_GROUP && *combinations == SEMANTIC_COMBINATIONS_GROUP =>
+            {
+                self.import_combination(name, state, property, &token.value)
+            }
+            [group, key] if *group == SPACES_GROUP => self.import_space(key, &token.value, tokens),
+            [group, key] if *group == RADIUS_GROUP => self.import_radius(key, &token.value, tokens),
+            [group, name] if *group == FONTS_GROUP => self.import_font(name, &token.value, tokens),
+            [group, ..] if *group == SHADOWS_GROUP => {
+                self.import_shadow(&token.path[1..].join("-"), token, tokens)
+            }
+            [group, ..] if *group == TYPOGRAPHY_GROUP => {
+                self.import_typography(&token.path[1..].join("-"), token, tokens)
+            }
+            _ => self.import_by_type(token, tokens),
+        }
+    }
+
+    /// Tokens outside of the uitea groups (files from other tools)
+    fn import_by_type(&mut self, token: &FlatToken, tokens: &[FlatToken]) -> bool {
+        let path = &token.path;
+        match (token.token_type.as_deref(), path.len()) {
+            (Some("color"), 1) => self.import_independant_color(&path[0], &token.value, tokens),
+            (Some("color"), _) => self.import_palette_tint(
+                &path[..path.len() - 1].join("-"),
+                &path[path.len() - 1],
+                &token.value,
+                tokens,
+            ),
+            (Some("dimension"), length) if length > 1 => {
+                let group = path[0].to_lowercase();
+                let key = path[1..].join("-");
+                if group.contains("radius") || group.contains("radii") {
+                    self.import_radius(&key, &token.value, tokens)
+                } else if group.contains("spac") || group.contains("size") {
+                    self.import_space(&key, &token.value, tokens)
+                } else {
+                    false
+                }
+            }
+            (Some("fontFamily"), _) => {
+                self.import_font(&path[path.len() - 1], &token.value, tokens)
+            }
+            (Some("shadow"), _) => self.import_shadow(&path.join("-"), token, tokens),
+            (Some("typography"), _) => self.import_typography(&path.join("-"), token, tokens),
+            _ => false,
+        }
+    }
+
+    fn import_palette_tint(
+        &mut self,
+        palette: &str,
+        tint: &str,
+        value: &Value,
+        tokens: &[FlatToken],
+    ) -> bool {
+        match resolve_value(value, tokens).as_ref().and_then(color_hex) {
+            Some(color) => {
+                self.add_palette_tint(palette, tint, color);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn import_independant_color(
+        &mut self,
+        label: &str,
+        value: &Value,
+        tokens: &[FlatToken],
+    ) -> bool {
+        match resolve_value(value, tokens).as_ref().and_then(color_hex) {
+            Some(color) => {
+                self.add_independant_color(label, color);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn import_combination(
+        &mut self,
+        name: &str,
+        state: &str,
+        property: &str,
+        value: &Value,
+    ) -> bool {
+        match alias_to_token_name(value) {
+            Some(token_name) => self.add_combination(name, state, property, token_name),
+            None => false,
+        }
+    }
+
+    fn read_combination_extensions(&mut self, root: &DtcgGroup) {
+        let combinations =
+            match root
+                .children
+                .get(SEMANTIC_GROUP)
+                .and_then(|semantic| match semantic {
+                    DtcgNode::Group(semantic) => semantic.children.get(SEMANTIC_COMBINATIONS_GROUP),
+                    _ => None,
+                }) {
+                Some(DtcgNode::Group(combinations)) => combinations,
+                _ => return,
+            };
+        for (name, node) in &combinations.children {
+            let extension = match node {
+                DtcgNode::Group(group) => group
+                    .extensions
+                    .as_ref()
+                    .and_then(|extensions| extensions.get(EXTENSION_KEY)),
+                _ => None,
+            };
+            if let (Some(collection), Some(extension)) =
+                (self.combinations.get_mut(name), extension)
+            {
+                collection.group = extension
+                    .get("group")
+                    .and_then(|group| group.as_str())
+                    .map(String::from);
+                collection.default_combination = extension
+                    .get("defaultCombination")
+                    .and_then(|default| default.as_bool());
+            }
+        }
+    }
+
+    /// Overrides of the other themes, in the `$extensions` of the semantic group
+    fn read_theme_overrides(&mut self, root: &DtcgGroup) {
+        if let Some(theme_overrides) = root
+            .children
+            .get(SEMANTIC_GROUP)
+            .and_then(|semantic| match semantic {
+                DtcgNode::Group(semantic) => semantic.extensions.as_ref(),
+                _ => None,
+            })
+            .and_then(|extensions| extensions.get(EXTENSION_KEY))
+            .and_then(|extension| extension.get("themeOverrides"))
+            .and_then(|theme_overrides| serde_json::from_value(theme_overrides.clone()).ok())
+        {
+            self.theme_overrides = theme_overrides;
+        }
+    }
+
+    fn import_space(&mut self, key: &str, value: &Value, tokens: &[FlatToken]) -> bool {
+        match resolve_value(value, tokens)
+            .as_ref()
+            .and_then(dimension_value)
+        {
+            Some(space_value) => {
+                self.add_space(key, space_value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn import_radius(&mut self, key: &str, value: &Value, tokens: &[FlatToken]) -> bool {
+        match resolve_value(value, tokens)
+            .as_ref()
+            .and_then(dimension_value)
+        {
+            Some(radius_value) => {
+                self.add_radius(key, radius_value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn import_font(&mut self, name: &str, value: &Value, tokens: &[FlatToken]) -> bool {
+        match resolve_value(value, tokens)
+            .as_ref()
+            .and_then(font_family_value)
+        {
+            Some(font) => {
+                self.add_font(name, font);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn import_shadow(&mut self, name: &str, token: &FlatToken, tokens: &[FlatToken]) -> bool {
+        // Lossless shadows written by the uitea export (keep the palette references)
+        if let Some(shadows_array) = token
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get(EXTENSION_KEY))
+            .and_then(|shadows| serde_json::from_value::<Vec<Shadow>>(shadows.clone()).ok())
+        {
+            self.add_shadows(name, shadows_array);
+            return true;
+        }
+
+        let layers: Vec<Value> = match resolve_value(&token.value, tokens) {
+            Some(Value::Array(layers)) => layers,
+            Some(layer @ Value::Object(_)) => vec![layer],
+            _ => return false,
+        };
+        let mut shadows_array: Vec<Shadow> = vec![];
+        for layer in &layers {
+            let color_property = match layer.get("color") {
+                Some(color) => color,
+                None => return false,
+            };
+            let (color, color_opacity) = match alias_to_token_name(color_property) {
+                Some(token_name) => (token_name, 1.0),
+                None => match resolve_value(color_property, tokens)
+                    .as_ref()
+                    .and_then(color_value)
+                {
+                    Some(color) => (
+                        Rgb {
+                            alpha: 1.0,
+                            ..color
+                        }
+                        .to_hex(),
+                        color.alpha as f32,
+                    ),
+                    None => return false,
+                },
+            };
+            shadows_array.push(Shadow {
+                color,
+                color_opacity,
+                shadow_x: pixels_value(layer.get("offsetX")),
+                shadow_y: pixels_value(layer.get("offsetY")),
+                blur: pixels_value(layer.get("blur")),
+                spread: pixels_value(layer.get("spread")),
+                inset: layer
+                    .get("inset")
+                    .and_then(|inset| inset.as_bool())
+                    .unwrap_or(false),
+            });
+        }
+        self.add_shadows(name, shadows_array);
+        true
+    }
+
+    fn import_typography(&mut self, name: &str, token: &FlatToken, tokens: &[FlatToken]) -> bool {
+        // Lossless scale written by the uitea export
+        if let Some(scale) = token
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get(EXTENSION_KEY))
+            .and_then(|scale| serde_json::from_value::<TypographyScale>(scale.clone()).ok())
+        {
+            self.add_typography(name, scale);
+            return true;
+        }
+
+        let value = match resolve_value(&token.value, tokens) {
+            Some(Value::Object(value)) => value,
+            _ => return false,
+        };
+        let mut scale = TypographyScale::default();
+        if let Some(font_size) = value.get("fontSize").and_then(dimension_value) {
+            scale.font_size = font_size;
+        }
+        match value.get("lineHeight") {
+            Some(Value::Number(ratio)) => {
+                scale.line_height = Measurement {
+                    unit: scale.font_size.unit.clone(),
+                    value: scale.font_size.value * ratio.as_f64().unwrap_or(1.0),
+                }
+            }
+            Some(line_height) => {
+                if let Some(line_height) = dimension_value(line_height) {
+                    scale.line_height = line_height;
+                }
+            }
+            None => {}
+        }
+        if let Some(font_weight) = value.get("fontWeight").and_then(|font_weight| {
+            let weight = match font_weight {
+                Value::Number(weight) => weight.to_string(),
+                Value::String(weight) => weight.clone(),
+                _ => return None,
+            };
+            serde_json::from_value::<FontWeight>(Value::String(weight)).ok()
+        }) {
+            scale.font_weight = font_weight;
+        }
+        if let Some(letter_spacing) = value.get("letterSpacing").and_then(|letter_spacing| {
+            serde_json::from_value::<TypographySpacing>(letter_spacing.clone()).ok()
+        }) {
+            scale.letter_spacing = letter_spacing;
+        }
+        if let Some(font) = value.get("fontFamily") {
+            scale.font = match alias_path(font).as_deref() {
+                Some([group, name]) if group == FONTS_GROUP && name != "default" => {
+                    Some(name.clone())
+                }
+                _ => None,
+            };
+        }
+        self.add_typography(name, scale);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::{export::dtcg_export::export_dtcg, test_fixtures::sample_design_system};
+
+    fn to_json<T: serde::Serialize>(value: &T) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    fn exported_content(design_system: &DesignSystem) -> String {
+        String::from_utf8(export_dtcg(design_system).unwrap().content).unwrap()
+    }
+
+    #[test]
+    fn round_trip_keeps_the_tokens_and_the_theme_overrides() {
+        let design_system = sample_design_system();
+        let mut imported = sample_design_system();
+        imported.palettes.clear();
+        imported.themes.other_themes.clear();
+        imported.semantic_color_tokens.theme_overrides = IndexMap::new();
+
+        let unmapped_tokens =
+            import_dtcg(&mut imported, &exported_content(&design_system)).unwrap();
+
+        assert!(unmapped_tokens.is_empty(), "{:?}", unmapped_tokens);
+        assert_eq!(
+            to_json(&imported.palettes),
+            to_json(&design_system.palettes)
+        );
+        assert_eq!(to_json(&imported.themes), to_json(&design_system.themes));
+        assert_eq!(
+            to_json(&imported.semantic_color_tokens),
+            to_json(&design_system.semantic_color_tokens)
+        );
+        assert_eq!(to_json(&imported.spaces), to_json(&design_system.spaces));
+        assert_eq!(to_json(&imported.shadows), to_json(&design_system.shadows));
+    }
+
+    #[test]
+    fn import_without_overrides_keeps_the_existing_ones() {
+        let mut design_system = sample_design_system();
+        design_system.semantic_color_tokens.theme_overrides = IndexMap::new();
+        let content = exported_content(&design_system);
+
+        let mut imported = sample_design_system();
+        import_dtcg(&mut imported, &content).unwrap();
+
+        assert_eq!(
+            to_json(&imported.semantic_color_tokens.theme_overrides),
+            to_json(&sample_design_system().semantic_color_tokens.theme_overrides)
+        );
+    }
+
+    #[test]
+    fn invalid_file_is_an_error() {
+        assert!(import_dtcg(&mut sample_design_system(), "{").is_err());
+    }
+}
@@ -0,0 +1,568 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+
+use crate::{
+    domain::{
+        color_domain::{Lab, Rgb},
+        color_picker_domain::ColorSample,
+        import_domain::{Swatch, SwatchFormat, SwatchGroup},
+        palette_builder_domain::{PaletteBuild, PaletteSettings, TintBuild},
+    },
+    utils::generate_uuid,
+};
+
+const ASE_SIGNATURE: &[u8] = b"ASEF";
+const ASE_GROUP_START: u16 = 0xC001;
+const ASE_GROUP_END: u16 = 0xC002;
+const ASE_COLOR_ENTRY: u16 = 0x0001;
+
+const ACO_RGB: u16 = 0;
+const ACO_HSB: u16 = 1;
+const ACO_CMYK: u16 = 2;
+const ACO_GRAYSCALE: u16 = 8;
+
+/// Groups of swatches of a swatch file. The colors without group (and the files without groups :
+/// `.aco`, `.sketchpalette`) are put in a group named `default_group_name` (the file name).
+/// The Lab colors of the `.ase` files are converted to sRGB, mapped into its gamut when needed
+pub fn read_swatches(
+    format: &SwatchFormat,
+    content: &[u8],
+    default_group_name: &str,
+) -> Result<Vec<SwatchGroup>> {
+    let groups: Vec<SwatchGroup> = match format {
+        SwatchFormat::Ase => read_ase(content, default_group_name)?,
+        SwatchFormat::Aco => vec![SwatchGroup {
+            name: String::from(default_group_name),
+            swatches: read_aco(content)?,
+        }],
+        SwatchFormat::Gpl => read_gpl(
+            std::str::from_utf8(content).context("Invalid GIMP palette")?,
+            default_group_name,
+        )?,
+        SwatchFormat::SketchPalette => vec![SwatchGroup {
+            name: String::from(default_group_name),
+            swatches: read_sketch_palette(content)?,
+        }],
+    };
+    let groups: Vec<SwatchGroup> = groups
+        .into_iter()
+        .filter(|group| !group.swatches.is_empty())
+        .collect();
+    if groups.is_empty() {
+        return Err(anyhow!("No color found in the swatch file"));
+    }
+    Ok(groups)
+}
+
+/// A sample per group, named after the group (suffixed when a sample has the same name)
+pub fn to_color_samples(groups: &[SwatchGroup], existing: &[ColorSample]) -> Vec<ColorSample> {
+    let mut samples: Vec<ColorSample> = vec![];
+    for group in groups {
+        let is_taken = |name: &str| {
+            existing
+                .iter()
+                .chain(samples.iter())
+                .any(|sample| sample.name == name)
+        };
+        let mut name = group.name.clone();
+        let mut index = 2;
+        while is_taken(&name) {
+            name = format!("{}-{}", group.name, index);
+            index += 1;
+        }
+        samples.push(ColorSample {
+            name,
+            colors: group
+                .swatches
+                .iter()
+                .map(|swatch| swatch.color.clone())
+                .collect(),
+        });
+    }
+    samples
+}
+
+/// A palette per group : the swatches are anchors (kept as is when the palette is regenerated),
+/// the middle swatch is the center of the palette
+pub fn to_palette_builds(groups: &[SwatchGroup], settings: &PaletteSettings) -> Vec<PaletteBuild> {
+    groups
+        .iter()
+        .map(|group| {
+            let center_index = group.swatches.len() / 2;
+            let tint_prefix = format!("{}-", group.name);
+            PaletteBuild {
+                id: generate_uuid(),
+                name: group.name.clone(),
+                tints: group
+                    .swatches
+                    .iter()
+                    .enumerate()
+                    .map(|(index, swatch)| TintBuild {
+                        // palette-500 -> 500 (swatches exported by uitea)
+                        name: swatch
+                            .name
+                            .strip_prefix(&tint_prefix)
+                            .unwrap_or(&swatch.name)
+                            .to_string(),
+                        is_anchor: if index == center_index {
+                            None
+                        } else {
+                            Some(true)
+                        },
+                        is_center: if index == center_index {
+                            Some(true)
+                        } else {
+                            None
+                        },
+                        color: swatch.color.clone(),
+                    })
+                    .collect(),
+                settings: settings.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Big endian reader of the binary swatch files
+struct Reader<'a> {
+    content: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(content: &'a [u8]) -> Reader<'a> {
+        Reader {
+            content,
+            position: 0,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position >= self.content.len()
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .content
+            .get(self.position..self.position + length)
+            .ok_or_else(|| anyhow!("Truncated swatch file"))?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    /// UTF-16 string of `length` code units, null terminator removed
+    fn utf16(&mut self, length: usize) -> Result<String> {
+        let units: Vec<u16> = (0..length)
+            .map(|_| self.u16())
+            .collect::<Result<Vec<u16>>>()?;
+        Ok(String::from_utf16_lossy(&units)
+            .trim_end_matches('\0')
+            .to_string())
+    }
+}
+
+fn read_ase(content: &[u8], default_group_name: &str) -> Result<Vec<SwatchGroup>> {
+    let mut reader = Reader::new(content);
+    if reader.bytes(4)? != ASE_SIGNATURE {
+        return Err(anyhow!("Invalid Adobe Swatch Exchange file"));
+    }
+    reader.bytes(4)?; // version
+    let block_count = reader.u32()?;
+
+    let mut groups: Vec<SwatchGroup> = vec![SwatchGroup {
+        name: String::from(default_group_name),
+        swatches: vec![],
+    }];
+    let mut in_group = false;
+    for _ in 0..block_count {
+        let block_type = reader.u16()?;
+        let length = reader.u32()? as usize;
+        let mut block = Reader::new(reader.bytes(length)?);
+        match block_type {
+            ASE_GROUP_START => {
+                let name_length = block.u16()? as usize;
+                groups.push(SwatchGroup {
+                    name: block.utf16(name_length)?,
+                    swatches: vec![],
+                });
+                in_group = true;
+            }
+            ASE_GROUP_END => in_group = false,
+            ASE_COLOR_ENTRY => {
+                let name_length = block.u16()? as usize;
+                let name = block.utf16(name_length)?;
+                let model = block.bytes(4)?;
+                let color = match model {
+                    b"RGB " => Some(Rgb::new(
+                        block.f32()? as f64,
+                        block.f32()? as f64,
+                        block.f32()? as f64,
+                    )),
+                    b"CMYK" => Some(cmyk_to_rgb(
+                        block.f32()? as f64,
+                        block.f32()? as f64,
+                        block.f32()? as f64,
+                        block.f32()? as f64,
+                    )),
+                    b"Gray" => {
+                        let gray = block.f32()? as f64;
+                        Some(Rgb::new(gray, gray, gray))
+                    }
+                    // L in 0...1, a & b in -128...127 (D50 white point)
+                    b"LAB " => Some(
+                        Rgb::from_lab(
+                            &Lab {
+                                l: block.f32()? as f64 * 100.0,
+                                a: block.f32()? as f64,
+                                b: block.f32()? as f64,
+                            },
+                            1.0,
+                        )
+                        .to_gamut(),
+                    ),
+                    _ => None,
+                };
+                if let Some(color) = color {
+                    let group_index = if in_group { groups.len() - 1 } else { 0 };
+                    groups[group_index].swatches.push(Swatch {
+                        name,
+                        color: color.to_hex(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(groups)
+}
+
+/// Version 1 section (colors only), followed by an optional version 2 section (colors & names)
+fn read_aco(content: &[u8]) -> Result<Vec<Swatch>> {
+    let mut reader = Reader::new(content);
+    let mut swatches: Vec<Swatch> = vec![];
+    while !reader.is_empty() {
+        let version = reader.u16()?;
+        if version != 1 && version != 2 {
+            return Err(anyhow!("Invalid Photoshop color swatches file"));
+        }
+        let count = reader.u16()?;
+        let mut section: Vec<Swatch> = vec![];
+        for index in 0..count {
+            let color_space = reader.u16()?;
+            let values = [reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?];
+            let name = if version == 2 {
+                let name_length = reader.u32()? as usize;
+                reader.utf16(name_length)?
+            } else {
+                format!("color-{}", index + 1)
+            };
+            if let Some(color) = aco_color(color_space, values) {
+                section.push(Swatch {
+                    name,
+                    color: color.to_hex(),
+                });
+            }
+        }
+        // The version 2 section has the same colors with their names
+        swatches = section;
+    }
+    Ok(swatches)
+}
+
+fn aco_color(color_space: u16, [w, x, y, z]: [u16; 4]) -> Option<Rgb> {
+    let ratio = |value: u16| value as f64 / 65535.0;
+    match color_space {
+        ACO_RGB => Some(Rgb::new(ratio(w), ratio(x), ratio(y))),
+        ACO_HSB => Some(hsb_to_rgb(ratio(w) * 360.0, ratio(x), ratio(y))),
+        // 0 is 100% of ink
+        ACO_CMYK => Some(cmyk_to_rgb(
+            1.0 - ratio(w),
+            1.0 - ratio(x),
+            1.0 - ratio(y),
+            1.0 - ratio(z),
+        )),
+        // 0...10000, 10000 is black
+        ACO_GRAYSCALE => {
+            let gray = 1.0 - (w as f64 / 10000.0).clamp(0.0, 1.0);
+            Some(Rgb::new(gray, gray, gray))
+        }
+        _ => None,
+    }
+}
+
+fn cmyk_to_rgb(c: f64, m: f64, y: f64, k: f64) -> Rgb {
+    Rgb::new(
+        (1.0 - c) * (1.0 - k),
+        (1.0 - m) * (1.0 - k),
+        (1.0 - y) * (1.0 - k),
+    )
+}
+
+fn hsb_to_rgb(hue: f64, saturation: f64, brightness: f64) -> Rgb {
+    let chroma = brightness * saturation;
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = brightness - chroma;
+    Rgb::new(r + m, g + m, b + m)
+}
+
+/// `R G B name` lines. A comment followed by a color starts a group (`# primary`, as in the
+/// `.gpl` export of uitea)
+fn read_gpl(content: &str, default_group_name: &str) -> Result<Vec<SwatchGroup>> {
+    let mut lines = content.lines().map(str::trim).peekable();
+    if lines.next() != Some("GIMP Palette") {
+        return Err(anyhow!("Invalid GIMP palette"));
+    }
+    let mut groups: Vec<SwatchGroup> = vec![SwatchGroup {
+        name: String::from(default_group_name),
+        swatches: vec![],
+    }];
+    let mut color_count = 0;
+    while let Some(line) = lines.next() {
+        if let Some(name) = line.strip_prefix("Name:") {
+            groups[0].name = name.trim().to_string();
+        } else if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim();
+            let next_is_color = lines
+                .peek()
+                .map(|next| gpl_color(next).is_some())
+                .unwrap_or(false);
+            if !comment.is_empty() && next_is_color {
+                groups.push(SwatchGroup {
+                    name: comment.to_string(),
+                    swatches: vec![],
+                });
+            }
+        } else if let Some((color, name)) = gpl_color(line) {
+            color_count += 1;
+            let name = if name.is_empty() {
+                format!("color-{}", color_count)
+            } else {
+                name
+            };
+            let group_index = groups.len() - 1;
+            groups[group_index].swatches.push(Swatch {
+                name,
+                color: color.to_hex(),
+            });
+        }
+    }
+    Ok(groups)
+}
+
+fn gpl_color(line: &str) -> Option<(Rgb, String)> {
+    let mut parts = line.split_whitespace();
+    let mut channel = || -> Option<f64> {
+        parts
+            .next()?
+            .parse::<u8>()
+            .ok()
+            .map(|value| value as f64 / 255.0)
+    };
+    let color = Rgb::new(channel()?, channel()?, channel()?);
+    Some((color, parts.collect::<Vec<&str>>().join(" ")))
+}
+
+/// Sketch Palettes plugin : hex strings (format 1.x) or `{ red, green, blue, alpha, name }` (format 2.0)
+fn read_sketch_palette(content: &[u8]) -> Result<Vec<Swatch>> {
+    let palette: Value = serde_json::from_slice(content).context("Invalid Sketch palette")?;
+    let colors = palette
+        .get("colors")
+        .and_then(|colors| colors.as_array())
+        .ok_or_else(|| anyhow!("Invalid Sketch palette"))?;
+    Ok(colors
+        .iter()
+        .enumerate()
+        .filter_map(|(index, color)| {
+            let default_name = format!("color-{}", index + 1);
+            match color {
+                Value::String(hex) => Rgb::parse(&format!("#{}", hex.trim_start_matches('#')))
+                    .ok()
+                    .map(|rgb| Swatch {
+                        name: default_name,
+                        color: rgb.to_hex(),
+                    }),
+                Value::Object(_) => {
+                    let channel = |key: &str| color.get(key).and_then(|value| value.as_f64());
+                    let rgb = Rgb {
+                        r: channel("red")?,
+                        g: channel("green")?,
+                        b: channel("blue")?,
+                        alpha: channel("alpha").unwrap_or(1.0),
+                    };
+                    Some(Swatch {
+                        name: color
+                            .get("name")
+                            .and_then(|name| name.as_str())
+                            .map(String::from)
+                            .unwrap_or(default_name),
+                        color: rgb.to_hex(),
+                    })
+                }
+                _ => None,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{export::swatches_export::export_swatches, test_fixtures::sample_design_system};
+
+    /// `.ase` file of color entries without group : (name, model, values)
+    fn ase_file(entries: &[(&str, &[u8; 4], &[f32])]) -> Vec<u8> {
+        let mut ase: Vec<u8> = ASE_SIGNATURE.to_vec();
+        ase.extend_from_slice(&[0, 1, 0, 0]);
+        ase.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for (name, model, values) in entries {
+            let mut units: Vec<u16> = name.encode_utf16().collect();
+            units.push(0);
+            let mut body: Vec<u8> = (units.len() as u16).to_be_bytes().to_vec();
+            units
+                .iter()
+                .for_each(|unit| body.extend_from_slice(&unit.to_be_bytes()));
+            body.extend_from_slice(*model);
+            values
+                .iter()
+                .for_each(|value| body.extend_from_slice(&value.to_be_bytes()));
+            body.extend_from_slice(&[0, 2]);
+            ase.extend_from_slice(&ASE_COLOR_ENTRY.to_be_bytes());
+            ase.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            ase.extend(body);
+        }
+        ase
+    }
+
+    fn colors(groups: &[SwatchGroup]) -> Vec<(String, Vec<(String, String)>)> {
+        groups
+            .iter()
+            .map(|group| {
+                (
+                    group.name.clone(),
+                    group
+                        .swatches
+                        .iter()
+                        .map(|swatch| (swatch.name.clone(), swatch.color.clone()))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ase_lab_colors_are_converted() {
+        let content = ase_file(&[
+            ("gray", b"LAB ", &[0.5, 0.0, 0.0]),
+            ("white", b"LAB ", &[1.0, 0.0, 0.0]),
+            ("red", b"RGB ", &[1.0, 0.0, 0.0]),
+            ("ink", b"CMYK", &[0.0, 0.0, 0.0, 1.0]),
+        ]);
+        let groups = read_swatches(&SwatchFormat::Ase, &content, "file").unwrap();
+        assert_eq!(
+            colors(&groups),
+            vec![(
+                String::from("file"),
+                vec![
+                    (String::from("gray"), String::from("#777777")),
+                    (String::from("white"), String::from("#ffffff")),
+                    (String::from("red"), String::from("#ff0000")),
+                    (String::from("ink"), String::from("#000000")),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn ase_lab_colors_out_of_gamut_are_mapped() {
+        let content = ase_file(&[("green", b"LAB ", &[0.9, -120.0, 120.0])]);
+        let groups = read_swatches(&SwatchFormat::Ase, &content, "file").unwrap();
+        let color = Rgb::parse(&groups[0].swatches[0].color).unwrap();
+        assert!(color.g > color.r && color.g > color.b);
+    }
+
+    #[test]
+    fn reads_the_exported_swatches() {
+        let design_system = sample_design_system();
+        let files = export_swatches(&design_system).unwrap();
+        let expected = vec![
+            (
+                String::from("primary"),
+                vec![
+                    (String::from("primary-50"), String::from("#eff6ff")),
+                    (String::from("primary-500"), String::from("#3b82f6")),
+                    (String::from("primary-900"), String::from("#1e3a8a")),
+                ],
+            ),
+            (
+                String::from("gray"),
+                vec![
+                    (String::from("gray-50"), String::from("#f9fafb")),
+                    (String::from("gray-500"), String::from("#6b7280")),
+                    (String::from("gray-900"), String::from("#111827")),
+                ],
+            ),
+            (
+                String::from("Independant colors"),
+                vec![
+                    (String::from("white"), String::from("#ffffff")),
+                    (String::from("success"), String::from("#10b981")),
+                ],
+            ),
+        ];
+        for file in files {
+            let format = SwatchFormat::from_path(&file.path).unwrap();
+            let groups = read_swatches(&format, &file.content, "palettes").unwrap();
+            match format {
+                SwatchFormat::Ase | SwatchFormat::Gpl => assert_eq!(colors(&groups), expected),
+                // a single group without names
+                _ => assert_eq!(groups[0].swatches.len(), 8),
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_files_are_errors() {
+        assert!(read_swatches(&SwatchFormat::Ase, b"ASEX", "file").is_err());
+        assert!(read_swatches(&SwatchFormat::Ase, &ase_file(&[]), "file").is_err());
+        assert!(read_swatches(&SwatchFormat::Gpl, b"not a palette", "file").is_err());
+        assert!(read_swatches(&SwatchFormat::Aco, &[0, 3], "file").is_err());
+    }
+
+    #[test]
+    fn sample_names_are_unique() {
+        let groups = vec![SwatchGroup {
+            name: String::from("brand"),
+            swatches: vec![Swatch {
+                name: String::from("red"),
+                color: String::from("#ff0000"),
+            }],
+        }];
+        let existing = to_color_samples(&groups, &[]);
+        let samples = to_color_samples(&groups, &existing);
+        assert_eq!(samples[0].name, "brand-2");
+        assert_eq!(samples[0].colors, vec![String::from("#ff0000")]);
+    }
+}
@@ -0,0 +1,520 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::domain::{
+    color_domain::Rgb,
+    design_system_domain::{DesignSystem, Shadow},
+};
+
+use super::{parse_color, parse_measurement, semantic_name, strip_prefixes, ImportedTokens};
+
+/// Max depth when following references ({a.b} -> {c.d} -> value)
+const MAX_REFERENCE_DEPTH: usize = 10;
+const METADATA_KEY: &str = "$metadata";
+const SPACE_GROUPS: [&str; 3] = ["spacing", "spaces", "space"];
+const RADIUS_GROUPS: [&str; 4] = ["borderRadius", "radius", "radii", "border-radius"];
+
+/// Node of a Tokens Studio file, groups keep the order of the file
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StudioNode {
+    Token {
+        #[serde(alias = "$value")]
+        value: Value,
+        #[serde(rename = "type", alias = "$type", default)]
+        token_type: Option<String>,
+    },
+    Group(IndexMap<String, StudioNode>),
+    /// `$themes`, `tokenSetOrder` of `$metadata`, descriptions...
+    Other(Value),
+}
+
+struct StudioToken {
+    set_name: String,
+    path: Vec<String>,
+    token_type: Option<String>,
+    value: Value,
+}
+
+/// Import a Figma Tokens Studio file (the export of uitea or a file edited in Figma). The token sets
+/// are read in the `$metadata.tokenSetOrder` order, a token already defined by a previous set
+/// (the palettes recolored for the other themes) is skipped.
+pub fn import_tokens_studio(
+    design_system: &mut DesignSystem,
+    content: &str,
+) -> Result<Vec<String>> {
+    let root: IndexMap<String, StudioNode> =
+        serde_json::from_str(content).context("Invalid Tokens Studio file")?;
+    let tokens = flatten_token_sets(&root);
+    if tokens.is_empty() {
+        return Err(anyhow!("No token found in the Tokens Studio file"));
+    }
+    let tokens_by_path: HashMap<String, &StudioToken> = tokens
+        .iter()
+        .map(|token| (token.path.join("."), token))
+        .collect();
+
+    let mut imported = ImportedTokens::default();
+    let mut unmapped_tokens: Vec<String> = vec![];
+    // Token path -> uitea token name, to map the semantic references
+    let mut color_tokens: HashMap<String, String> = HashMap::new();
+    let mut references: Vec<(&StudioToken, String)> = vec![];
+    // Read once every color is known, a shadow color can reference a palette defined after it
+    let mut other_tokens: Vec<&StudioToken> = vec![];
+
+    for token in &tokens {
+        if token.token_type.as_deref() != Some("color") {
+            other_tokens.push(token);
+            continue;
+        }
+        if let Some(reference) = reference_path(&token.value) {
+            if semantic_name(&semantic_token_name(token)).is_some() {
+                references.push((token, reference));
+                continue;
+            }
+        }
+        match resolve_value(&token.value, &tokens_by_path)
+            .and_then(|value| value.as_str().and_then(parse_color))
+        {
+            Some(color) => {
+                let token_name = imported.add_color(&color_token_name(&token.path), color);
+                color_tokens.insert(token.path.join("."), token_name);
+            }
+            None => unmapped_tokens.push(format!("{}.{}", token.set_name, token.path.join("."))),
+        }
+    }
+
+    for token in other_tokens {
+        if !imported.import_studio_token(token, &tokens_by_path, &color_tokens) {
+            unmapped_tokens.push(format!("{}.{}", token.set_name, token.path.join(".")));
+        }
+    }
+
+    for (token, reference) in references {
+        let mapped = match color_tokens.get(&reference) {
+            Some(token_name) => {
+                imported.add_semantic(&semantic_token_name(token), token_name.clone())
+            }
+            None => false,
+        };
+        if !mapped {
+            unmapped_tokens.push(format!("{}.{}", token.set_name, token.path.join(".")));
+        }
+    }
+
+    imported.apply(design_system);
+    Ok(unmapped_tokens)
+}
+
+/// Tokens of every set, the first definition of a path wins
+fn flatten_token_sets(root: &IndexMap<String, StudioNode>) -> Vec<StudioToken> {
+    let mut set_names: Vec<&str> = match root.get(METADATA_KEY) {
+        Some(StudioNode::Group(metadata)) => match metadata.get("tokenSetOrder") {
+            Some(StudioNode::Other(Value::Array(order))) => {
+                order.iter().filter_map(|name| name.as_str()).collect()
+            }
+            _ => vec![],
+        },
+        _ => vec![],
+    };
+    for name in root.keys() {
+        if !name.starts_with('$') && !set_names.contains(&name.as_str()) {
+            set_names.push(name);
+        }
+    }
+
+    let mut tokens: Vec<StudioToken> = vec![];
+    for set_name in set_names {
+        if let Some(StudioNode::Group(set)) = root.get(set_name) {
+            let mut set_tokens: Vec<StudioToken> = vec![];
+            flatten_group(set_name, set, &[], &mut set_tokens);
+            for token in set_tokens {
+                if !tokens.iter().any(|existing| existing.path == token.path) {
+                    tokens.push(token);
+                }
+            }
+        }
+    }
+    tokens
+}
+
+/// A token is an object with a `value` (`$value` in the W3C mode of Tokens Studio)
+fn flatten_group(
+    set_name: &str,
+    group: &IndexMap<String, StudioNode>,
+    path: &[String],
+    tokens: &mut Vec<StudioToken>,
+) {
+    for (name, node) in group {
+        let mut node_path: Vec<String> = path.to_vec();
+        node_path.push(name.clone());
+        match node {
+            StudioNode::Token { value, token_type } => tokens.push(StudioToken {
+                set_name: String::from(set_name),
+                path: node_path,
+                token_type: token_type.clone(),
+                value: value.clone(),
+            }),
+            StudioNode::Group(group) => flatten_group(set_name, group, &node_path, tokens),
+            StudioNode::Other(_) => {}
+        }
+    }
+}
+
+/// Name of a color token : the uitea export writes `<palette>.palette-<palette>-<tint>`,
+/// other files are named from their path (`colors.primary.500` -> colors-primary-500)
+fn color_token_name(path: &[String]) -> String {
+    match path {
+        [.., palette, name] if name.starts_with(&format!("palette-{}-", palette)) => name.clone(),
+        _ => path.join("-"),
+    }
+}
+
+/// Name of a semantic token, without the `semantic` group (`semantic.base.background` -> base-background)
+fn semantic_token_name(token: &StudioToken) -> String {
+    let name = token.path.join("-");
+    strip_prefixes(&name, &["semantic-"]).to_string()
+}
+
+/// Referenced path of `{colors.primary.500}`
+fn reference_path(value: &Value) -> Option<String> {
+    value
+        .as_str()?
+        .trim()
+        .strip_prefix('{')?
+        .strip_suffix('}')
+        .map(String::from)
+}
+
+fn resolve_value(value: &Value, tokens_by_path: &HashMap<String, &StudioToken>) -> Option<Value> {
+    let mut value = value.clone();
+    for _ in 0..MAX_REFERENCE_DEPTH {
+        match reference_path(&value) {
+            Some(reference) => value = tokens_by_path.get(&reference)?.value.clone(),
+            None => return Some(value),
+        }
+    }
+    None
+}
+
+/// Number or string of a Tokens Studio dimension, unitless values are px
+fn dimension_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(number) => Some(number.to_string()),
+        Value::String(text) => Some(text.clone()),
+        _ => None,
+    }
+}
+
+fn pixels_value(value: Option<&Value>) -> f32 {
+    value
+        .and_then(dimension_text)
+        .and_then(|text| text.trim().trim_end_matches("px").parse::<f32>().ok())
+        .unwrap_or(0.0)
+}
+
+/// Key of a space / radius : the path without its group (`spacing.sm` -> sm)
+fn group_key(path: &[String], groups: &[&str]) -> String {
+    match path {
+        [group, rest @ ..] if !rest.is_empty() && groups.contains(&group.as_str()) => {
+            rest.join("-")
+        }
+        _ => path.join("-"),
+    }
+}
+
+impl ImportedTokens {
+    fn import_studio_token(
+        &mut self,
+        token: &StudioToken,
+        tokens_by_path: &HashMap<String, &StudioToken>,
+        color_tokens: &HashMap<String, String>,
+    ) -> bool {
+        let value = match resolve_value(&token.value, tokens_by_path) {
+            Some(value) => value,
+            None => return false,
+        };
+        let group = token.path[0].as_str();
+        match token.token_type.as_deref() {
+            Some("spacing") => self.import_studio_space(&token.path, &value),
+            Some("borderRadius") => self.import_studio_radius(&token.path, &value),
+            Some("dimension") | Some("sizing") if SPACE_GROUPS.contains(&group) => {
+                self.import_studio_space(&token.path, &value)
+            }
+            Some("dimension") | Some("sizing") if RADIUS_GROUPS.contains(&group) => {
+                self.import_studio_radius(&token.path, &value)
+            }
+            Some("fontFamilies") | Some("fontFamily") => match value.as_str() {
+                Some(font) => {
+                    let name = &token.path[token.path.len() - 1];
+                    self.add_font(name, font.to_string());
+                    true
+                }
+                None => false,
+            },
+            Some("boxShadow") | Some("shadow") => {
+                self.import_studio_shadow(token, &value, tokens_by_path, color_tokens)
+            }
+            _ => false,
+        }
+    }
+
+    fn import_studio_space(&mut self, path: &[String], value: &Value) -> bool {
+        match dimension_text(value).and_then(|text| parse_measurement(&text)) {
+            Some(space_value) => {
+                self.add_space(&group_key(path, &SPACE_GROUPS), space_value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn import_studio_radius(&mut self, path: &[String], value: &Value) -> bool {
+        match dimension_text(value).and_then(|text| parse_measurement(&text)) {
+            Some(radius_value) => {
+                let key = group_key(path, &RADIUS_GROUPS);
+                let key = if RADIUS_GROUPS.contains(&key.as_str()) {
+                    "default"
+                } else {
+                    &key
+                };
+                self.add_radius(key, radius_value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `{ x, y, blur, spread, color, type: dropShadow | innerShadow }` or an array of layers.
+    /// A color referencing a palette keeps the reference
+    fn import_studio_shadow(
+        &mut self,
+        token: &StudioToken,
+        value: &Value,
+        tokens_by_path: &HashMap<String, &StudioToken>,
+        color_tokens: &HashMap<String, String>,
+    ) -> bool {
+        let layers: Vec<&Value> = match value {
+            Value::Array(layers) => layers.iter().collect(),
+            Value::Object(_) => vec![value],
+            _ => return false,
+        };
+        let mut shadows_array: Vec<Shadow> = vec![];
+        for layer in layers {
+            let color_property = match layer.get("color") {
+                Some(color) => color,
+                None => return false,
+            };
+            let (color, color_opacity) = match reference_path(color_property)
+                .and_then(|reference| color_tokens.get(&reference))
+            {
+                Some(token_name) => (token_name.clone(), 1.0),
+                None => match resolve_value(color_property, tokens_by_path)
+                    .and_then(|color| color.as_str().and_then(|color| Rgb::parse(color).ok()))
+                {
+                    Some(color) => (
+                        Rgb {
+                            alpha: 1.0,
+                            ..color
+                        }
+                        .to_hex(),
+                        color.alpha as f32,
+                    ),
+                    None => return false,
+                },
+            };
+            shadows_array.push(Shadow {
+                color,
+                color_opacity,
+                shadow_x: pixels_value(layer.get("x")),
+                shadow_y: pixels_value(layer.get("y")),
+                blur: pixels_value(layer.get("blur")),
+                spread: pixels_value(layer.get("spread")),
+                inset: layer
+                    .get("type")
+                    .and_then(|shadow_type| shadow_type.as_str())
+                    == Some("innerShadow"),
+            });
+        }
+        self.add_shadows(
+            &group_key(&token.path, &["boxShadow", "shadows", "shadow"]),
+            shadows_array,
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        export::tokens_studio_export::export_tokens_studio, test_fixtures::sample_design_system,
+    };
+
+    fn to_json<T: serde::Serialize>(value: &T) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    /// File edited in Figma : references, dimensions without unit, shadow layers
+    fn figma_file() -> Value {
+        json!({
+            "global": {
+                "colors": {
+                    "primary": { "500": { "value": "#3b82f6", "type": "color" } },
+                    "white": { "value": "#fafafa", "type": "color" },
+                    "brand": { "value": "{colors.primary.500}", "type": "color" },
+                },
+                "semantic": {
+                    "base": {
+                        "background": { "value": "{colors.white}", "type": "color" },
+                        "border": { "value": "{colors.missing}", "type": "color" },
+                    },
+                    "button": {
+                        "background": { "value": "{colors.primary.500}", "type": "color" },
+                        "hover": { "text": { "value": "{colors.white}", "type": "color" } },
+                    },
+                },
+                "spacing": { "sm": { "value": "8", "type": "spacing" } },
+                "radius": {
+                    "default": { "value": 4, "type": "dimension" },
+                    "lg": { "value": "0.75rem", "type": "dimension" },
+                },
+                "fontFamilies": { "body": { "value": "Inter", "type": "fontFamilies" } },
+                "boxShadow": {
+                    "card": {
+                        "value": [
+                            { "x": "0", "y": "2", "blur": "4", "spread": "0", "color": "{colors.primary.500}", "type": "dropShadow" },
+                            { "x": 0, "y": 1, "blur": 2, "spread": 0, "color": "#00000040", "type": "innerShadow" },
+                        ],
+                        "type": "boxShadow",
+                    },
+                },
+                "opacity": { "half": { "value": "50%", "type": "opacity" } },
+            },
+        })
+    }
+
+    #[test]
+    fn round_trip_keeps_the_palettes() {
+        let design_system = sample_design_system();
+        let content =
+            String::from_utf8(export_tokens_studio(&design_system).unwrap().content).unwrap();
+        let mut imported = sample_design_system();
+        imported.palettes.clear();
+
+        // The recolored palettes of the dark set have the same paths as the light set
+        let unmapped_tokens = import_tokens_studio(&mut imported, &content).unwrap();
+
+        assert!(unmapped_tokens.is_empty(), "{:?}", unmapped_tokens);
+        assert_eq!(
+            to_json(&imported.palettes),
+            to_json(&design_system.palettes)
+        );
+    }
+
+    #[test]
+    fn maps_the_tokens_of_a_figma_file() {
+        let mut design_system = sample_design_system();
+        let unmapped_tokens =
+            import_tokens_studio(&mut design_system, &figma_file().to_string()).unwrap();
+
+        assert_eq!(
+            unmapped_tokens,
+            vec!["global.opacity.half", "global.semantic.base.border"]
+        );
+        assert_eq!(
+            to_json(&design_system.palettes),
+            json!([{
+                "paletteName": "primary",
+                "palettePath": null,
+                "tints": [{ "label": "500", "color": "#3b82f6" }],
+            }])
+        );
+        let independant_colors = &design_system.independant_colors;
+        assert_eq!(independant_colors.white, "#fafafa");
+        assert_eq!(
+            to_json(&independant_colors.independant_colors),
+            json!([{ "label": "brand", "color": "#3b82f6" }])
+        );
+
+        let semantic_color_tokens = &design_system.semantic_color_tokens;
+        assert_eq!(
+            semantic_color_tokens.background.as_deref(),
+            Some("color-white")
+        );
+        assert_eq!(semantic_color_tokens.border, None);
+        let button = &semantic_color_tokens.color_combination_collections[0];
+        assert_eq!(button.combination_name.as_deref(), Some("button"));
+        assert_eq!(
+            button.default.as_ref().unwrap().background.as_deref(),
+            Some("palette-primary-500")
+        );
+        assert_eq!(
+            button.hover.as_ref().unwrap().text.as_deref(),
+            Some("color-white")
+        );
+        // The file has no theme, the overrides are kept
+        assert_eq!(
+            to_json(&semantic_color_tokens.theme_overrides),
+            to_json(&sample_design_system().semantic_color_tokens.theme_overrides)
+        );
+    }
+
+    #[test]
+    fn maps_the_dimensions_fonts_and_shadows() {
+        let mut design_system = sample_design_system();
+        import_tokens_studio(&mut design_system, &figma_file().to_string()).unwrap();
+
+        assert_eq!(
+            to_json(&design_system.spaces),
+            json!([{ "spaceKey": "sm", "spaceValue": { "unit": "PX", "value": 8.0 } }])
+        );
+        assert_eq!(design_system.radius.default.value, 4.0);
+        assert_eq!(
+            to_json(&design_system.radius.additionals_radius),
+            json!([{ "radiusKey": "lg", "radiusValue": { "unit": "REM", "value": 0.75 } }])
+        );
+        assert_eq!(design_system.fonts.default, "Open Sans");
+        assert_eq!(design_system.fonts.additionals.len(), 1);
+        assert_eq!(design_system.fonts.additionals[0].value, "Inter");
+
+        // `json!` sorts the keys, the shadow referencing the palette is read before the colors
+        let card = &design_system.shadows[0];
+        assert_eq!(card.shadow_name, "card");
+        let (drop, inner) = (&card.shadows_array[0], &card.shadows_array[1]);
+        assert_eq!(
+            (drop.color.as_str(), drop.color_opacity),
+            ("palette-primary-500", 1.0)
+        );
+        assert_eq!((drop.shadow_y, drop.blur, drop.inset), (2.0, 4.0, false));
+        assert_eq!(inner.color, "#000000");
+        assert!((inner.color_opacity - 0.25).abs() < 0.01);
+        assert!(inner.inset);
+    }
+
+    #[test]
+    fn token_sets_follow_the_metadata_order() {
+        let content = json!({
+            "dark": { "colors": { "gray": { "50": { "value": "#111827", "type": "color" } } } },
+            "light": { "colors": { "gray": { "50": { "value": "#f9fafb", "type": "color" } } } },
+            "$themes": [],
+            "$metadata": { "tokenSetOrder": ["light", "dark"] },
+        });
+        let mut design_system = sample_design_system();
+        import_tokens_studio(&mut design_system, &content.to_string()).unwrap();
+        assert_eq!(design_system.palettes[0].tints[0].color, "#f9fafb");
+    }
+
+    #[test]
+    fn files_without_token_are_errors() {
+        let mut design_system = sample_design_system();
+        assert!(import_tokens_studio(&mut design_system, "{").is_err());
+        assert!(import_tokens_studio(&mut design_system, "{ \"global\": {} }").is_err());
+    }
+}
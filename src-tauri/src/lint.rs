@@ -0,0 +1,310 @@
+use std::collections::HashSet;
+
+use crate::{
+    domain::{
+        color_domain::Rgb,
+        design_system_domain::DesignSystem,
+        lint_domain::{LintConfig, LintIssue, LintLevel, LintReport, LintRule},
+    },
+    export::{
+        independant_color_tokens, measurement_to_px, palette_token_name, resolve_color,
+        root_font_size, semantic_color_tokens,
+    },
+};
+
+/// Run the rules enabled in the lint configuration, `font_files` are the names (without extension)
+/// of the files under `exports/fonts`
+pub fn lint_design_system(
+    design_system: &DesignSystem,
+    config: &LintConfig,
+    font_files: &[String],
+) -> LintReport {
+    let mut issues: Vec<LintIssue> = vec![];
+    for rule in LintRule::ALL {
+        let level = config.level(rule);
+        if level == LintLevel::Off {
+            continue;
+        }
+        let rule_issues: Vec<(String, String)> = match rule {
+            LintRule::UnresolvedColorReference => unresolved_color_references(design_system),
+            LintRule::InvalidColor => invalid_colors(design_system),
+            LintRule::DuplicateSpaceKey => duplicate_space_keys(design_system),
+            LintRule::NonMonotonicSpacing => non_monotonic_spacing(design_system),
+            LintRule::LineHeightBelowFontSize => line_heights_below_font_size(design_system),
+            LintRule::MissingFontFile => missing_font_files(design_system, font_files),
+            LintRule::UnusedPalette => unused_palettes(design_system),
+        };
+        issues.extend(
+            rule_issues
+                .into_iter()
+                .map(|(location, message)| LintIssue {
+                    rule,
+                    level,
+                    location,
+                    message,
+                }),
+        );
+    }
+    LintReport { issues }
+}
+
+/// Colors referenced by the semantic tokens (with the theme overrides), typographies & shadows as
+/// (location, color)
+fn color_references(design_system: &DesignSystem) -> Vec<(String, String)> {
+    let semantic_tokens = semantic_color_tokens(&design_system.semantic_color_tokens);
+    let mut references = semantic_tokens.clone();
+    // Only the values which differ from the main theme, the others are already listed
+    for theme_name in design_system.semantic_color_tokens.theme_overrides.keys() {
+        references.extend(
+            semantic_color_tokens(&design_system.semantic_color_tokens.for_theme(theme_name))
+                .into_iter()
+                .filter(|token| !semantic_tokens.contains(token))
+                .map(|(name, color)| (format!("themes.{}.{}", theme_name, name), color)),
+        );
+    }
+    for (scale_name, scale) in design_system.typography.scales() {
+        if let Some(color) = &scale.color {
+            references.push((format!("typography.{}", scale_name), color.clone()));
+        }
+    }
+    for shadows in &design_system.shadows {
+        for shadow in &shadows.shadows_array {
+            references.push((
+                format!("shadow-{}", shadows.shadow_name),
+                shadow.color.clone(),
+            ));
+        }
+    }
+    references
+}
+
+fn unresolved_color_references(design_system: &DesignSystem) -> Vec<(String, String)> {
+    color_references(design_system)
+        .into_iter()
+        .filter(|(_, color)| resolve_color(design_system, color).is_none())
+        .map(|(location, color)| {
+            (
+                location,
+                format!(
+                    "{} is neither a palette tint, an independant color nor a css color",
+                    color
+                ),
+            )
+        })
+        .collect()
+}
+
+fn invalid_colors(design_system: &DesignSystem) -> Vec<(String, String)> {
+    design_system
+        .palettes
+        .iter()
+        .flat_map(|palette| {
+            palette
+                .tints
+                .iter()
+                .map(move |tint| (palette_token_name(palette, tint), tint.color.clone()))
+        })
+        .chain(independant_color_tokens(&design_system.independant_colors))
+        .filter(|(_, color)| Rgb::parse(color).is_err())
+        .map(|(location, color)| (location, format!("{} is not a css color", color)))
+        .collect()
+}
+
+fn duplicate_space_keys(design_system: &DesignSystem) -> Vec<(String, String)> {
+    let mut space_keys: HashSet<&str> = HashSet::new();
+    design_system
+        .spaces
+        .iter()
+        .filter(|space| !space_keys.insert(&space.space_key))
+        .map(|space| {
+            (
+                format!("spaces.{}", space.space_key),
+                format!("Space {} is defined several times", space.space_key),
+            )
+        })
+        .collect()
+}
+
+fn non_monotonic_spacing(design_system: &DesignSystem) -> Vec<(String, String)> {
+    let root_font_size = root_font_size(design_system);
+    design_system
+        .spaces
+        .windows(2)
+        .filter(|spaces| {
+            measurement_to_px(&spaces[1].space_value, root_font_size)
+                <= measurement_to_px(&spaces[0].space_value, root_font_size)
+        })
+        .map(|spaces| {
+            (
+                format!("spaces.{}", spaces[1].space_key),
+                format!(
+                    "Space {} is not larger than the previous space {}",
+                    spaces[1].space_key, spaces[0].space_key
+                ),
+            )
+        })
+        .collect()
+}
+
+fn line_heights_below_font_size(design_system: &DesignSystem) -> Vec<(String, String)> {
+    let root_font_size = root_font_size(design_system);
+    design_system
+        .typography
+        .scales()
+        .into_iter()
+        .filter(|(_, scale)| {
+            measurement_to_px(&scale.line_height, root_font_size)
+                < measurement_to_px(&scale.font_size, root_font_size)
+        })
+        .map(|(scale_name, scale)| {
+            (
+                format!("typography.{}", scale_name),
+                format!(
+                    "Line height ({}px) is smaller than the font size ({}px), the lines overlap",
+                    measurement_to_px(&scale.line_height, root_font_size),
+                    measurement_to_px(&scale.font_size, root_font_size)
+                ),
+            )
+        })
+        .collect()
+}
+
+fn missing_font_files(
+    design_system: &DesignSystem,
+    font_files: &[String],
+) -> Vec<(String, String)> {
+    design_system
+        .fonts
+        .additionals
+        .iter()
+        .filter(|font| !font_files.contains(&font.value))
+        .map(|font| {
+            (
+                format!("fonts.{}", font.font_name),
+                format!("No file for the font {} under exports/fonts", font.value),
+            )
+        })
+        .collect()
+}
+
+fn unused_palettes(design_system: &DesignSystem) -> Vec<(String, String)> {
+    let references: HashSet<String> = color_references(design_system)
+        .into_iter()
+        .map(|(_, color)| color)
+        .collect();
+    design_system
+        .palettes
+        .iter()
+        .filter(|palette| {
+            !palette
+                .tints
+                .iter()
+                .any(|tint| references.contains(&palette_token_name(palette, tint)))
+        })
+        .map(|palette| {
+            (
+                format!("palette-{}", palette.palette_name),
+                format!(
+                    "No tint of the palette {} is used by the semantic tokens, typographies or shadows",
+                    palette.palette_name
+                ),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::design_system_domain::{Measurement, UnitOfMeasurement},
+        test_fixtures::sample_design_system,
+    };
+
+    fn font_files() -> Vec<String> {
+        vec![String::from("JetBrains Mono, monospace")]
+    }
+
+    fn issues(report: &LintReport) -> Vec<(LintRule, &str)> {
+        report
+            .issues
+            .iter()
+            .map(|issue| (issue.rule, issue.location.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn sample_design_system_is_clean() {
+        let report = lint_design_system(
+            &sample_design_system(),
+            &LintConfig::default(),
+            &font_files(),
+        );
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn reports_the_issues_of_each_rule() {
+        let mut design_system = sample_design_system();
+        design_system.semantic_color_tokens.text_light = Some(String::from("palette-gray-400"));
+        design_system.independant_colors.independant_colors[0].color = String::from("#10b98");
+        design_system.spaces.push(design_system.spaces[0].clone());
+        design_system.spaces[1].space_value = Measurement {
+            unit: UnitOfMeasurement::PX,
+            value: 2.0,
+        };
+        design_system
+            .palettes
+            .push(design_system.palettes[0].clone());
+        design_system.palettes[2].palette_name = String::from("accent");
+
+        let report = lint_design_system(&design_system, &LintConfig::default(), &[]);
+        assert_eq!(
+            issues(&report),
+            vec![
+                (LintRule::UnresolvedColorReference, "base-text-light"),
+                (LintRule::InvalidColor, "color-success"),
+                (LintRule::DuplicateSpaceKey, "spaces.1"),
+                (LintRule::NonMonotonicSpacing, "spaces.2"),
+                (LintRule::MissingFontFile, "fonts.mono"),
+                (LintRule::UnusedPalette, "palette-accent"),
+            ]
+        );
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn rule_levels_come_from_the_config() {
+        let mut design_system = sample_design_system();
+        design_system.semantic_color_tokens.text_light = Some(String::from("palette-gray-400"));
+        let mut config = LintConfig::default();
+        config
+            .rules
+            .insert(LintRule::UnresolvedColorReference, LintLevel::Warning);
+        config
+            .rules
+            .insert(LintRule::MissingFontFile, LintLevel::Off);
+
+        let report = lint_design_system(&design_system, &config, &[]);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].level, LintLevel::Warning);
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn theme_overrides_are_linted_once() {
+        let mut design_system = sample_design_system();
+        let overrides = &mut design_system.semantic_color_tokens.theme_overrides;
+        overrides.get_mut("dark").unwrap().text_default = Some(String::from("palette-gray-950"));
+
+        let report = lint_design_system(&design_system, &LintConfig::default(), &font_files());
+        assert_eq!(
+            issues(&report),
+            vec![(
+                LintRule::UnresolvedColorReference,
+                "themes.dark.base-text-default"
+            )]
+        );
+    }
+}
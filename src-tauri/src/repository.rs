@@ -12,6 +12,7 @@ use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::SystemTime;
 
 use crate::domain::fonts_domain::FONTS_EXTENSIONS;
 use crate::domain::image_domain::ImageLocal;
@@ -39,7 +40,7 @@ pub struct FetchPath {
     pub fetch_pathbuf: PathBuf,
 }
 
-fn filename_equals(path: &PathBuf, filename: &str) -> bool {
+fn filename_equals(path: &Path, filename: &str) -> bool {
     // `file_name()` renvoie un `Option<&OsStr>`
     // On utilise `and_then` pour convertir en `Option<&str>`
     path.file_name()
@@ -48,22 +49,22 @@ fn filename_equals(path: &PathBuf, filename: &str) -> bool {
         .unwrap_or(false)
 }
 
-pub fn compute_fetch_pathbuf(original_pathbuf: &PathBuf) -> FetchPath {
-    let tmp_path: PathBuf = original_pathbuf.clone().join(TMP_PATH);
+pub fn compute_fetch_pathbuf(original_pathbuf: &Path) -> FetchPath {
+    let tmp_path: PathBuf = original_pathbuf.join(TMP_PATH);
     let metadata_path: PathBuf = tmp_path.join(DESIGN_SYSTEM_METADATA_PATH);
     let fetch_pathbuf: PathBuf = if tmp_path.is_dir() && metadata_path.is_file() {
         tmp_path
     } else {
-        original_pathbuf.clone()
+        original_pathbuf.to_path_buf()
     };
 
     FetchPath {
         fetch_pathbuf,
-        original_pathbuf: original_pathbuf.clone(),
+        original_pathbuf: original_pathbuf.to_path_buf(),
     }
 }
 
-pub fn compute_path(directory: &PathBuf, filename: &str) -> PathBuf {
+pub fn compute_path(directory: &Path, filename: &str) -> PathBuf {
     let mut file_path = directory.join(filename.replace(" ", "-"));
 
     if file_path.exists() {
@@ -77,11 +78,7 @@ pub fn compute_path(directory: &PathBuf, filename: &str) -> PathBuf {
     file_path
 }
 
-pub fn compute_path_with_extension(
-    directory: &PathBuf,
-    filename: &str,
-    extension: &str,
-) -> PathBuf {
+pub fn compute_path_with_extension(directory: &Path, filename: &str, extension: &str) -> PathBuf {
     let mut file_path = directory.join(filename.replace(" ", "-"));
 
     // Ajouter l'extension
@@ -102,7 +99,7 @@ pub fn compute_path_with_extension(
 }
 
 pub fn compute_path_with_extension_overwrite(
-    directory: &PathBuf,
+    directory: &Path,
     filename: &str,
     extension: &str,
 ) -> PathBuf {
@@ -119,6 +116,7 @@ where
 
     let mut file = File::create(path)?;
     file.write_all(yaml_data.as_bytes())?;
+    file.sync_all()?;
     Ok(())
 }
 
@@ -145,7 +143,7 @@ where
     T: DeserializeOwned,
 {
     let mut file =
-        File::open(&pathbuf).context(format!("Failed to open file '{}'", pathbuf.display()))?;
+        File::open(pathbuf).context(format!("Failed to open file '{}'", pathbuf.display()))?;
 
     let mut contents = String::new();
     file.read_to_string(&mut contents)
@@ -169,11 +167,9 @@ fn fetch_image_folder(path: &PathBuf) -> Result<Vec<String>> {
                     if ["png", "jpg", "jpeg", "gif"].contains(&ext_lower.as_str()) {
                         let canonical_path: String =
                             std::fs::canonicalize(&path)?.to_string_lossy().to_string();
-                        let normalized_path: &str = if canonical_path.starts_with(r"\\?\") {
-                            &canonical_path[4..]
-                        } else {
-                            &canonical_path
-                        };
+                        let normalized_path: &str = canonical_path
+                            .strip_prefix(r"\\?\")
+                            .unwrap_or(&canonical_path);
                         images.push(normalized_path.to_string());
                     }
                 }
@@ -187,7 +183,7 @@ pub fn encode_image_base64(path: String) -> Result<ImageLocal> {
     let pathbuf = Path::new(&path);
     match fs::read(pathbuf) {
         Ok(bytes) => Ok(ImageLocal {
-            path: path,
+            path,
             binary: format!("data:image/png;base64,{}", STANDARD.encode(&bytes)),
         }),
         Err(err) => Err(anyhow!("Erreur lors du chargement de l'image : {}", err)),
@@ -202,7 +198,7 @@ pub fn copy_file(original_file: &PathBuf, destination_folder: &PathBuf) -> Resul
 
     // Vérifie si le dossier de destination existe, sinon le créer
     if !destination_folder.exists() {
-        fs::create_dir_all(&destination_folder)?;
+        fs::create_dir_all(destination_folder)?;
     }
 
     // Récupère le nom du fichier d'origine
@@ -214,12 +210,21 @@ pub fn copy_file(original_file: &PathBuf, destination_folder: &PathBuf) -> Resul
     let destination_path: PathBuf = destination_folder.join(file_name);
 
     // Copie le fichier vers le dossier de destination
-    fs::copy(&original_file, &destination_path)?;
+    fs::copy(original_file, &destination_path)?;
 
     let string_path = destination_path.to_str().ok_or_else(|| anyhow!("fail"))?;
     Ok(String::from(string_path))
 }
 
+pub fn read_file_to_string<P: AsRef<Path>>(path: P) -> Result<String> {
+    fs::read_to_string(&path)
+        .with_context(|| format!("Fail to read file '{}'", path.as_ref().display()))
+}
+
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    fs::read(&path).with_context(|| format!("Fail to read file '{}'", path.as_ref().display()))
+}
+
 pub fn assert_file_in_directory(filepath: &String, folder: &PathBuf) -> Result<String> {
     let file_path = PathBuf::from(filepath);
 
@@ -275,8 +280,10 @@ pub fn open_folder<P: AsRef<Path>>(path: P) -> Result<()> {
 /// Retourne une chaîne « data:image/png;base64,… » prête à être insérée dans du HTML/CSS.
 pub fn svg_to_png_b64(svg: &str, dpi: f32, fonts_repository: Option<PathBuf>) -> Result<String> {
     /*─── 1. Préparation des options + bases de polices ────────────────*/
-    let mut opt = Options::default();
-    opt.dpi = dpi;
+    let mut opt = Options {
+        dpi,
+        ..Default::default()
+    };
 
     // Chargement des polices système par défaut
     opt.fontdb_mut().load_system_fonts();
@@ -395,6 +402,41 @@ pub fn get_file_metadata<P: AsRef<Path>>(path: P) -> Option<FileMetadata> {
     })
 }
 
+/// Métadonnées d'un dossier d'export (ex: tokens/ de Style Dictionary).
+/// - `filename` : le nom du dossier
+/// - `update_date` : date de modification du fichier le plus récent du dossier (RFC 3339)
+/// - `None` si le dossier n'existe pas ou ne contient aucun fichier.
+pub fn get_folder_metadata<P: AsRef<Path>>(path: P) -> Option<FileMetadata> {
+    let path = path.as_ref();
+    if !path.is_dir() {
+        return None;
+    }
+
+    let modified = latest_modification(path)?;
+    let datetime: DateTime<Local> = modified.into();
+    let filename = path.file_name()?.to_string_lossy().into_owned();
+
+    Some(FileMetadata {
+        filename,
+        update_date: datetime.to_rfc3339(),
+    })
+}
+
+fn latest_modification(path: &Path) -> Option<SystemTime> {
+    fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                latest_modification(&entry_path)
+            } else {
+                entry.metadata().ok()?.modified().ok()
+            }
+        })
+        .max()
+}
+
 pub fn get_file_date<P: AsRef<Path>>(path: P) -> Result<String> {
     let path = path.as_ref();
 
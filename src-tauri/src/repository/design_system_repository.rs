@@ -7,16 +7,22 @@ use std::{
 
 use anyhow::{anyhow, Context, Result};
 use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Local};
+use serde::de::DeserializeOwned;
 
 use crate::domain::design_system_domain::{ExportsMetadata, IndependantColors};
+use crate::domain::export_domain::ExportFile;
+use crate::domain::lint_domain::LintConfig;
+use crate::domain::version_domain::{validate_version_name, DesignSystemVersion};
 use crate::repository::{
-    compute_path_with_extension_overwrite, get_file_date, get_file_metadata, open_folder,
+    compute_path_with_extension_overwrite, fonts_repository::load_design_system_fonts,
+    get_file_date, get_file_metadata, get_folder_metadata, open_folder,
 };
 use crate::{
     domain::design_system_domain::{
         DesignSystem, DesignSystemMetadata, DesignSystemMetadataFile, ExportPayload, Fonts,
-        Palette, PalettesMetadataFile, Radius, SemanticColorTokens, Shadows, Space, SpacesFile,
-        Themes, TintsFile, Typographies,
+        Palette, PalettesMetadataFile, Radius, SaveJournalFile, SemanticColorTokens, Shadows,
+        Space, SpacesFile, Themes, TintsFile, Typographies,
     },
     repository::{
         compute_fetch_pathbuf, compute_path_with_extension, filename_equals, FetchPath, TMP_PATH,
@@ -42,11 +48,43 @@ const PREVIEW_IMAGES_PATH: &str = "preview_images";
 const SEMANTIC_COLOR_TOKENS_PATH: &str = "semantic_color_tokens.yaml";
 const INDEPENDANT_COLORS_PATH: &str = "independant_colors.yaml";
 const README_PATH: &str = "README.md";
-const EXPORT_STYLESHEET_PATH: &str = "export-stylesheet.css";
-const EXPORT_FIGMA_PATH: &str = "export-figma-token-studio.json";
+const CHANGELOG_PATH: &str = "CHANGELOG.md";
+const LINT_PATH: &str = "lint.yaml";
+/// A save is written in `.pending_save/staging`, then swapped with the previous files (moved to
+/// `.pending_save/backup`) once the journal is written
+const PENDING_SAVE_PATH: &str = ".pending_save";
+const PENDING_SAVE_STAGING_PATH: &str = "staging";
+const PENDING_SAVE_BACKUP_PATH: &str = "backup";
+const PENDING_SAVE_JOURNAL_PATH: &str = "journal.yaml";
+const VERSIONS_PATH: &str = "versions";
+const VERSION_PATH: &str = "version.yaml";
+/// Files & folders written by a save
+const SAVED_ENTRIES: [&str; 10] = [
+    DESIGN_SYSTEM_METADATA_PATH,
+    PALETTES_PATH,
+    INDEPENDANT_COLORS_PATH,
+    FONTS_PATH,
+    TYPOGRAPHY_PATH,
+    SPACES_PATH,
+    RADIUS_PATH,
+    EFFECTS_PATH,
+    THEMELIST_PATH,
+    SEMANTIC_COLOR_TOKENS_PATH,
+];
+pub const EXPORT_STYLESHEET_PATH: &str = "export-stylesheet.css";
+pub const EXPORT_FIGMA_PATH: &str = "export-figma-token-studio.json";
+pub const EXPORT_DTCG_PATH: &str = "export-design-tokens.tokens.json";
+pub const EXPORT_STYLE_DICTIONARY_PATH: &str = "tokens";
+pub const EXPORT_SCSS_PATH: &str = "_tokens.scss";
+pub const EXPORT_LESS_PATH: &str = "tokens.less";
+pub const EXPORT_TAILWIND_CONFIG_PATH: &str = "tailwind.config.js";
+pub const EXPORT_TAILWIND_THEME_PATH: &str = "tailwind-theme.css";
+pub const EXPORT_ANDROID_PATH: &str = "android";
+pub const EXPORT_IOS_PATH: &str = "ios";
+pub const EXPORT_SWATCHES_PATH: &str = "swatches";
 
 pub fn create_design_system(design_system_metadata: &mut DesignSystemMetadata) -> Result<()> {
-    println!(
+    eprintln!(
         "Create new design system : {:?}",
         &design_system_metadata.design_system_name
     );
@@ -55,17 +93,19 @@ pub fn create_design_system(design_system_metadata: &mut DesignSystemMetadata) -
         .design_system_path
         .join(DESIGN_SYSTEM_METADATA_PATH);
 
-    let banner: String = insert_image(
-        &design_system_metadata.banner,
-        &design_system_metadata.design_system_path,
-    )?;
-    let logo: String = insert_image(
-        &design_system_metadata.logo,
-        &design_system_metadata.design_system_path,
-    )?;
-
-    design_system_metadata.banner = banner;
-    design_system_metadata.logo = logo;
+    //Without banner / logo (headless creation), the presets are applied on the first opening
+    if !design_system_metadata.banner.is_empty() {
+        design_system_metadata.banner = insert_image(
+            &design_system_metadata.banner,
+            &design_system_metadata.design_system_path,
+        )?;
+    }
+    if !design_system_metadata.logo.is_empty() {
+        design_system_metadata.logo = insert_image(
+            &design_system_metadata.logo,
+            &design_system_metadata.design_system_path,
+        )?;
+    }
     let design_system_file: DesignSystemMetadataFile =
         DesignSystemMetadataFile::from(design_system_metadata);
     save_to_yaml_file(design_system_metadata_path, &design_system_file)?;
@@ -73,20 +113,20 @@ pub fn create_design_system(design_system_metadata: &mut DesignSystemMetadata) -
     Ok(())
 }
 
-pub fn get_images_path(design_system_path: &PathBuf) -> PathBuf {
+pub fn get_images_path(design_system_path: &Path) -> PathBuf {
     design_system_path.join(IMAGES_PATH)
 }
 
-pub fn find_design_system_metadata(design_system_path: &PathBuf) -> Result<DesignSystemMetadata> {
+pub fn find_design_system_metadata(design_system_path: &Path) -> Result<DesignSystemMetadata> {
     let FetchPath {
         fetch_pathbuf,
         original_pathbuf,
-    } = compute_fetch_pathbuf(&design_system_path);
+    } = compute_fetch_pathbuf(design_system_path);
 
     let design_system_metadata_pathbuf: PathBuf = fetch_pathbuf.join(DESIGN_SYSTEM_METADATA_PATH);
 
     if !design_system_metadata_pathbuf.is_file() {
-        println!(
+        eprintln!(
             "Design system not found : {:?} (to remove)",
             design_system_metadata_pathbuf
         );
@@ -94,8 +134,8 @@ pub fn find_design_system_metadata(design_system_path: &PathBuf) -> Result<Desig
     let file: DesignSystemMetadataFile =
         load_yaml_from_pathbuf::<DesignSystemMetadataFile>(&design_system_metadata_pathbuf)?;
 
-    let exports: ExportsMetadata = fetch_exports_metadata(&design_system_path)?;
-    let update_date = get_file_date(&design_system_path.join(DESIGN_SYSTEM_METADATA_PATH))?;
+    let exports: ExportsMetadata = fetch_exports_metadata(design_system_path)?;
+    let update_date = get_file_date(design_system_path.join(DESIGN_SYSTEM_METADATA_PATH))?;
     Ok(DesignSystemMetadata::from(
         &file,
         &original_pathbuf,
@@ -109,15 +149,19 @@ pub fn find_design_system_metadata(design_system_path: &PathBuf) -> Result<Desig
     ))
 }
 
-pub fn fetch_palettes(design_system_path: &PathBuf) -> Result<Vec<Palette>> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_palettes(design_system_path: &Path) -> Result<Vec<Palette>> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let palettes_path: PathBuf = fetch_pathbuf.join(PALETTES_PATH);
     if !&palettes_path.is_dir() {
-        println!("Fail to fetch color palettes");
+        eprintln!("Fail to fetch color palettes");
         return Err(anyhow!("Fail to fetch color palettes"));
     }
+    read_palettes(&palettes_path)
+}
 
-    let read_dir = fs::read_dir(&palettes_path)?;
+/// Palettes of a `palettes` folder, in the order of its metadata file
+fn read_palettes(palettes_path: &Path) -> Result<Vec<Palette>> {
+    let read_dir = fs::read_dir(palettes_path)?;
 
     let mut color_palettes: Vec<Palette> = read_dir
         .into_iter()
@@ -162,13 +206,86 @@ pub fn fetch_palettes(design_system_path: &PathBuf) -> Result<Vec<Palette>> {
     Ok(color_palettes)
 }
 
-pub fn init_palettes(design_system_path: &PathBuf) -> Result<()> {
+/// Design system saved in `design_system_path` (never its tmp copy) without writing anything : an
+/// interrupted save is not recovered and the missing files take their default value
+pub fn read_saved_design_system(design_system_path: &Path) -> Result<DesignSystem> {
+    if design_system_path
+        .join(PENDING_SAVE_PATH)
+        .join(PENDING_SAVE_JOURNAL_PATH)
+        .is_file()
+    {
+        return Err(anyhow!(
+            "A save of {:?} was interrupted, open the design system in uitea to recover it",
+            design_system_path
+        ));
+    }
+    let design_system_pathbuf: PathBuf = design_system_path.to_path_buf();
+    let metadata_file: DesignSystemMetadataFile =
+        load_yaml_from_pathbuf(&design_system_pathbuf.join(DESIGN_SYSTEM_METADATA_PATH))?;
+    let mut metadata: DesignSystemMetadata = DesignSystemMetadata::from(
+        &metadata_file,
+        &design_system_pathbuf,
+        false,
+        &get_images_path(&design_system_pathbuf),
+        fetch_exports_metadata(&design_system_pathbuf)?,
+        get_file_date(design_system_pathbuf.join(DESIGN_SYSTEM_METADATA_PATH))?,
+    );
+    metadata.fonts = load_design_system_fonts(&design_system_pathbuf)?;
+
+    let palettes_path: PathBuf = design_system_pathbuf.join(PALETTES_PATH);
+    Ok(DesignSystem {
+        metadata,
+        palettes: if palettes_path.is_dir() {
+            read_palettes(&palettes_path)?
+        } else {
+            vec![]
+        },
+        fonts: read_yaml_file_or(design_system_pathbuf.join(FONTS_PATH), Fonts::new)?,
+        typography: read_yaml_file_or(
+            design_system_pathbuf.join(TYPOGRAPHY_PATH),
+            Typographies::new,
+        )?,
+        spaces: SpacesFile::to(&read_yaml_file_or(
+            design_system_pathbuf.join(SPACES_PATH),
+            SpacesFile::new,
+        )?),
+        radius: read_yaml_file_or(design_system_pathbuf.join(RADIUS_PATH), Radius::new)?,
+        shadows: read_yaml_file_or(design_system_pathbuf.join(EFFECTS_PATH), || {
+            vec![Shadows::new()]
+        })?,
+        themes: read_yaml_file_or(design_system_pathbuf.join(THEMELIST_PATH), || Themes {
+            main_theme: None,
+            other_themes: vec![],
+        })?,
+        semantic_color_tokens: read_yaml_file_or(
+            design_system_pathbuf.join(SEMANTIC_COLOR_TOKENS_PATH),
+            SemanticColorTokens::new,
+        )?,
+        independant_colors: read_yaml_file_or(
+            design_system_pathbuf.join(INDEPENDANT_COLORS_PATH),
+            IndependantColors::new,
+        )?,
+    })
+}
+
+/// Content of a yaml file, `default` when the file is missing
+fn read_yaml_file_or<T: DeserializeOwned>(
+    pathbuf: PathBuf,
+    default: impl FnOnce() -> T,
+) -> Result<T> {
+    if !pathbuf.is_file() {
+        return Ok(default());
+    }
+    load_yaml_from_pathbuf::<T>(&pathbuf)
+}
+
+pub fn init_palettes(design_system_path: &Path) -> Result<()> {
     let palettes_path: PathBuf = design_system_path.join(PALETTES_PATH);
     fs::create_dir_all(&palettes_path)?;
     Ok(())
 }
 
-pub fn save_palettes(design_system: &DesignSystem, design_system_path: &PathBuf) -> Result<()> {
+pub fn save_palettes(design_system: &DesignSystem, design_system_path: &Path) -> Result<()> {
     //Save colors
     let palettes_path: PathBuf = design_system_path.join(PALETTES_PATH);
     if palettes_path.is_dir() {
@@ -179,7 +296,7 @@ pub fn save_palettes(design_system: &DesignSystem, design_system_path: &PathBuf)
     //Palettes
     for color_palette in &design_system.palettes {
         let color_palette_pathbuf: PathBuf =
-            compute_path_with_extension(&palettes_path, &color_palette.palette_name, &"yaml");
+            compute_path_with_extension(&palettes_path, &color_palette.palette_name, "yaml");
         let shades_file: TintsFile = TintsFile::from(&color_palette.tints);
         save_to_yaml_file(color_palette_pathbuf, &shades_file)?;
     }
@@ -188,7 +305,7 @@ pub fn save_palettes(design_system: &DesignSystem, design_system_path: &PathBuf)
         .palettes
         .clone()
         .into_iter()
-        .map(|palette| return palette.palette_name)
+        .map(|palette| palette.palette_name)
         .collect::<Vec<String>>();
 
     let palettes_metadata_file: PalettesMetadataFile = PalettesMetadataFile { palettes_order };
@@ -215,7 +332,7 @@ pub fn save_design_system(design_system: &mut DesignSystem, is_tmp: bool) -> Res
         design_system.metadata.clone().design_system_path
     };
 
-    println!("save is tmp: {:?}, path: {:?}", is_tmp, design_system_path);
+    eprintln!("save is tmp: {:?}, path: {:?}", is_tmp, design_system_path);
 
     if !design_system_path.is_dir() {
         return Err(anyhow!(
@@ -224,55 +341,279 @@ pub fn save_design_system(design_system: &mut DesignSystem, is_tmp: bool) -> Res
         ));
     }
 
-    //Save metadata
-    save_metadata(&design_system_path, &design_system.metadata)?;
+    // Leftover of a crash, the previous files are put back before saving over them
+    rollback_pending_save(&design_system_path)?;
+    let pending_save_path: PathBuf = design_system_path.join(PENDING_SAVE_PATH);
+    if let Err(error) = stage_design_system(design_system, &design_system_path) {
+        fs::remove_dir_all(&pending_save_path)?;
+        return Err(error);
+    }
+    if let Err(error) = commit_pending_save(&design_system_path) {
+        rollback_pending_save(&design_system_path)?;
+        return Err(error);
+    }
 
-    save_palettes(&design_system, &design_system_path)?;
+    //Once the save is complete (when is not a tmp save) -> remove the tmp copy
+    if !is_tmp {
+        let tmp_pathbuf: PathBuf = design_system
+            .metadata
+            .clone()
+            .design_system_path
+            .join(TMP_PATH);
+        if tmp_pathbuf.is_dir() {
+            fs::remove_dir_all(tmp_pathbuf)?;
+        }
+    }
+    design_system.metadata.is_tmp = is_tmp;
 
-    let independant_colors_pathbuf: PathBuf = design_system_path.join(INDEPENDANT_COLORS_PATH);
+    Ok(design_system)
+}
+
+/// Write every file of the design system in the staging folder of the pending save
+fn stage_design_system(design_system: &DesignSystem, design_system_path: &PathBuf) -> Result<()> {
+    let staging_path: PathBuf = design_system_path
+        .join(PENDING_SAVE_PATH)
+        .join(PENDING_SAVE_STAGING_PATH);
+    fs::create_dir_all(&staging_path)?;
+
+    //Save metadata (the images are inserted in the design system folder)
+    remove_empty_temp(design_system_path)?;
+    save_to_yaml_file(
+        staging_path.join(DESIGN_SYSTEM_METADATA_PATH),
+        &metadata_file(design_system_path, &design_system.metadata)?,
+    )?;
+
+    save_palettes(design_system, &staging_path)?;
+
+    let independant_colors_pathbuf: PathBuf = staging_path.join(INDEPENDANT_COLORS_PATH);
     save_to_yaml_file(
         independant_colors_pathbuf,
         &design_system.independant_colors,
     )?;
 
-    let fonts_pathbuf: PathBuf = design_system_path.join(FONTS_PATH);
+    let fonts_pathbuf: PathBuf = staging_path.join(FONTS_PATH);
     save_to_yaml_file(fonts_pathbuf, &design_system.fonts)?;
 
-    let typography_pathbuf: PathBuf = design_system_path.join(TYPOGRAPHY_PATH);
+    let typography_pathbuf: PathBuf = staging_path.join(TYPOGRAPHY_PATH);
     save_to_yaml_file(typography_pathbuf, &design_system.typography)?;
 
-    save_spaces(&design_system.spaces, &design_system_path)?;
+    save_spaces(&design_system.spaces, &staging_path)?;
 
-    let radius_pathbuf: PathBuf = design_system_path.join(RADIUS_PATH);
+    let radius_pathbuf: PathBuf = staging_path.join(RADIUS_PATH);
     save_to_yaml_file(radius_pathbuf, &design_system.radius)?;
 
-    let effect_pathbuf: PathBuf = design_system_path.join(EFFECTS_PATH);
+    let effect_pathbuf: PathBuf = staging_path.join(EFFECTS_PATH);
     save_to_yaml_file(effect_pathbuf, &design_system.shadows)?;
 
-    let themes_pathbuf: PathBuf = design_system_path.join(THEMELIST_PATH);
+    let themes_pathbuf: PathBuf = staging_path.join(THEMELIST_PATH);
     save_to_yaml_file(themes_pathbuf, &design_system.themes)?;
 
-    let semantic_color_tokens_pathbuf: PathBuf =
-        design_system_path.join(SEMANTIC_COLOR_TOKENS_PATH);
+    let semantic_color_tokens_pathbuf: PathBuf = staging_path.join(SEMANTIC_COLOR_TOKENS_PATH);
     save_to_yaml_file(
         semantic_color_tokens_pathbuf,
         &design_system.semantic_color_tokens,
-    )?;
+    )
+}
 
-    //Once the save is complete (when is not a tmp save) -> remove the tmp copy
-    if !is_tmp {
-        let tmp_pathbuf: PathBuf = design_system
-            .metadata
-            .clone()
-            .design_system_path
-            .join(TMP_PATH);
-        if tmp_pathbuf.is_dir() {
-            fs::remove_dir_all(tmp_pathbuf)?;
+/// Swap the staged files with the previous ones. The save is committed once the journal is
+/// removed, before that a crash is rolled back by `rollback_pending_save`
+fn commit_pending_save(design_system_path: &Path) -> Result<()> {
+    let pending_save_path: PathBuf = design_system_path.join(PENDING_SAVE_PATH);
+    let staging_path: PathBuf = pending_save_path.join(PENDING_SAVE_STAGING_PATH);
+    let backup_path: PathBuf = pending_save_path.join(PENDING_SAVE_BACKUP_PATH);
+    let journal_path: PathBuf = pending_save_path.join(PENDING_SAVE_JOURNAL_PATH);
+    fs::create_dir_all(&backup_path)?;
+
+    // The journal is renamed in place so that it is never read half written
+    let journal = SaveJournalFile {
+        new_entries: SAVED_ENTRIES
+            .iter()
+            .filter(|entry| !design_system_path.join(entry).exists())
+            .map(|entry| String::from(*entry))
+            .collect(),
+    };
+    let journal_tmp_path: PathBuf = journal_path.with_extension("tmp");
+    save_to_yaml_file(&journal_tmp_path, &journal)?;
+    fs::rename(&journal_tmp_path, &journal_path)?;
+
+    for entry in SAVED_ENTRIES {
+        let entry_path: PathBuf = design_system_path.join(entry);
+        if entry_path.exists() {
+            fs::rename(&entry_path, backup_path.join(entry))?;
         }
+        fs::rename(staging_path.join(entry), &entry_path)?;
     }
-    design_system.metadata.is_tmp = is_tmp;
 
-    Ok(design_system)
+    fs::remove_file(&journal_path)?;
+    fs::remove_dir_all(&pending_save_path)?;
+    Ok(())
+}
+
+/// Undo a save interrupted by a crash or an error. Return whether there was a pending save
+fn rollback_pending_save(design_system_path: &Path) -> Result<bool> {
+    let pending_save_path: PathBuf = design_system_path.join(PENDING_SAVE_PATH);
+    if !pending_save_path.is_dir() {
+        return Ok(false);
+    }
+
+    // Without journal, the save was interrupted while staging : the files were not touched yet
+    let journal_path: PathBuf = pending_save_path.join(PENDING_SAVE_JOURNAL_PATH);
+    if journal_path.is_file() {
+        let journal: SaveJournalFile = load_yaml_from_pathbuf::<SaveJournalFile>(&journal_path)?;
+        let backup_path: PathBuf = pending_save_path.join(PENDING_SAVE_BACKUP_PATH);
+        for entry in SAVED_ENTRIES {
+            let entry_path: PathBuf = design_system_path.join(entry);
+            let backup_entry_path: PathBuf = backup_path.join(entry);
+            if backup_entry_path.exists() {
+                remove_entry(&entry_path)?;
+                fs::rename(&backup_entry_path, &entry_path)?;
+            } else if journal.new_entries.contains(&entry.to_string()) {
+                remove_entry(&entry_path)?;
+            }
+        }
+    }
+    fs::remove_dir_all(&pending_save_path)?;
+    Ok(true)
+}
+
+fn remove_entry(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Restore the previous version of the design system (and of its tmp copy) when a save was
+/// interrupted
+pub fn recover_interrupted_save(design_system_path: &Path) -> Result<()> {
+    for path in [
+        design_system_path.to_path_buf(),
+        design_system_path.join(TMP_PATH),
+    ] {
+        if rollback_pending_save(&path)? {
+            eprintln!("Interrupted save rolled back : {:?}", path);
+        }
+    }
+    Ok(())
+}
+
+/// Copy the saved files of the design system (without the unsaved changes of the tmp copy) under
+/// `versions/{name}`, the version file is written last so that a partial copy is not listed
+pub fn create_version(
+    design_system_path: &Path,
+    version_name: &str,
+    description: Option<String>,
+) -> Result<DesignSystemVersion> {
+    validate_version_name(version_name)?;
+    let version_path: PathBuf = design_system_path.join(VERSIONS_PATH).join(version_name);
+    if version_path.exists() {
+        return Err(anyhow!("The version {} already exists", version_name));
+    }
+    let version = DesignSystemVersion {
+        name: String::from(version_name),
+        description,
+        update_date: Local::now().to_rfc3339(),
+    };
+    fs::create_dir_all(&version_path)?;
+    let copied: Result<()> = copy_saved_entries(design_system_path, &version_path)
+        .and_then(|_| save_to_yaml_file(version_path.join(VERSION_PATH), &version));
+    if let Err(error) = copied {
+        // Best effort cleanup, the copy error is the one reported
+        let _ = fs::remove_dir_all(&version_path);
+        return Err(error);
+    }
+    Ok(version)
+}
+
+/// Versions of the design system, oldest first
+pub fn fetch_versions(design_system_path: &Path) -> Result<Vec<DesignSystemVersion>> {
+    let versions_path: PathBuf = design_system_path.join(VERSIONS_PATH);
+    if !versions_path.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut versions: Vec<DesignSystemVersion> = fs::read_dir(&versions_path)?
+        .filter_map(|dir_entry| {
+            let version_file: PathBuf = dir_entry.ok()?.path().join(VERSION_PATH);
+            load_yaml_from_pathbuf::<DesignSystemVersion>(&version_file).ok()
+        })
+        .collect();
+    // The dates keep the local offset of their creation, they are compared as instants
+    versions.sort_by_key(|version| DateTime::parse_from_rfc3339(&version.update_date).ok());
+    Ok(versions)
+}
+
+/// Folder of a version, it can be loaded as a design system
+pub fn get_version_path(design_system_path: &Path, version_name: &str) -> Result<PathBuf> {
+    validate_version_name(version_name)?;
+    let version_path: PathBuf = design_system_path.join(VERSIONS_PATH).join(version_name);
+    if !version_path.join(VERSION_PATH).is_file() {
+        return Err(anyhow!("Version not found : {}", version_name));
+    }
+    Ok(version_path)
+}
+
+/// Replace the files of the design system by the ones of a version (as a save), the unsaved changes
+/// are dropped
+pub fn restore_version(design_system_path: &Path, version_name: &str) -> Result<()> {
+    let version_path: PathBuf = get_version_path(design_system_path, version_name)?;
+    rollback_pending_save(design_system_path)?;
+    let pending_save_path: PathBuf = design_system_path.join(PENDING_SAVE_PATH);
+    let staging_path: PathBuf = pending_save_path.join(PENDING_SAVE_STAGING_PATH);
+    fs::create_dir_all(&staging_path)?;
+    // A version without `themes.yaml` is restored without themes
+    let staged: Result<()> = copy_saved_entries(&version_path, &staging_path).and_then(|_| {
+        let themes_path: PathBuf = staging_path.join(THEMELIST_PATH);
+        if themes_path.exists() {
+            return Ok(());
+        }
+        save_to_yaml_file(themes_path, &fetch_themes(&version_path))
+    });
+    if let Err(error) = staged {
+        let _ = fs::remove_dir_all(&pending_save_path);
+        return Err(error);
+    }
+    if let Err(error) = commit_pending_save(design_system_path) {
+        let _ = rollback_pending_save(design_system_path);
+        return Err(error);
+    }
+
+    let tmp_pathbuf: PathBuf = design_system_path.join(TMP_PATH);
+    if tmp_pathbuf.is_dir() {
+        fs::remove_dir_all(tmp_pathbuf)?;
+    }
+    Ok(())
+}
+
+/// Copy the saved files of a design system, the entries never saved (`themes.yaml` of a design
+/// system saved before the themes) are skipped
+fn copy_saved_entries(from: &Path, to: &Path) -> Result<()> {
+    SAVED_ENTRIES
+        .iter()
+        .filter(|entry| from.join(entry).exists())
+        .try_for_each(|entry| copy_entry(&from.join(entry), &to.join(entry)))
+}
+
+/// Copy a file or a folder (recursively)
+fn copy_entry(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for dir_entry in fs::read_dir(from)? {
+            let dir_entry = dir_entry?;
+            copy_entry(&dir_entry.path(), &to.join(dir_entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to).with_context(|| format!("Fail to copy {:?}", from))?;
+    }
+    Ok(())
+}
+
+/// Write the `CHANGELOG.md` of the design system, next to its README
+pub fn save_changelog(design_system_path: &Path, changelog: &str) -> Result<()> {
+    let mut file = File::create(design_system_path.join(CHANGELOG_PATH))?;
+    file.write_all(changelog.as_bytes())?;
+    Ok(())
 }
 
 pub fn save_readme(metadata: DesignSystemMetadata) -> Result<()> {
@@ -303,15 +644,26 @@ pub fn save_readme(metadata: DesignSystemMetadata) -> Result<()> {
 }
 
 pub fn save_metadata(design_system_path: &PathBuf, metadata: &DesignSystemMetadata) -> Result<()> {
-    remove_empty_temp(&design_system_path)?;
-    let images_path: PathBuf = get_images_path(&design_system_path);
+    remove_empty_temp(design_system_path)?;
+    let design_system_metadata_path: PathBuf = design_system_path.join(DESIGN_SYSTEM_METADATA_PATH);
+    save_to_yaml_file(
+        design_system_metadata_path,
+        &metadata_file(design_system_path, metadata)?,
+    )
+}
+
+/// Metadata file, the banner & logo outside of the images folder are inserted in it
+fn metadata_file(
+    design_system_path: &PathBuf,
+    metadata: &DesignSystemMetadata,
+) -> Result<DesignSystemMetadataFile> {
+    let images_path: PathBuf = get_images_path(design_system_path);
     let banner = match assert_file_in_directory(&metadata.banner, &images_path) {
         Ok(path) => path,
         Err(_) => {
             let banner_pathbuf = PathBuf::from(&metadata.banner);
             if banner_pathbuf.is_file() {
-                let image = insert_image(&metadata.banner, &design_system_path)?;
-                image
+                insert_image(&metadata.banner, design_system_path)?
             } else {
                 String::new()
             }
@@ -323,66 +675,59 @@ pub fn save_metadata(design_system_path: &PathBuf, metadata: &DesignSystemMetada
         Err(_) => {
             let logo_pathbuf = PathBuf::from(&metadata.logo);
             if logo_pathbuf.is_file() {
-                let image = insert_image(&metadata.logo, &design_system_path)?;
-                image
+                insert_image(&metadata.logo, design_system_path)?
             } else {
                 String::new()
             }
         }
     };
 
-    //Save metadata
-    let design_system_metadata_path: PathBuf = design_system_path.join(DESIGN_SYSTEM_METADATA_PATH);
+    // Without banner / logo (headless creation), the file names are left empty
     let mut design_system_file = DesignSystemMetadataFile::from(metadata);
-    let banner_filename = Path::new(&banner)
+    design_system_file.banner = Path::new(&banner)
         .file_name()
-        .context("Fail to read banner")?
-        .to_str()
-        .context("Fail to read banner")?
+        .and_then(|filename| filename.to_str())
+        .unwrap_or_default()
         .to_owned();
-
-    let logo_filename = Path::new(&logo)
+    design_system_file.logo = Path::new(&logo)
         .file_name()
-        .context("Fail to read logo")?
-        .to_str()
-        .context("Fail to read logo")?
+        .and_then(|filename| filename.to_str())
+        .unwrap_or_default()
         .to_owned();
-    design_system_file.banner = String::from(banner_filename);
-    design_system_file.logo = String::from(logo_filename);
-    save_to_yaml_file(design_system_metadata_path, &design_system_file)
+    Ok(design_system_file)
 }
 
-pub fn fetch_fonts(design_system_path: &PathBuf) -> Result<Fonts> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_fonts(design_system_path: &Path) -> Result<Fonts> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let typo_pathbuf: PathBuf = fetch_pathbuf.join(FONTS_PATH);
     load_yaml_from_pathbuf::<Fonts>(&typo_pathbuf)
 }
 
-pub fn init_fonts(design_system_path: &PathBuf) -> Result<()> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn init_fonts(design_system_path: &Path) -> Result<()> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let typo_pathbuf: PathBuf = fetch_pathbuf.join(FONTS_PATH);
     let typo_file = Fonts::new();
     save_to_yaml_file(typo_pathbuf, &typo_file)
 }
 
-pub fn fetch_typography(design_system_path: &PathBuf) -> Result<Typographies> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_typography(design_system_path: &Path) -> Result<Typographies> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let typo_pathbuf: PathBuf = fetch_pathbuf.join(TYPOGRAPHY_PATH);
     load_yaml_from_pathbuf::<Typographies>(&typo_pathbuf)
 }
 
-pub fn init_typography(design_system_path: &PathBuf) -> Result<()> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn init_typography(design_system_path: &Path) -> Result<()> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let typo_pathbuf: PathBuf = fetch_pathbuf.join(TYPOGRAPHY_PATH);
     let typo_file = Typographies::new();
     save_to_yaml_file(typo_pathbuf, &typo_file)
 }
 
-pub fn fetch_spaces(design_system_path: &PathBuf) -> Result<Vec<Space>> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_spaces(design_system_path: &Path) -> Result<Vec<Space>> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let spaces_path = fetch_pathbuf.join(SPACES_PATH);
     if !spaces_path.is_file() {
-        println!("Fail to fetch spaces file");
+        eprintln!("Fail to fetch spaces file");
         return Err(anyhow!("spaces.yaml is missing"));
     }
 
@@ -393,7 +738,7 @@ pub fn fetch_spaces(design_system_path: &PathBuf) -> Result<Vec<Space>> {
 }
 
 pub fn init_spaces(design_system_path: &PathBuf) -> Result<()> {
-    fs::create_dir_all(&design_system_path)?;
+    fs::create_dir_all(design_system_path)?;
 
     let spaces_path: PathBuf = design_system_path.join(SPACES_PATH);
     let spaces_file: SpacesFile = SpacesFile::new();
@@ -403,57 +748,57 @@ pub fn init_spaces(design_system_path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-pub fn save_spaces(spaces: &Vec<Space>, design_system_path: &PathBuf) -> Result<()> {
+pub fn save_spaces(spaces: &[Space], design_system_path: &Path) -> Result<()> {
     let spaces_path: PathBuf = design_system_path.join(SPACES_PATH);
     let spaces_file: SpacesFile = SpacesFile::from(spaces);
     save_to_yaml_file(spaces_path, &spaces_file)?;
     Ok(())
 }
 
-pub fn fetch_radius(design_system_path: &PathBuf) -> Result<Radius> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_radius(design_system_path: &Path) -> Result<Radius> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let radius_pathbuf: PathBuf = fetch_pathbuf.join(RADIUS_PATH);
     load_yaml_from_pathbuf::<Radius>(&radius_pathbuf)
 }
 
-pub fn init_radius(design_system_path: &PathBuf) -> Result<()> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn init_radius(design_system_path: &Path) -> Result<()> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let radius_pathbuf: PathBuf = fetch_pathbuf.join(RADIUS_PATH);
     let radius_file = Radius::new();
     save_to_yaml_file(radius_pathbuf, &radius_file)
 }
 
-pub fn fetch_effects(design_system_path: &PathBuf) -> Result<Vec<Shadows>> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_effects(design_system_path: &Path) -> Result<Vec<Shadows>> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let effects_pathbuf: PathBuf = fetch_pathbuf.join(EFFECTS_PATH);
     load_yaml_from_pathbuf::<Vec<Shadows>>(&effects_pathbuf)
 }
 
-pub fn init_effects(design_system_path: &PathBuf) -> Result<()> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn init_effects(design_system_path: &Path) -> Result<()> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let effect_pathbuf: PathBuf = fetch_pathbuf.join(EFFECTS_PATH);
     let effect = Shadows::new();
     save_to_yaml_file(effect_pathbuf, &vec![effect])
 }
 
-pub fn init_images(design_system_path: &PathBuf) -> Result<()> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn init_images(design_system_path: &Path) -> Result<()> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let images_pathbuf: PathBuf = fetch_pathbuf.join(IMAGES_PATH);
     fs::create_dir(images_pathbuf)?;
     Ok(())
 }
 
 pub fn insert_image(image_path: &String, design_system_path: &PathBuf) -> Result<String> {
-    println!("insert image {}", image_path);
+    eprintln!("insert image {}", image_path);
     let images_folder: PathBuf = PathBuf::from(design_system_path).join(IMAGES_PATH);
     let image_path: PathBuf = PathBuf::from(image_path);
     if !images_folder.is_dir() {
-        init_images(&design_system_path)?;
+        init_images(design_system_path)?;
     }
     copy_file(&image_path, &images_folder)
 }
 
-pub fn is_under_design_system(path: &PathBuf) -> bool {
+pub fn is_under_design_system(path: &Path) -> bool {
     // Remonte d'un dossier
     if let Some(parent) = path.parent() {
         // Crée le chemin complet du fichier design_system_metadata.yaml
@@ -466,28 +811,37 @@ pub fn is_under_design_system(path: &PathBuf) -> bool {
     false
 }
 
-pub fn fetch_themes(design_system_path: &PathBuf) -> Themes {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_themes(design_system_path: &Path) -> Themes {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let theme_path = fetch_pathbuf.join(THEMELIST_PATH);
     if theme_path.is_file() {
         if let Result::Ok(themes) = load_yaml_from_pathbuf::<Themes>(&theme_path) {
             return themes;
         }
     }
-    return Themes {
+    Themes {
         main_theme: None,
         other_themes: vec![],
-    };
+    }
 }
 
-pub fn init_semantic_color_tokens(design_system_path: &PathBuf) -> Result<()> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+/// Lint configuration of the design system, default rule levels without `lint.yaml`
+pub fn fetch_lint_config(design_system_path: &Path) -> Result<LintConfig> {
+    let lint_pathbuf: PathBuf = design_system_path.join(LINT_PATH);
+    if !lint_pathbuf.is_file() {
+        return Ok(LintConfig::default());
+    }
+    load_yaml_from_pathbuf::<LintConfig>(&lint_pathbuf)
+}
+
+pub fn init_semantic_color_tokens(design_system_path: &Path) -> Result<()> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let semantic_color_tokens_path: PathBuf = fetch_pathbuf.join(SEMANTIC_COLOR_TOKENS_PATH);
     save_to_yaml_file(semantic_color_tokens_path, &SemanticColorTokens::new())
 }
 
-pub fn fetch_semantic_color_tokens(design_system_path: &PathBuf) -> Result<SemanticColorTokens> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_semantic_color_tokens(design_system_path: &Path) -> Result<SemanticColorTokens> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let semantic_color_tokens_path: PathBuf = fetch_pathbuf.join(SEMANTIC_COLOR_TOKENS_PATH);
     load_yaml_from_pathbuf::<SemanticColorTokens>(&semantic_color_tokens_path)
 }
@@ -509,19 +863,40 @@ pub fn register_export(payload: ExportPayload) -> Result<()> {
     Ok(())
 }
 
-pub fn init_independant_colors(design_system_path: &PathBuf) -> Result<()> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn write_export_files(
+    export_pathbuf: &Path,
+    export_files: &[ExportFile],
+) -> Result<Vec<PathBuf>> {
+    let mut written_files: Vec<PathBuf> = vec![];
+    for export_file in export_files {
+        let file_path: PathBuf = export_pathbuf.join(&export_file.path);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&file_path, &export_file.content)
+            .with_context(|| format!("Fail to write export '{}'", file_path.display()))?;
+        written_files.push(file_path);
+    }
+    Ok(written_files)
+}
+
+pub fn get_exports_path(design_system_path: &Path) -> PathBuf {
+    design_system_path.join(EXPORTS_PATH)
+}
+
+pub fn init_independant_colors(design_system_path: &Path) -> Result<()> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let independant_color_path: PathBuf = fetch_pathbuf.join(INDEPENDANT_COLORS_PATH);
     save_to_yaml_file(independant_color_path, &IndependantColors::new())
 }
 
-pub fn fetch_independant_colors(design_system_path: &PathBuf) -> Result<IndependantColors> {
-    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(&design_system_path);
+pub fn fetch_independant_colors(design_system_path: &Path) -> Result<IndependantColors> {
+    let FetchPath { fetch_pathbuf, .. } = compute_fetch_pathbuf(design_system_path);
     let independant_colors_path: PathBuf = fetch_pathbuf.join(INDEPENDANT_COLORS_PATH);
     load_yaml_from_pathbuf::<IndependantColors>(&independant_colors_path)
 }
 
-pub fn fetch_exports_metadata(design_system_path: &PathBuf) -> Result<ExportsMetadata> {
+pub fn fetch_exports_metadata(design_system_path: &Path) -> Result<ExportsMetadata> {
     Ok(ExportsMetadata {
         css: get_file_metadata(
             design_system_path
@@ -533,6 +908,30 @@ pub fn fetch_exports_metadata(design_system_path: &PathBuf) -> Result<ExportsMet
                 .join(EXPORTS_PATH)
                 .join(EXPORT_FIGMA_PATH),
         ),
+        dtcg: get_file_metadata(design_system_path.join(EXPORTS_PATH).join(EXPORT_DTCG_PATH)),
+        style_dictionary: get_folder_metadata(
+            design_system_path
+                .join(EXPORTS_PATH)
+                .join(EXPORT_STYLE_DICTIONARY_PATH),
+        ),
+        scss: get_file_metadata(design_system_path.join(EXPORTS_PATH).join(EXPORT_SCSS_PATH)),
+        less: get_file_metadata(design_system_path.join(EXPORTS_PATH).join(EXPORT_LESS_PATH)),
+        tailwind: get_file_metadata(
+            design_system_path
+                .join(EXPORTS_PATH)
+                .join(EXPORT_TAILWIND_CONFIG_PATH),
+        ),
+        android: get_folder_metadata(
+            design_system_path
+                .join(EXPORTS_PATH)
+                .join(EXPORT_ANDROID_PATH),
+        ),
+        ios: get_folder_metadata(design_system_path.join(EXPORTS_PATH).join(EXPORT_IOS_PATH)),
+        swatches: get_folder_metadata(
+            design_system_path
+                .join(EXPORTS_PATH)
+                .join(EXPORT_SWATCHES_PATH),
+        ),
         readme: get_file_metadata(design_system_path.join(README_PATH)),
     })
 }
@@ -542,18 +941,244 @@ pub fn open_export_folder(design_system_path: PathBuf) -> Result<()> {
     open_folder(export_pathbuf)
 }
 
-pub fn get_design_system_update_date(design_system_path: &PathBuf) -> Result<String> {
-    get_file_date(&design_system_path.join(DESIGN_SYSTEM_METADATA_PATH))
+/// Remove a design system folder, used to clean a creation which failed
+pub fn delete_design_system(design_system_path: &Path) -> Result<()> {
+    if design_system_path
+        .join(DESIGN_SYSTEM_METADATA_PATH)
+        .is_file()
+    {
+        fs::remove_dir_all(design_system_path)?;
+    }
+    Ok(())
+}
+
+pub fn get_design_system_update_date(design_system_path: &Path) -> Result<String> {
+    get_file_date(design_system_path.join(DESIGN_SYSTEM_METADATA_PATH))
 }
 
 
 //Sometimes there is an issue with tmp empty but not deleted. This function verify it and remove when necessary
-pub fn remove_empty_temp(design_system_path: &PathBuf) -> Result<()>{
+pub fn remove_empty_temp(design_system_path: &Path) -> Result<()>{
     let tmp_path: PathBuf = design_system_path.join(TMP_PATH);
     let tmp_metadata_path: PathBuf = tmp_path.join(DESIGN_SYSTEM_METADATA_PATH);
     if tmp_path.is_dir() && !tmp_metadata_path.is_file() {
-        println!("remove empty folder tmp");
+        eprintln!("remove empty folder tmp");
         fs::remove_dir(tmp_path)?;
     }
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::sample_design_system;
+
+    /// Sample design system in a new folder, with its banner & logo images
+    fn design_system_in_temp_dir() -> DesignSystem {
+        let design_system_path: PathBuf =
+            std::env::temp_dir().join(format!("uitea-save-{}", uuid::Uuid::new_v4()));
+        let images_path: PathBuf = get_images_path(&design_system_path);
+        fs::create_dir_all(&images_path).unwrap();
+        let mut design_system = sample_design_system();
+        for (image, name) in [
+            (&mut design_system.metadata.banner, "banner.png"),
+            (&mut design_system.metadata.logo, "logo.png"),
+        ] {
+            fs::write(images_path.join(name), b"").unwrap();
+            *image = images_path.join(name).to_string_lossy().to_string();
+        }
+        design_system.metadata.design_system_path = design_system_path;
+        design_system
+    }
+
+    fn palette_names(design_system: &DesignSystem) -> Vec<String> {
+        design_system
+            .palettes
+            .iter()
+            .map(|palette| palette.palette_name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn saved_design_system_is_read_back() {
+        let mut design_system = design_system_in_temp_dir();
+        let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+        save_design_system(&mut design_system, false).unwrap();
+        assert!(!design_system_path.join(PENDING_SAVE_PATH).exists());
+
+        let saved = read_saved_design_system(&design_system_path).unwrap();
+        assert_eq!(palette_names(&saved), vec!["primary", "gray"]);
+        assert_eq!(saved.palettes[0].tints[1].color, "#3b82f6");
+        assert_eq!(
+            saved.semantic_color_tokens.theme_overrides["dark"]
+                .text_default
+                .as_deref(),
+            Some("palette-gray-50")
+        );
+        assert_eq!(saved.spaces.len(), 2);
+        assert_eq!(saved.metadata.banner, design_system.metadata.banner);
+        fs::remove_dir_all(&design_system_path).unwrap();
+    }
+
+    #[test]
+    fn design_system_without_images_is_saved() {
+        let mut design_system = design_system_in_temp_dir();
+        let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+        design_system.metadata.banner = String::new();
+        design_system.metadata.logo = String::new();
+        save_design_system(&mut design_system, false).unwrap();
+
+        // Saved again once read back, the images folder is given as banner & logo
+        let mut saved = read_saved_design_system(&design_system_path).unwrap();
+        save_design_system(&mut saved, false).unwrap();
+        let file: DesignSystemMetadataFile =
+            load_yaml_from_pathbuf(&design_system_path.join(DESIGN_SYSTEM_METADATA_PATH)).unwrap();
+        assert_eq!((file.banner.as_str(), file.logo.as_str()), ("", ""));
+        fs::remove_dir_all(&design_system_path).unwrap();
+    }
+
+    #[test]
+    fn interrupted_commit_is_rolled_back() {
+        let mut design_system = design_system_in_temp_dir();
+        let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+        save_design_system(&mut design_system, false).unwrap();
+        fs::remove_file(design_system_path.join(RADIUS_PATH)).unwrap();
+
+        // The commit stops before the last entry is swapped, as after a crash
+        design_system.palettes.remove(0);
+        stage_design_system(&design_system, &design_system_path).unwrap();
+        let staging_path: PathBuf = design_system_path
+            .join(PENDING_SAVE_PATH)
+            .join(PENDING_SAVE_STAGING_PATH);
+        fs::remove_file(staging_path.join(SEMANTIC_COLOR_TOKENS_PATH)).unwrap();
+        assert!(commit_pending_save(&design_system_path).is_err());
+        assert!(design_system_path.join(RADIUS_PATH).is_file());
+        assert!(read_saved_design_system(&design_system_path).is_err());
+
+        recover_interrupted_save(&design_system_path).unwrap();
+        assert!(!design_system_path.join(PENDING_SAVE_PATH).exists());
+        // The entries missing before the save are removed again
+        assert!(!design_system_path.join(RADIUS_PATH).exists());
+        let saved = read_saved_design_system(&design_system_path).unwrap();
+        assert_eq!(palette_names(&saved), vec!["primary", "gray"]);
+        assert!(saved.semantic_color_tokens.background.is_some());
+        fs::remove_dir_all(&design_system_path).unwrap();
+    }
+
+    #[test]
+    fn interrupted_staging_leaves_the_files_untouched() {
+        let mut design_system = design_system_in_temp_dir();
+        let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+        save_design_system(&mut design_system, false).unwrap();
+
+        design_system.palettes.remove(0);
+        stage_design_system(&design_system, &design_system_path).unwrap();
+        assert_eq!(
+            palette_names(&read_saved_design_system(&design_system_path).unwrap()),
+            vec!["primary", "gray"]
+        );
+        assert!(rollback_pending_save(&design_system_path).unwrap());
+        assert!(!rollback_pending_save(&design_system_path).unwrap());
+
+        // A leftover staging does not prevent the next save
+        stage_design_system(&design_system, &design_system_path).unwrap();
+        save_design_system(&mut design_system, false).unwrap();
+        assert_eq!(
+            palette_names(&read_saved_design_system(&design_system_path).unwrap()),
+            vec!["gray"]
+        );
+        fs::remove_dir_all(&design_system_path).unwrap();
+    }
+
+    #[test]
+    fn versions_are_restored() {
+        let mut design_system = design_system_in_temp_dir();
+        let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+        save_design_system(&mut design_system, false).unwrap();
+        let version =
+            create_version(&design_system_path, "1.0.0", Some(String::from("First"))).unwrap();
+        assert!(create_version(&design_system_path, "1.0.0", None).is_err());
+
+        design_system.palettes.remove(0);
+        save_design_system(&mut design_system, false).unwrap();
+        save_design_system(&mut design_system, true).unwrap();
+        let versions = fetch_versions(&design_system_path).unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].name, version.name);
+        assert_eq!(versions[0].description.as_deref(), Some("First"));
+
+        let version_path: PathBuf = get_version_path(&design_system_path, "1.0.0").unwrap();
+        assert_eq!(
+            palette_names(&read_saved_design_system(&version_path).unwrap()),
+            vec!["primary", "gray"]
+        );
+        assert!(get_version_path(&design_system_path, "2.0.0").is_err());
+
+        restore_version(&design_system_path, "1.0.0").unwrap();
+        assert_eq!(
+            palette_names(&read_saved_design_system(&design_system_path).unwrap()),
+            vec!["primary", "gray"]
+        );
+        // The unsaved changes are dropped
+        assert!(!design_system_path.join(TMP_PATH).exists());
+        fs::remove_dir_all(&design_system_path).unwrap();
+    }
+
+    #[test]
+    fn design_system_without_themes_file_is_versioned() {
+        let mut design_system = design_system_in_temp_dir();
+        let design_system_path: PathBuf = design_system.metadata.design_system_path.clone();
+        save_design_system(&mut design_system, false).unwrap();
+        // As a design system never saved since it was created
+        fs::remove_file(design_system_path.join(THEMELIST_PATH)).unwrap();
+        create_version(&design_system_path, "1.0.0", None).unwrap();
+        let version_path: PathBuf = get_version_path(&design_system_path, "1.0.0").unwrap();
+        assert!(!version_path.join(THEMELIST_PATH).exists());
+
+        save_design_system(&mut design_system, false).unwrap();
+        assert!(design_system_path.join(THEMELIST_PATH).is_file());
+        restore_version(&design_system_path, "1.0.0").unwrap();
+        assert!(!design_system_path.join(PENDING_SAVE_PATH).exists());
+        assert!(fetch_themes(&design_system_path).main_theme.is_none());
+        assert_eq!(
+            palette_names(&read_saved_design_system(&design_system_path).unwrap()),
+            vec!["primary", "gray"]
+        );
+        fs::remove_dir_all(&design_system_path).unwrap();
+    }
+
+    #[test]
+    fn versions_are_sorted_by_instant() {
+        let design_system_path: PathBuf = std::env::temp_dir().join("uitea-versions-order-test");
+        for (name, update_date) in [
+            ("1.1.0", "2024-10-27T02:30:00+01:00"),
+            ("1.0.0", "2024-10-27T02:45:00+02:00"),
+        ] {
+            let version = DesignSystemVersion {
+                name: String::from(name),
+                description: None,
+                update_date: String::from(update_date),
+            };
+            let version_path: PathBuf = design_system_path.join(VERSIONS_PATH).join(name);
+            fs::create_dir_all(&version_path).unwrap();
+            save_to_yaml_file(version_path.join(VERSION_PATH), &version).unwrap();
+        }
+        let names: Vec<String> = fetch_versions(&design_system_path)
+            .unwrap()
+            .into_iter()
+            .map(|version| version.name)
+            .collect();
+        assert_eq!(names, vec!["1.0.0", "1.1.0"]);
+        fs::remove_dir_all(&design_system_path).unwrap();
+    }
+
+    #[test]
+    fn version_names_are_not_joined_as_paths() {
+        let design_system_path: PathBuf = std::env::temp_dir().join("uitea-versions-test");
+        for version_name in ["", "..", "../../x", "a/b"] {
+            assert!(create_version(&design_system_path, version_name, None).is_err());
+            assert!(get_version_path(&design_system_path, version_name).is_err());
+            assert!(restore_version(&design_system_path, version_name).is_err());
+        }
+        assert!(!design_system_path.exists());
+    }
+}
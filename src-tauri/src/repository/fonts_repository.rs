@@ -42,10 +42,8 @@ pub fn manage_font_export(design_system: &DesignSystem) -> Result<()> {
         .collect::<Vec<String>>();
     design_system_fonts.push(design_system.fonts.default.clone());
     design_system_fonts.iter().for_each(|font| {
-        if !font_filenames.contains(font) {
-            if GOOGLE_FONTS.contains(&font.as_str()) {
-                download_google_font(&font, &fonts_pathbuf)
-            }
+        if !font_filenames.contains(font) && GOOGLE_FONTS.contains(&font.as_str()) {
+            download_google_font(font, &fonts_pathbuf)
         }
     });
     for font_file in font_files {
@@ -57,7 +55,7 @@ pub fn manage_font_export(design_system: &DesignSystem) -> Result<()> {
     Ok(())
 }
 
-pub fn download_google_font(font_name: &str, dest_path: &PathBuf) {
+pub fn download_google_font(font_name: &str, dest_path: &Path) {
     let client = Client::new();
     let font_css_url = format!(
         "https://fonts.googleapis.com/css2?family={}&display=swap",
@@ -91,7 +89,7 @@ pub fn download_google_font(font_name: &str, dest_path: &PathBuf) {
     }
 }
 
-pub fn load_design_system_fonts(design_system_path: &PathBuf) -> Result<Vec<FileInfos>> {
+pub fn load_design_system_fonts(design_system_path: &Path) -> Result<Vec<FileInfos>> {
     list_file_info_in_dir(
         &design_system_path.join(EXPORTS_PATH).join(FONTS_PATH),
         Some(FONTS_EXTENSIONS),
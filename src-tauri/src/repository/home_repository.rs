@@ -44,7 +44,7 @@ pub fn insert_recent_file(state: State<AppState>, recent_file: RecentFile) -> Re
     // Vérifier si le fichier est déjà dans la liste
     if !recent_files
         .iter()
-        .any(|rf| &rf.file_path == &recent_file.file_path)
+        .any(|rf| rf.file_path == recent_file.file_path)
     {
         recent_files.push(recent_file.clone());
         db.set("recentFiles", &recent_files)
@@ -153,9 +153,7 @@ pub fn fetch_user_settings(state: State<AppState>) -> Result<UserSettings> {
 }
 
 pub fn svg_to_png(svg: &str, design_system_path: Option<PathBuf>) -> Result<String> {
-    let compute_pathbuf: Option<PathBuf> = match design_system_path {
-        Some(path) => Some(path.join(EXPORTS_PATH).join(FONTS_PATH)),
-        None => None,
-    };
-    svg_to_png_b64(&svg, 96.0, compute_pathbuf)
+    let compute_pathbuf: Option<PathBuf> =
+        design_system_path.map(|path| path.join(EXPORTS_PATH).join(FONTS_PATH));
+    svg_to_png_b64(svg, 96.0, compute_pathbuf)
 }
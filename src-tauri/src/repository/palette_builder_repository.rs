@@ -1,6 +1,6 @@
 use std::{
     fs::{self, create_dir, DirEntry},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use anyhow::{Context, Result};
@@ -15,13 +15,13 @@ use super::{compute_path_with_extension_overwrite, load_yaml_from_pathbuf, save_
 const PALETTE_BUILDER_PATH: &str = "palette_builder";
 
 pub fn save_palette_builder(palette_builder: &PaletteBuilder) -> Result<()> {
-    let palette_builder_file: PaletteBuilderFile = PaletteBuilderFile::from(&palette_builder);
+    let palette_builder_file: PaletteBuilderFile = PaletteBuilderFile::from(palette_builder);
     save_to_yaml_file(palette_builder.metadata.path.clone(), &palette_builder_file)?;
     Ok(())
 }
 
 pub fn save_palette_builder_into_design_system(
-    design_system_path: &PathBuf,
+    design_system_path: &Path,
     palette_builder: &PaletteBuilder,
 ) -> Result<()> {
     let folder_path: PathBuf = design_system_path.join(PALETTE_BUILDER_PATH);
@@ -31,17 +31,17 @@ pub fn save_palette_builder_into_design_system(
     let save_path: PathBuf = compute_path_with_extension_overwrite(
         &folder_path,
         &palette_builder.metadata.palette_builder_name,
-        &"yaml",
+        "yaml",
     );
     save_to_yaml_file(save_path, &palette_builder)?;
     Ok(())
 }
 
 pub fn fetch_design_system_palette_builders(
-    design_system_path: &PathBuf,
+    design_system_path: &Path,
 ) -> Result<Vec<PaletteBuilderMetadata>> {
     let palette_builder_path = &design_system_path.join(PALETTE_BUILDER_PATH);
-    let read_dir = fs::read_dir(&palette_builder_path)?;
+    let read_dir = fs::read_dir(palette_builder_path)?;
 
     let palette_builders: Vec<PaletteBuilderMetadata> = read_dir
         .into_iter()
@@ -65,28 +65,79 @@ pub fn fetch_design_system_palette_builders(
                         .tints
                         .into_iter()
                         .find(|color| color.is_center.unwrap_or(false));
-                    return match color {
+                    match color {
                         Some(tint) => Some(tint.color),
                         None => None,
-                    };
+                    }
                 })
                 .collect::<Vec<String>>();
-            return Some(PaletteBuilderMetadata {
+            Some(PaletteBuilderMetadata {
                 palette_builder_name: String::from(file_name),
                 path: path.clone(),
                 main_colors,
-            });
+            })
         })
         .collect::<Vec<PaletteBuilderMetadata>>();
     Ok(palette_builders)
 }
 
+/// Palette builder files saved into the design system folder, with their path
+pub fn fetch_design_system_palette_builder_files(
+    design_system_path: &Path,
+) -> Result<Vec<(PathBuf, PaletteBuilderFile)>> {
+    let palette_builder_path: PathBuf = design_system_path.join(PALETTE_BUILDER_PATH);
+    if !palette_builder_path.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut palette_builder_files: Vec<(PathBuf, PaletteBuilderFile)> = vec![];
+    for dir_entry in fs::read_dir(&palette_builder_path)? {
+        let path: PathBuf = dir_entry?.path();
+        let extension: Option<&str> = path.extension().and_then(|ext| ext.to_str());
+        if extension != Some("yaml") && extension != Some("yml") {
+            continue;
+        }
+        let palette_builder_file: PaletteBuilderFile = load_palette_builder(&path)?;
+        palette_builder_files.push((path, palette_builder_file));
+    }
+    Ok(palette_builder_files)
+}
+
 pub fn load_palette_builder(path: &PathBuf) -> Result<PaletteBuilderFile> {
     let palette_builder_file: PaletteBuilderFile =
-        load_yaml_from_pathbuf::<PaletteBuilderFile>(&path)?;
+        load_yaml_from_pathbuf::<PaletteBuilderFile>(path)?;
     Ok(palette_builder_file)
 }
 
+pub fn save_palette_builder_file(
+    path: PathBuf,
+    palette_builder_file: &PaletteBuilderFile,
+) -> Result<()> {
+    save_to_yaml_file(path, palette_builder_file)
+}
+
+/// Write the renamed palette builder files (path, original, renamed), then `save` the design system.
+/// The original files are written back when a file or the design system can not be saved
+pub fn save_renamed_palette_builder_files<T>(
+    palette_builders: &[(PathBuf, PaletteBuilderFile, PaletteBuilderFile)],
+    save: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let rollback = |written: usize| {
+        for (path, original, _) in &palette_builders[..written] {
+            let _ = save_palette_builder_file(path.clone(), original);
+        }
+    };
+    for (index, (path, _, palette_builder)) in palette_builders.iter().enumerate() {
+        if let Err(err) = save_palette_builder_file(path.clone(), palette_builder) {
+            rollback(index);
+            return Err(err);
+        }
+    }
+    save().map_err(|err| {
+        rollback(palette_builders.len());
+        err
+    })
+}
+
 pub fn remove_palette_builder_from_design_system(path: &PathBuf) -> Result<()> {
     fs::remove_file(path)?;
     Ok(())
@@ -95,7 +146,7 @@ pub fn remove_palette_builder_from_design_system(path: &PathBuf) -> Result<()> {
 pub fn rename_palette_builder(payload: PaletteBuilderRenamePayload) -> Result<()> {
     let palette_builder_repo: PathBuf = payload.design_system_path.join(PALETTE_BUILDER_PATH);
     let new_path: PathBuf =
-        compute_path_with_extension_overwrite(&palette_builder_repo, &payload.new_name, &"yaml");
+        compute_path_with_extension_overwrite(&palette_builder_repo, &payload.new_name, "yaml");
     fs::rename(&payload.metadata.path, new_path)?;
     Ok(())
 }
@@ -120,9 +171,8 @@ pub fn find_palette_builder_metadata(path: &PathBuf) -> Result<PaletteBuilderMet
         .and_then(|name| name.to_str())
         .context("Impossible de récupérer le nom du fichier sans extension")?;
     // Charge le contenu du fichier YAML dans une instance de `PaletteBuilder`
-    let palette_builder: PaletteBuilderFile =
-        load_yaml_from_pathbuf::<PaletteBuilderFile>(&path)
-            .context("Erreur lors du chargement du fichier YAML")?;
+    let palette_builder: PaletteBuilderFile = load_yaml_from_pathbuf::<PaletteBuilderFile>(path)
+        .context("Erreur lors du chargement du fichier YAML")?;
 
     // Extrait pour chaque palette la couleur associée à la teinte centrale
     let main_colors: Vec<String> = palette_builder
@@ -144,3 +194,102 @@ pub fn find_palette_builder_metadata(path: &PathBuf) -> Result<PaletteBuilderMet
         main_colors,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    fn palette_builder_file(palette_name: &str) -> PaletteBuilderFile {
+        let palette_settings = serde_json::json!({
+            "lightnessMax": 0.97,
+            "lightnessMin": 0.2,
+            "satChromaGapLeft": 0.0,
+            "satChromaGapRight": 0.0,
+            "hueGapLeft": 0.0,
+            "hueGapRight": 0.0
+        });
+        serde_json::from_value(serde_json::json!({
+            "palettes": [{
+                "id": "1",
+                "name": palette_name,
+                "tints": [{ "name": "500", "isAnchor": true, "isCenter": true, "color": "#3b82f6" }],
+                "settings": palette_settings
+            }],
+            "settings": {
+                "steps": 1,
+                "tintNamingMode": "50-950",
+                "interpolationColorSpace": "oklch",
+                "paletteSettings": palette_settings
+            }
+        }))
+        .unwrap()
+    }
+
+    fn palette_name(path: &PathBuf) -> String {
+        load_palette_builder(path).unwrap().palettes[0].name.clone()
+    }
+
+    /// Two palette builder files renamed from primary to brand, the second one with `second_path`
+    fn renamed_files(
+        folder: &Path,
+        second_path: PathBuf,
+    ) -> Vec<(PathBuf, PaletteBuilderFile, PaletteBuilderFile)> {
+        [folder.join("first.yaml"), second_path]
+            .into_iter()
+            .map(|path| {
+                let _ = save_palette_builder_file(path.clone(), &palette_builder_file("primary"));
+                (
+                    path,
+                    palette_builder_file("primary"),
+                    palette_builder_file("brand"),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renamed_files_are_kept_when_the_design_system_is_saved() {
+        let folder = std::env::temp_dir().join(format!("uitea-rename-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&folder).unwrap();
+        let files = renamed_files(&folder, folder.join("second.yaml"));
+
+        let saved = save_renamed_palette_builder_files(&files, || Ok(42)).unwrap();
+        assert_eq!(saved, 42);
+        assert_eq!(palette_name(&files[0].0), "brand");
+        assert_eq!(palette_name(&files[1].0), "brand");
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn files_are_written_back_when_the_design_system_is_not_saved() {
+        let folder = std::env::temp_dir().join(format!("uitea-rename-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&folder).unwrap();
+        let files = renamed_files(&folder, folder.join("second.yaml"));
+
+        let result: Result<()> =
+            save_renamed_palette_builder_files(&files, || Err(anyhow!("disk full")));
+        assert_eq!(result.unwrap_err().to_string(), "disk full");
+        assert_eq!(palette_name(&files[0].0), "primary");
+        assert_eq!(palette_name(&files[1].0), "primary");
+        fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn written_files_are_restored_when_a_file_can_not_be_written() {
+        let folder = std::env::temp_dir().join(format!("uitea-rename-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&folder).unwrap();
+        let files = renamed_files(&folder, folder.join("missing").join("second.yaml"));
+
+        let mut saved = false;
+        let result = save_renamed_palette_builder_files(&files, || {
+            saved = true;
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert!(!saved);
+        assert_eq!(palette_name(&files[0].0), "primary");
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
@@ -1,10 +1,59 @@
-use std::fmt::Debug;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 
 use anyhow::{anyhow, Result};
+use pickledb::PickleDb;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
-use crate::AppState;
+use crate::{make_db, AppState};
+
+/// Past entries kept by a history
+const MAX_HISTORY_ENTRIES: usize = 50;
+/// Serialized size (bytes) of the entries of a history, the oldest entries are dropped above
+const MAX_HISTORY_SIZE: usize = 16 * 1024 * 1024;
+/// History files kept on launch, the least recently edited documents lose their history
+const MAX_HISTORY_FILES: usize = 30;
+
+/// Undo / redo histories persisted per document (design system path, palette builder, color
+/// picker, token crafter), a file per document under the `undo-redo` folder of the app data
+pub struct UndoHistories {
+    folder: PathBuf,
+    dbs: HashMap<String, PickleDb>,
+}
+
+/// FNV-1a hash of the document id : stable across launches, unlike the std hasher
+fn history_file_name(object_id: &str) -> String {
+    let hash: u64 = object_id.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}.db", hash)
+}
+
+/// Keep the `MAX_HISTORY_FILES` most recently modified history files
+fn remove_old_histories(folder: &Path) -> Result<()> {
+    if !folder.is_dir() {
+        return Ok(());
+    }
+    let mut files: Vec<(SystemTime, PathBuf)> = fs::read_dir(folder)?
+        .filter_map(|dir_entry| {
+            let path: PathBuf = dir_entry.ok()?.path();
+            let modified: SystemTime = path.metadata().ok()?.modified().ok()?;
+            Some((modified, path))
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| Reverse(*modified));
+    for (_, path) in files.into_iter().skip(MAX_HISTORY_FILES) {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -20,6 +69,37 @@ pub struct Historic<T> {
     future: Vec<T>,
 }
 
+impl<T: Serialize> Historic<T> {
+    /// Drop the oldest past entries (then the farthest future ones) above `MAX_HISTORY_ENTRIES`
+    /// or `MAX_HISTORY_SIZE`
+    fn bound(&mut self) {
+        if self.past.len() > MAX_HISTORY_ENTRIES {
+            self.past.drain(0..self.past.len() - MAX_HISTORY_ENTRIES);
+        }
+        let entry_size = |entry: &T| serde_json::to_vec(entry).map_or(0, |bytes| bytes.len());
+        let mut size: usize = self
+            .past
+            .iter()
+            .chain(&self.present)
+            .chain(&self.future)
+            .map(entry_size)
+            .sum();
+        while size > MAX_HISTORY_SIZE {
+            let dropped: Option<T> = if !self.past.is_empty() {
+                Some(self.past.remove(0))
+            } else if !self.future.is_empty() {
+                Some(self.future.remove(0))
+            } else {
+                None
+            };
+            match dropped {
+                Some(entry) => size -= entry_size(&entry),
+                None => break,
+            }
+        }
+    }
+}
+
 impl<T> Default for Historic<T> {
     fn default() -> Self {
         Self {
@@ -30,17 +110,117 @@ impl<T> Default for Historic<T> {
     }
 }
 
+impl UndoHistories {
+    pub fn new(folder: PathBuf) -> UndoHistories {
+        if let Err(err) = remove_old_histories(&folder) {
+            eprintln!("Fail to clean the undo histories : {:?}", err);
+        }
+        UndoHistories {
+            folder,
+            dbs: HashMap::new(),
+        }
+    }
+
+    /// Database of the document, loaded from its file on first access
+    fn db(&mut self, object_id: &str) -> &mut PickleDb {
+        let path: PathBuf = self.folder.join(history_file_name(object_id));
+        self.dbs
+            .entry(object_id.to_string())
+            .or_insert_with(|| make_db(path))
+    }
+
+    pub fn can_undo_redo<T: for<'de> Deserialize<'de>>(
+        &mut self,
+        object_id: &str,
+    ) -> UndoRedoActions {
+        let historic: Historic<T> = self.db(object_id).get(object_id).unwrap_or_default();
+
+        UndoRedoActions {
+            can_redo: !historic.future.is_empty(),
+            can_undo: !historic.past.is_empty(),
+        }
+    }
+
+    pub fn set_new<T: Serialize + for<'de> Deserialize<'de> + Clone>(
+        &mut self,
+        object_id: &str,
+        new_value: &T,
+    ) -> Result<()> {
+        let mut historic: Historic<T> = self.db(object_id).get(object_id).unwrap_or_default();
+
+        if let Some(current) = historic.present.take() {
+            historic.past.push(current);
+        }
+        historic.present = Some(new_value.clone());
+        historic.future.clear();
+        historic.bound();
+
+        self.db(object_id).set(object_id, &historic)?;
+        Ok(())
+    }
+
+    pub fn undo<T: Serialize + for<'de> Deserialize<'de> + Clone + Debug>(
+        &mut self,
+        object_id: &str,
+    ) -> Result<T> {
+        let mut historic: Historic<T> = self.db(object_id).get(object_id).unwrap_or_default();
+        if let Some(prev) = historic.past.pop() {
+            if let Some(current) = historic.present.take() {
+                historic.future.push(current);
+            }
+            historic.present = Some(prev.clone());
+            let db: &mut PickleDb = self.db(object_id);
+            db.set(object_id, &historic)
+                .or(Err(anyhow!("Impossible to undo")))?;
+            db.dump()?;
+            return Ok(prev);
+        }
+
+        Err(anyhow!("fail to undo design system"))
+    }
+
+    pub fn redo<T: Serialize + for<'de> Deserialize<'de> + Clone + Debug>(
+        &mut self,
+        object_id: &str,
+    ) -> Result<T> {
+        let mut historic: Historic<T> = self.db(object_id).get(object_id).unwrap_or_default();
+
+        if let Some(next) = historic.future.pop() {
+            if let Some(current) = historic.present.take() {
+                historic.past.push(current);
+            }
+            historic.present = Some(next.clone());
+            self.db(object_id).set(object_id, &historic)?;
+
+            return Ok(next);
+        }
+
+        Err(anyhow!("fail to redo design system"))
+    }
+
+    pub fn get_present<T: Serialize + for<'de> Deserialize<'de> + Clone + Debug>(
+        &mut self,
+        object_id: &str,
+    ) -> Result<T> {
+        let historic: Historic<T> = self.db(object_id).get(object_id).unwrap_or_default();
+        historic.present.ok_or(anyhow!("fail to find present"))
+    }
+}
+
+/// History id of a document of a tool (`palette-builder`, `color-picker`, `token-crafter`) : the
+/// documents keep separate histories, a document not saved yet has the history of the tool
+pub fn tool_history_id(tool: &str, document_path: Option<&Path>) -> String {
+    match document_path {
+        Some(document_path) => format!("{}:{}", tool, document_path.display()),
+        None => tool.to_string(),
+    }
+}
+
 pub fn can_undo_redo<T: for<'de> Deserialize<'de>>(
     state: &State<AppState>,
     object_id: &str,
 ) -> Result<UndoRedoActions> {
-    let undo_db: std::sync::MutexGuard<'_, pickledb::PickleDb> = state.undo_db.lock().unwrap();
-    let historic: Historic<T> = undo_db.get(object_id).unwrap_or_default();
-
-    Ok(UndoRedoActions {
-        can_redo: historic.future.len() > 0,
-        can_undo: historic.past.len() > 0,
-    })
+    Ok(state.undo_db.lock().unwrap().can_undo_redo::<T>(object_id))
 }
 
 pub fn set_new<T: Serialize + for<'de> Deserialize<'de> + Clone>(
@@ -48,76 +228,98 @@ pub fn set_new<T: Serialize + for<'de> Deserialize<'de> + Clone>(
     object_id: &str,
     new_value: &T,
 ) -> Result<()> {
-    let mut undo_db = state.undo_db.lock().unwrap();
-    let mut historic: Historic<T> = undo_db.get(object_id).unwrap_or_default();
-
-    if let Some(current) = historic.present.take() {
-        historic.past.push(current);
-    }
-    historic.present = Some(new_value.clone());
-    historic.future.clear();
-
-    undo_db.set(object_id, &historic)?;
-    Ok(())
+    state.undo_db.lock().unwrap().set_new(object_id, new_value)
 }
 
 pub fn undo<T: Serialize + for<'de> Deserialize<'de> + Clone + Debug>(
     state: &State<AppState>,
     object_id: &str,
 ) -> Result<T> {
-    let mut undo_db = state.undo_db.lock().unwrap();
-    let mut historic: Historic<T> = undo_db.get(object_id).unwrap_or_default();
-    if let Some(prev) = historic.past.pop() {
-        if let Some(current) = historic.present.take() {
-            historic.future.push(current);
-        }
-        historic.present = Some(prev.clone());
-        // Limite à 15 entrées dans le passé
-        if historic.past.len() > 25 {
-            historic.past.drain(0..historic.past.len() - 15);
-        }
-        undo_db
-            .set(object_id, &historic)
-            .or(Err(anyhow!("Impossible to undo")))?;
-        undo_db.dump()?;
-        return Ok(prev);
-    }
-
-    Err(anyhow!("fail to undo design system"))
+    state.undo_db.lock().unwrap().undo(object_id)
 }
 
 pub fn redo<T: Serialize + for<'de> Deserialize<'de> + Clone + Debug>(
     state: &State<AppState>,
     object_id: &str,
 ) -> Result<T> {
-    let mut undo_db = state.undo_db.lock().unwrap();
-    let mut historic: Historic<T> = undo_db.get(object_id).unwrap_or_default();
-
-    if let Some(next) = historic.future.pop() {
-        if let Some(current) = historic.present.take() {
-            historic.past.push(current);
-        }
-        historic.present = Some(next.clone());
-        undo_db.set(object_id, &historic)?;
-        match undo_db.get::<Historic<T>>(object_id) {
-            None => {
-                println!("fail to get")
-            }
-            Some(_) => {}
-        };
-
-        return Ok(next);
-    }
-
-    Err(anyhow!("fail to redo design system"))
+    state.undo_db.lock().unwrap().redo(object_id)
 }
 
 pub fn get_present<T: Serialize + for<'de> Deserialize<'de> + Clone + Debug>(
     state: &State<AppState>,
     object_id: &str,
 ) -> Result<T> {
-    let undo_db = state.undo_db.lock().unwrap();
-    let historic: Historic<T> = undo_db.get(object_id).unwrap_or_default();
-    let present_historic: T = historic.present.ok_or(anyhow!("fail to find present"))?;
-    Ok(present_historic)
+    state.undo_db.lock().unwrap().get_present(object_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_histories() -> (PathBuf, UndoHistories) {
+        let folder: PathBuf =
+            std::env::temp_dir().join(format!("uitea-undo-test-{}", uuid::Uuid::new_v4()));
+        (folder.clone(), UndoHistories::new(folder))
+    }
+
+    #[test]
+    fn documents_keep_separate_stacks() {
+        let (folder, mut histories) = temp_histories();
+        let first: String = tool_history_id("palette-builder", Some(Path::new("/a/first.yaml")));
+        let second: String = tool_history_id("palette-builder", Some(Path::new("/a/second.yaml")));
+
+        histories.set_new(&first, &1).unwrap();
+        histories.set_new(&first, &2).unwrap();
+        histories.set_new(&second, &10).unwrap();
+
+        assert!(histories.can_undo_redo::<i32>(&first).can_undo);
+        assert!(!histories.can_undo_redo::<i32>(&second).can_undo);
+        assert_eq!(histories.undo::<i32>(&first).unwrap(), 1);
+        assert!(histories.undo::<i32>(&second).is_err());
+        assert_eq!(histories.get_present::<i32>(&second).unwrap(), 10);
+        assert_eq!(histories.redo::<i32>(&first).unwrap(), 2);
+
+        // the histories are persisted per document
+        let mut reloaded = UndoHistories::new(folder.clone());
+        assert_eq!(reloaded.get_present::<i32>(&first).unwrap(), 2);
+        assert_eq!(reloaded.get_present::<i32>(&second).unwrap(), 10);
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn unsaved_documents_share_the_tool_history() {
+        assert_eq!(tool_history_id("token-crafter", None), "token-crafter");
+        assert_ne!(
+            tool_history_id("token-crafter", Some(Path::new("/ds"))),
+            tool_history_id("palette-builder", Some(Path::new("/ds")))
+        );
+    }
+
+    #[test]
+    fn bound_keeps_the_last_entries() {
+        let mut historic: Historic<usize> = Historic {
+            past: (0..MAX_HISTORY_ENTRIES + 10).collect(),
+            present: Some(1000),
+            future: vec![],
+        };
+        historic.bound();
+        assert_eq!(historic.past.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(historic.past[0], 10);
+        assert_eq!(historic.present, Some(1000));
+    }
+
+    #[test]
+    fn bound_drops_the_oldest_entries_above_the_size_limit() {
+        let entry: String = "a".repeat(MAX_HISTORY_SIZE / 3);
+        let mut historic: Historic<String> = Historic {
+            past: vec![format!("0{}", entry), format!("1{}", entry)],
+            present: Some(entry.clone()),
+            future: vec![format!("3{}", entry), format!("2{}", entry)],
+        };
+        historic.bound();
+        assert!(historic.past.is_empty());
+        assert_eq!(historic.present, Some(entry));
+        assert_eq!(historic.future.len(), 1);
+        assert!(historic.future[0].starts_with('2'));
+    }
 }
@@ -0,0 +1,113 @@
+use serde_json::json;
+
+use crate::domain::design_system_domain::{DesignSystem, Typographies};
+
+/// Small design system with two palettes, a dark theme with overrides, a combination and a
+/// token of each kind, the colors are resolved (as loaded by the app)
+pub fn sample_design_system() -> DesignSystem {
+    serde_json::from_value(json!({
+        "metadata": {
+            "designSystemId": "id",
+            "designSystemName": "Demo",
+            "designSystemPath": "/tmp/demo",
+            "isTmp": false,
+            "canUndo": false,
+            "canRedo": false,
+            "banner": "",
+            "logo": "",
+            "readme": null,
+            "previewImages": [],
+            "fonts": [],
+            "exports": {},
+            "updateDate": ""
+        },
+        "palettes": [
+            {
+                "paletteName": "primary",
+                "palettePath": null,
+                "tints": [
+                    { "label": "50", "color": "#eff6ff" },
+                    { "label": "500", "color": "#3b82f6" },
+                    { "label": "900", "color": "#1e3a8a" }
+                ]
+            },
+            {
+                "paletteName": "gray",
+                "palettePath": null,
+                "tints": [
+                    { "label": "50", "color": "#f9fafb" },
+                    { "label": "500", "color": "#6b7280" },
+                    { "label": "900", "color": "#111827" }
+                ]
+            }
+        ],
+        "independantColors": {
+            "white": "#ffffff",
+            "independantColors": [{ "label": "success", "color": "#10b981" }]
+        },
+        "themes": {
+            "mainTheme": { "name": "light", "background": "#ffffff" },
+            "otherThemes": [{ "name": "dark", "background": "#111827" }]
+        },
+        "semanticColorTokens": {
+            "background": "palette-gray-50",
+            "border": null,
+            "textLight": "palette-gray-500",
+            "textDefault": "palette-gray-900",
+            "textDark": null,
+            "colorCombinationCollections": [{
+                "combinationName": "button",
+                "default": {
+                    "background": "palette-primary-500",
+                    "text": "color-white",
+                    "border": null
+                },
+                "hover": { "background": "palette-primary-900", "text": null, "border": null },
+                "active": null,
+                "focus": null,
+                "group": null,
+                "defaultCombination": true
+            }],
+            "themeOverrides": {
+                "dark": {
+                    "background": "palette-gray-900",
+                    "textDefault": "palette-gray-50",
+                    "colorCombinationCollections": [{
+                        "combinationName": "button",
+                        "hover": {
+                            "background": "palette-primary-50",
+                            "text": null,
+                            "border": null
+                        }
+                    }]
+                }
+            }
+        },
+        "spaces": [
+            { "spaceKey": "1", "spaceValue": { "unit": "PX", "value": 4.0 } },
+            { "spaceKey": "2", "spaceValue": { "unit": "REM", "value": 0.5 } }
+        ],
+        "fonts": {
+            "default": "Open Sans",
+            "additionals": [{ "fontName": "mono", "value": "JetBrains Mono, monospace" }]
+        },
+        "typography": Typographies::new(),
+        "radius": {
+            "default": { "unit": "PX", "value": 4.0 },
+            "additionalsRadius": [{ "radiusKey": "lg", "radiusValue": { "unit": "PX", "value": 12.0 } }]
+        },
+        "shadows": [{
+            "shadowName": "shadow",
+            "shadowsArray": [{
+                "color": "palette-gray-900",
+                "colorOpacity": 0.25,
+                "shadowX": 0.0,
+                "shadowY": 4.0,
+                "blur": 4.0,
+                "spread": 0.0,
+                "inset": false
+            }]
+        }]
+    }))
+    .unwrap()
+}
@@ -5,6 +5,9 @@ export interface Sample {
   colors: string[];
 }
 
+/** Swatch files of the design tools : Adobe (ase, aco), GIMP / Inkscape (gpl), Sketch */
+export const SWATCH_EXTENSIONS = ["ase", "aco", "gpl", "sketchpalette"];
+
 export interface ColorPickerStoreData {
   colors: string[];
   samples: Sample[];
@@ -261,6 +261,16 @@ export interface SemanticColorTokens {
   textDark?: string;
   border?: string;
   colorCombinationCollections: ColorCombinationCollection[];
+  themeOverrides?: Record<string, ThemeSemanticColorTokens>;
+}
+
+export interface ThemeSemanticColorTokens {
+  background?: string;
+  textLight?: string;
+  textDefault?: string;
+  textDark?: string;
+  border?: string;
+  colorCombinationCollections?: ColorCombinationCollection[];
 }
 
 export type ColorCombinationState = "default" | "hover" | "active" | "focus";
@@ -378,11 +388,24 @@ export interface HandleUpdateColorPayload {
   value: string | undefined;
 }
 
-export interface GenerateExportPayload {
-  designSystemPath: string;
-  exportName: string;
+/** Rename of a palette, or of one of its tints when `tintLabel` is given */
+export interface PaletteRenamePayload {
+  designSystem: DesignSystem;
+  paletteName: string;
+  tintLabel?: string;
+  newName: string;
+}
+
+export interface RenamedReference {
+  location: string;
+  previousValue: string;
   value: string;
-  extension: string;
+}
+
+export interface PaletteRenameReport {
+  designSystem: DesignSystem;
+  renamedReferences: RenamedReference[];
+  paletteBuilderPaths: string[];
 }
 
 export type UnitOfMeasurement = "REM" | "PX";
@@ -439,7 +462,26 @@ export interface FileMetadata {
 export interface ExportsMetadata {
   css?: FileMetadata;
   figmaTokenStudio?: FileMetadata;
+  dtcg?: FileMetadata;
+  styleDictionary?: FileMetadata;
+  scss?: FileMetadata;
+  less?: FileMetadata;
+  tailwind?: FileMetadata;
+  android?: FileMetadata;
+  ios?: FileMetadata;
+  swatches?: FileMetadata;
   readme?: FileMetadata;
 }
 
-export type EXPORT_CATEGORY = "css" | "figma" | "readme";
\ No newline at end of file
+export type EXPORT_CATEGORY =
+  | "css"
+  | "figma"
+  | "dtcg"
+  | "style-dictionary"
+  | "scss"
+  | "less"
+  | "tailwind"
+  | "android"
+  | "ios"
+  | "swatches"
+  | "readme";
\ No newline at end of file
@@ -22,6 +22,7 @@ interface ColorPickerStore {
   updateColorSample: (index: number, sample: Sample) => void;
   createColorSample: () => void;
   removeColorSample: (index: number) => void;
+  importSwatches: (path: string) => Promise<void>;
   undoColorPicker: () => void;
   redoColorPicker: () => void;
   setPickerFallback: (color: ColorIO, colorSpace: ColorSpace) => void;
@@ -60,11 +61,13 @@ export const useColorPickerStore = create<ColorPickerStore>((set, get) => ({
     const colorPickerStore = await invoke<ColorPickerStoreData>(
       "fetch_color_picker_store"
     );
+    const canUndoRedo = await invoke<CanUndoRedo>("can_undo_redo_color_picker");
     set((state) => {
       return {
         ...state,
         ...colorPickerStore,
         colors: colorPickerStore.colors.map((color) => new ColorIO(color)),
+        canUndoRedo,
       };
     });
   },
@@ -120,6 +123,20 @@ export const useColorPickerStore = create<ColorPickerStore>((set, get) => ({
     });
     get().registerColorPicker();
   },
+  importSwatches: async (path: string) => {
+    const colorPicker = await invoke<ColorPickerStoreData>(
+      "import_swatches_into_color_picker",
+      { path }
+    );
+    const canUndoRedo = await invoke<CanUndoRedo>("can_undo_redo_color_picker");
+    set((state) => {
+      return {
+        ...state,
+        samples: colorPicker.samples,
+        canUndoRedo,
+      };
+    });
+  },
   undoColorPicker: async () => {
     const colorPicker = await invoke<ColorPickerStoreData>("undo_color_picker");
     const canUndoRedo = await invoke<CanUndoRedo>("can_undo_redo_color_picker");
@@ -4,6 +4,7 @@ import {
   MdRefresh,
   MdStar,
   MdStarOutline,
+  MdUpload,
 } from "react-icons/md";
 import { useSidepanelContext } from "../../ui/kit/SidepanelContext";
 import { getRectSize, ICON_SIZE_MD, ICON_SIZE_SM } from "../../ui/UiConstants";
@@ -14,9 +15,13 @@ import { useState } from "react";
 import { getContrastInfo, STAR_ARRAY } from "../../util/PickerUtil";
 import { DesignToken, TokenFamily } from "../../domain/DesignSystemDomain";
 import ColorSample from "./ColorSample";
+import { open } from "@tauri-apps/plugin-dialog";
+import toast from "react-hot-toast";
+import { SWATCH_EXTENSIONS } from "../../domain/ColorPickerDomain";
 
 function PickerToolSidepanel({ tokens }: { tokens?: TokenFamily[] }) {
-  const { colors, samples, createColorSample } = useColorPickerStore();
+  const { colors, samples, createColorSample, importSwatches } =
+    useColorPickerStore();
   const { closeModal } = useSidepanelContext();
   const [quote, setQuote] = useState(getRandomQuote);
   const contrastInfo = getContrastInfo(colors);
@@ -47,6 +52,28 @@ function PickerToolSidepanel({ tokens }: { tokens?: TokenFamily[] }) {
     };
   });
 
+  async function importSwatchesFromDialog() {
+    const path = (await open({
+      directory: false,
+      multiple: false,
+      title: "Select a swatch file",
+      filters: [
+        {
+          name: "Swatches",
+          extensions: SWATCH_EXTENSIONS,
+        },
+      ],
+    })) as null | string;
+
+    if (!path) return;
+    try {
+      await importSwatches(path);
+    } catch (e) {
+      console.error(e);
+      toast.error("Fail to import swatches");
+    }
+  }
+
   return (
     <div className={styles.sidePanel}>
       <div className={styles.sidePanelHeader}>
@@ -119,7 +146,11 @@ function PickerToolSidepanel({ tokens }: { tokens?: TokenFamily[] }) {
           {samples.map((sample, index) => (
             <ColorSample sample={sample} key={sample.name} index={index} />
           ))}
-          <div className="row justify-end align-center">
+          <div className="row justify-end align-center gap-4">
+            <button className="add-button" onClick={importSwatchesFromDialog}>
+              <MdUpload size={ICON_SIZE_MD} />
+              Import swatches
+            </button>
             <button className="add-button" onClick={createColorSample}>
               <MdAdd size={ICON_SIZE_MD} />
               Create sample
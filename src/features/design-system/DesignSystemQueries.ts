@@ -4,8 +4,10 @@ import {
   DesignSystem,
   DesignSystemCreationPayload,
   DesignSystemMetadata,
-  GenerateExportPayload,
+  PaletteRenamePayload,
+  PaletteRenameReport,
 } from "../../domain/DesignSystemDomain";
+import { ExportFormat } from "../../domain/ExportDomain";
 import toast from "react-hot-toast";
 import { useInsertRecentFile } from "../home/HomeQueries";
 import { useParams } from "react-router-dom";
@@ -111,6 +113,41 @@ export function useSaveDesignSystem(designSystemPath?: string) {
   return { saveDesignSystem, isSavingDesignSystem };
 }
 
+/** Rename a palette (or a tint) with its references, the design system is saved */
+export function useRenamePalette(designSystemPath?: string) {
+  const queryClient = useQueryClient();
+
+  const { mutate: renamePalette, isPending: isRenamingPalette } = useMutation({
+    mutationFn: async (
+      payload: PaletteRenamePayload
+    ): Promise<PaletteRenameReport> =>
+      await invoke<PaletteRenameReport>("rename_palette", { payload }),
+    onError: (error) => {
+      console.error(error);
+      toast.error(`Fail to rename the palette : ${error}`);
+      queryClient.refetchQueries({
+        queryKey: ["design-system", designSystemPath],
+      });
+    },
+    onSuccess: (report: PaletteRenameReport) => {
+      queryClient.setQueryData(
+        ["design-system", designSystemPath],
+        report.designSystem
+      );
+      if (report.paletteBuilderPaths.length > 0) {
+        queryClient.invalidateQueries({
+          queryKey: ["palette-builder-design-system", designSystemPath],
+        });
+      }
+    },
+    scope: {
+      id: "save-design-system",
+    },
+  });
+
+  return { renamePalette, isRenamingPalette };
+}
+
 export function useUndoRedoDesignSystem(designSystemPath?: string) {
   const queryClient = useQueryClient();
 
@@ -146,22 +183,30 @@ export function useUndoRedoDesignSystem(designSystemPath?: string) {
   return { undoDesignSystem, redoDesignSystem };
 }
 
-export function useGenerateExport() {
-  const { mutate: generateExport, isPending: isGeneratingExport } = useMutation(
-    {
-      mutationFn: async (payload: GenerateExportPayload) => {
-        await invoke("register_export", {
-          payload,
-        });
-      },
-      onError: (err) => {
-        toast.error("fail to generate export");
-        console.error(err);
-      },
-    }
-  );
+export function useGenerateNativeExport() {
+  const {
+    mutateAsync: generateNativeExport,
+    isPending: isGeneratingNativeExport,
+  } = useMutation({
+    mutationFn: async ({
+      designSystemPath,
+      format,
+    }: {
+      designSystemPath: string;
+      format: ExportFormat;
+    }) => {
+      await invoke("generate_export", {
+        designSystemPath,
+        format,
+      });
+    },
+    onError: (err) => {
+      toast.error("fail to generate export");
+      console.error(err);
+    },
+  });
 
-  return { generateExport, isGeneratingExport };
+  return { generateNativeExport, isGeneratingNativeExport };
 }
 
 export function useSaveReadme() {
@@ -1,8 +1,7 @@
 import { useState } from "react";
 import Modal from "../../../ui/kit/Modal";
-import { cssExport, generateTokenStudioFile } from "../../../util/Export";
 import { useDesignSystemContext } from "../DesignSystemContext";
-import { useGenerateExport, useSaveReadme } from "../DesignSystemQueries";
+import { useGenerateNativeExport, useSaveReadme } from "../DesignSystemQueries";
 import { ButtonPrimary } from "../../../ui/kit/Buttons";
 import { MdDownload, MdFolder } from "react-icons/md";
 import toast from "react-hot-toast";
@@ -13,14 +12,46 @@ import Loader from "../../../ui/kit/Loader";
 import ExportLine from "./ExportLine";
 import { ICON_SIZE_MD } from "../../../ui/UiConstants";
 import { invoke } from "@tauri-apps/api/core";
+import { ExportFormat } from "../../../domain/ExportDomain";
+
+/** Exports generated by the backend, in generation order */
+const NATIVE_EXPORTS: {
+  category: EXPORT_CATEGORY;
+  format: ExportFormat;
+  label: string;
+}[] = [
+  { category: "css", format: "css", label: "css" },
+  { category: "dtcg", format: "dtcg", label: "design tokens" },
+  {
+    category: "style-dictionary",
+    format: "style-dictionary",
+    label: "style dictionary",
+  },
+  { category: "scss", format: "scss", label: "scss" },
+  { category: "less", format: "less", label: "less" },
+  { category: "tailwind", format: "tailwind", label: "tailwind" },
+  { category: "android", format: "android", label: "android" },
+  { category: "ios", format: "ios", label: "iOS" },
+  { category: "swatches", format: "swatches", label: "swatches" },
+  { category: "figma", format: "tokens-studio", label: "figma" },
+];
 
 function ExportModal() {
   const { designSystem } = useDesignSystemContext();
-  const { generateExport, isGeneratingExport } = useGenerateExport();
+  const { generateNativeExport, isGeneratingNativeExport } =
+    useGenerateNativeExport();
   const { saveReadme, isSavingReadme } = useSaveReadme();
   const queryClient = useQueryClient();
   const [exportGenerated, setExportGenerated] = useState<EXPORT_CATEGORY[]>([
     "css",
+    "dtcg",
+    "style-dictionary",
+    "scss",
+    "less",
+    "tailwind",
+    "android",
+    "ios",
+    "swatches",
     "figma",
     "readme",
   ]);
@@ -39,61 +70,27 @@ function ExportModal() {
     }
   }
 
-  async function handleExportFigma() {
-    const exportFigma = generateTokenStudioFile(designSystem);
-    await generateExport(
-      {
-        designSystemPath: designSystem.metadata.designSystemPath,
-        exportName: "export-figma-token-studio",
-        value: JSON.stringify(exportFigma),
-        extension: "json",
-      },
-      {
-        onSuccess: () => {
-          toast.success("Success to generate figma export");
-          setExportGenerated((val) => val.filter((x) => x !== "figma"));
-          queryClient.refetchQueries({
-            queryKey: ["design-system", designSystem.metadata.designSystemPath],
-          });
-        },
-      }
-    );
-  }
-
-  async function handleCssExport({ callback }: { callback?: () => void }) {
-    const exportCss = cssExport(designSystem);
-    await generateExport(
-      {
-        designSystemPath: designSystem.metadata.designSystemPath,
-        exportName: "export-stylesheet",
-        value: exportCss,
-        extension: "css",
-      },
-      {
-        onSuccess: () => {
-          toast.success("Success to generate css export");
-          setExportGenerated((val) => val.filter((x) => x !== "css"));
-          callback?.();
-          queryClient.refetchQueries({
-            queryKey: ["design-system", designSystem.metadata.designSystemPath],
-          });
-        },
+  async function handleNativeExports() {
+    for (const { category, format, label } of NATIVE_EXPORTS) {
+      if (!exportGenerated.includes(category)) continue;
+      try {
+        await generateNativeExport({
+          designSystemPath: designSystem.metadata.designSystemPath,
+          format,
+        });
+      } catch {
+        return;
       }
-    );
+      toast.success(`Success to generate ${label} export`);
+      setExportGenerated((val) => val.filter((x) => x !== category));
+    }
+    queryClient.refetchQueries({
+      queryKey: ["design-system", designSystem.metadata.designSystemPath],
+    });
   }
 
   async function generateExports() {
-    if (exportGenerated.includes("css")) {
-      await handleCssExport({
-        callback: () => {
-          if (exportGenerated.includes("figma")) {
-            handleExportFigma();
-          }
-        },
-      });
-    } else if (exportGenerated.includes("figma")) {
-      await handleExportFigma();
-    }
+    await handleNativeExports();
     if (exportGenerated.includes("readme")) {
       saveReadme(designSystem, {
         onSuccess: () => {
@@ -115,6 +112,18 @@ function ExportModal() {
           <div className="column gap-6">
             <ExportLine filemetadata={designSystem.metadata.exports.readme} />
             <ExportLine filemetadata={designSystem.metadata.exports.css} />
+            <ExportLine filemetadata={designSystem.metadata.exports.dtcg} />
+            <ExportLine
+              filemetadata={designSystem.metadata.exports.styleDictionary}
+            />
+            <ExportLine filemetadata={designSystem.metadata.exports.scss} />
+            <ExportLine filemetadata={designSystem.metadata.exports.less} />
+            <ExportLine filemetadata={designSystem.metadata.exports.tailwind} />
+            <ExportLine filemetadata={designSystem.metadata.exports.android} />
+            <ExportLine filemetadata={designSystem.metadata.exports.ios} />
+            <ExportLine
+              filemetadata={designSystem.metadata.exports.swatches}
+            />
             <ExportLine
               filemetadata={designSystem.metadata.exports.figmaTokenStudio}
             />
@@ -133,6 +142,54 @@ function ExportModal() {
               toggleExport={toggleExport}
               title="CSS Stylesheet"
             />
+            <ExportItem
+              category="dtcg"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="Design tokens (DTCG)"
+            />
+            <ExportItem
+              category="style-dictionary"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="Style Dictionary tokens"
+            />
+            <ExportItem
+              category="scss"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="SCSS variables & maps"
+            />
+            <ExportItem
+              category="less"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="Less variables & maps"
+            />
+            <ExportItem
+              category="tailwind"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="Tailwind theme (v3 config & v4 @theme)"
+            />
+            <ExportItem
+              category="android"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="Android resources & Compose theme"
+            />
+            <ExportItem
+              category="ios"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="iOS asset catalog & SwiftUI tokens"
+            />
+            <ExportItem
+              category="swatches"
+              exportGenerated={exportGenerated}
+              toggleExport={toggleExport}
+              title="Palette swatches (ASE, GPL, Sketch)"
+            />
             <ExportItem
               category="figma"
               exportGenerated={exportGenerated}
@@ -145,12 +202,12 @@ function ExportModal() {
               toggleExport={toggleExport}
               title="Readme"
             />
-            {(isSavingReadme || isGeneratingExport) && <Loader />}
+            {(isSavingReadme || isGeneratingNativeExport) && <Loader />}
             <div className="row justify-end gap-2">
               <ButtonPrimary
                 disabled={
                   isSavingReadme ||
-                  isGeneratingExport ||
+                  isGeneratingNativeExport ||
                   !exportGenerated.length
                 }
                 onClick={generateExports}
@@ -8,7 +8,7 @@ import { useDesignSystemContext } from "../DesignSystemContext";
 import classNames from "classnames";
 import { useRef } from "react";
 import TintComponent from "./TintComponent";
-import { useSaveDesignSystem } from "../DesignSystemQueries";
+import { useRenamePalette, useSaveDesignSystem } from "../DesignSystemQueries";
 import { useParams } from "react-router-dom";
 import { useFieldArray, useForm } from "react-hook-form";
 import {
@@ -47,6 +47,7 @@ function PaletteComponent({
   const colorPaletteRef = useRef<HTMLFormElement>(null);
   const { designSystemPath } = useParams();
   const { saveDesignSystem } = useSaveDesignSystem(designSystemPath);
+  const { renamePalette } = useRenamePalette(designSystemPath);
   const { setDragIndex, dragIndex, setHoverIndex, hoverIndex } =
     useParentDraggableContext();
   useSidebarComponentVisible(colorPaletteRef, componentId);
@@ -153,17 +154,60 @@ function PaletteComponent({
     }
   }
 
+  /** Index of the tint renamed in place, -1 when the labels are moved or unchanged */
+  function findRenamedTintIndex(newPalette: Palette): number {
+    if (newPalette.tints.length !== colorPalette.tints.length) return -1;
+    const changedIndexes = colorPalette.tints
+      .map((tint, tintIndex) =>
+        tint.label !== newPalette.tints[tintIndex].label ? tintIndex : -1
+      )
+      .filter((tintIndex) => tintIndex !== -1);
+    if (changedIndexes.length !== 1) return -1;
+    const previousLabel = colorPalette.tints[changedIndexes[0]].label;
+    return newPalette.tints.some((tint) => tint.label === previousLabel)
+      ? -1
+      : changedIndexes[0];
+  }
+
   function submitPalette(newPalette: Palette) {
     if (isEqual(newPalette, colorPalette)) return;
     const newColorPalettes: Palette[] = [...designSystem.palettes];
-    newColorPalettes.splice(index, 1, newPalette);
-    saveDesignSystem({
-      designSystem: {
-        ...designSystem,
-        palettes: newColorPalettes,
-      },
-      isTmp: true,
-    });
+    const renamedTintIndex = findRenamedTintIndex(newPalette);
+    // The renames go through the backend to rename the references too, with the other changes
+    if (newPalette.paletteName !== colorPalette.paletteName) {
+      newColorPalettes.splice(index, 1, {
+        ...newPalette,
+        paletteName: colorPalette.paletteName,
+      });
+      renamePalette({
+        designSystem: { ...designSystem, palettes: newColorPalettes },
+        paletteName: colorPalette.paletteName,
+        newName: newPalette.paletteName,
+      });
+    } else if (renamedTintIndex !== -1) {
+      const previousLabel = colorPalette.tints[renamedTintIndex].label;
+      newColorPalettes.splice(index, 1, {
+        ...newPalette,
+        tints: newPalette.tints.map((tint, tintIndex) =>
+          tintIndex === renamedTintIndex ? { ...tint, label: previousLabel } : tint
+        ),
+      });
+      renamePalette({
+        designSystem: { ...designSystem, palettes: newColorPalettes },
+        paletteName: colorPalette.paletteName,
+        tintLabel: previousLabel,
+        newName: newPalette.tints[renamedTintIndex].label,
+      });
+    } else {
+      newColorPalettes.splice(index, 1, newPalette);
+      saveDesignSystem({
+        designSystem: {
+          ...designSystem,
+          palettes: newColorPalettes,
+        },
+        isTmp: true,
+      });
+    }
   }
 
   return (
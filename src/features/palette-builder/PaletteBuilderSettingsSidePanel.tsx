@@ -37,6 +37,7 @@ import ExistingPaletteBuilderTab from "./ExistingPaletteBuilderTab";
 import toast from "react-hot-toast";
 import ColorIO from "colorjs.io";
 import ColorPickerLinear from "../color-picker/ColorPickerLinear";
+import { SWATCH_EXTENSIONS } from "../../domain/ColorPickerDomain";
 
 function PaletteBuilderSettingsSidePanel({
   saveOnComputer,
@@ -50,6 +51,7 @@ function PaletteBuilderSettingsSidePanel({
     settings,
     setSettings,
     palettes,
+    path: paletteBuilderPath,
     loadPaletteBuilder,
     alignerSettings,
     setAlignerSettings,
@@ -121,7 +123,7 @@ function PaletteBuilderSettingsSidePanel({
         { path }
       );
       const paletteBuilder = paletteBuilderFromFile(paletteBuilderFile);
-      loadPaletteBuilder(paletteBuilder.palettes, paletteBuilder.settings);
+      loadPaletteBuilder(paletteBuilder.palettes, paletteBuilder.settings, path);
     } catch (e) {
       console.error(e);
       toast.error("Fail to load palette builder");
@@ -148,7 +150,44 @@ function PaletteBuilderSettingsSidePanel({
       { path }
     );
     const paletteBuilder = paletteBuilderFromFile(paletteBuilderFile);
-    loadPaletteBuilder(paletteBuilder.palettes, paletteBuilder.settings);
+    loadPaletteBuilder(paletteBuilder.palettes, paletteBuilder.settings, path);
+  }
+
+  async function importSwatchesFromDialog() {
+    const path = (await open({
+      directory: false,
+      multiple: false,
+      title: "Select a swatch file",
+      filters: [
+        {
+          name: "Swatches",
+          extensions: SWATCH_EXTENSIONS,
+        },
+      ],
+    })) as null | string;
+
+    if (!path) return;
+    try {
+      const paletteBuilderFile = await invoke<PaletteBuilderPayload>(
+        "import_swatches_into_palette_builder",
+        {
+          paletteBuilder: {
+            palettes: palettes.map(paletteBuildToFile),
+            settings,
+          },
+          path,
+        }
+      );
+      const paletteBuilder = paletteBuilderFromFile(paletteBuilderFile);
+      loadPaletteBuilder(
+        paletteBuilder.palettes,
+        paletteBuilder.settings,
+        paletteBuilderPath
+      );
+    } catch (e) {
+      console.error(e);
+      toast.error("Fail to import swatches");
+    }
   }
 
   function setAligner(aligner: Aligner) {
@@ -218,6 +257,10 @@ function PaletteBuilderSettingsSidePanel({
                 <MdUpload size={ICON_SIZE_MD} />
                 Load
               </ButtonPrimary>
+              <ButtonPrimary type="button" onClick={importSwatchesFromDialog}>
+                <MdUpload size={ICON_SIZE_MD} />
+                Import swatches
+              </ButtonPrimary>
               {designSystemPathComputed ? (
                 <>
                   <Popover.Toggle
@@ -27,6 +27,8 @@ export interface Point {
 }
 
 interface PaletteBuilderStore {
+  /** File of the loaded palette builder, undefined while not saved */
+  path?: string;
   palettes: PaletteBuild[];
   settings: PalettesStoreSettings;
   alignerSettings: AlignerSettings;
@@ -48,7 +50,8 @@ interface PaletteBuilderStore {
   movePalette: (fromIndex: number, toIndex: number) => void;
   loadPaletteBuilder: (
     palettes: PaletteBuild[],
-    settings: PalettesStoreSettings
+    settings: PalettesStoreSettings,
+    path?: string
   ) => void;
   setAlignerSettings: (alignerSettings: AlignerSettings) => void;
   doPaletteBuilder: () => void;
@@ -245,6 +248,7 @@ export const usePaletteBuilderStore = create<PaletteBuilderStore>(
       set((state) => {
         return {
           ...state,
+          path: undefined,
           palettes: [],
         };
       });
@@ -270,11 +274,13 @@ export const usePaletteBuilderStore = create<PaletteBuilderStore>(
     },
     loadPaletteBuilder(
       palettes: PaletteBuild[],
-      settings: PalettesStoreSettings
+      settings: PalettesStoreSettings,
+      path?: string
     ) {
       set((state) => {
         return {
           ...state,
+          path,
           palettes,
           settings,
         };
@@ -290,15 +296,17 @@ export const usePaletteBuilderStore = create<PaletteBuilderStore>(
       });
     },
     doPaletteBuilder: async () => {
-      const { palettes, settings } = get();
+      const { path, palettes, settings } = get();
       await invoke("do_palette_builder", {
+        path,
         paletteBuilder: {
           palettes: palettes.map(paletteBuildToFile),
           settings,
         },
       });
       const canUndoRedo = await invoke<CanUndoRedo>(
-        "can_undo_redo_palette_builder"
+        "can_undo_redo_palette_builder",
+        { path }
       );
       set((state) => {
         return {
@@ -308,11 +316,14 @@ export const usePaletteBuilderStore = create<PaletteBuilderStore>(
       });
     },
     undoPaletteBuilder: async () => {
+      const { path } = get();
       const paletteBuilder = await invoke<PaletteBuilderPayload>(
-        "undo_palette_builder"
+        "undo_palette_builder",
+        { path }
       );
       const canUndoRedo = await invoke<CanUndoRedo>(
-        "can_undo_redo_palette_builder"
+        "can_undo_redo_palette_builder",
+        { path }
       );
       set((state) => {
         return {
@@ -323,11 +334,14 @@ export const usePaletteBuilderStore = create<PaletteBuilderStore>(
       });
     },
     redoPaletteBuilder: async () => {
+      const { path } = get();
       const paletteBuilder = await invoke<PaletteBuilderPayload>(
-        "redo_palette_builder"
+        "redo_palette_builder",
+        { path }
       );
       const canUndoRedo = await invoke<CanUndoRedo>(
-        "can_undo_redo_palette_builder"
+        "can_undo_redo_palette_builder",
+        { path }
       );
       set((state) => {
         return {
@@ -3,6 +3,8 @@ import { TokenFamily } from "../../domain/DesignSystemDomain";
 import { useFindDesignSystem } from "../design-system/DesignSystemQueries";
 import { getDesignSystemTokens } from "../../util/DesignSystemUtils";
 import TokenCrafterComponent from "./TokenCrafterComponent";
+import { useTokenCrafterStore } from "./TokenCrafterStore";
+import { useEffect } from "react";
 
 function PageTokenCrafter() {
   const [searchParams] = useSearchParams();
@@ -11,6 +13,14 @@ function PageTokenCrafter() {
     currentDesignSystem ?? undefined
   );
   const tokenFamilies: TokenFamily[] = getDesignSystemTokens(designSystem);
+  const { setDesignSystemPath } = useTokenCrafterStore();
+  const designSystemPath = designSystem?.metadata.designSystemPath;
+
+  useEffect(() => {
+    if (designSystemPath) {
+      setDesignSystemPath(designSystemPath);
+    }
+  }, [designSystemPath, setDesignSystemPath]);
 
   if (!designSystem) return null;
   
@@ -32,6 +32,8 @@ interface CustomRowRecommandation {
 }
 
 interface TokenCrafterStore {
+  /** Design system of the token crafter, each one has its undo / redo history */
+  designSystemPath?: string;
   collection: ColorCombinationCollection;
   recommandations: RecommandationRow[];
   dragTools: TokenDragTools;
@@ -40,6 +42,7 @@ interface TokenCrafterStore {
   customRowRecommandation: CustomRowRecommandation;
   applyBorder: boolean;
   canUndoRedo: CanUndoRedo;
+  setDesignSystemPath: (designSystemPath: string) => void;
   setCustomRowRecommandation: (customRowReco: CustomRowRecommandation) => void;
   setCollection: (collection: ColorCombinationCollection) => void;
   applyCollection: () => void;
@@ -65,6 +68,24 @@ export const useTokenCrafterStore = create<TokenCrafterStore>((set, get) => ({
     canUndo: false,
     canRedo: false,
   },
+  setDesignSystemPath: async (designSystemPath) => {
+    set((state) => {
+      return {
+        ...state,
+        designSystemPath,
+      };
+    });
+    const canUndoRedo = await invoke<CanUndoRedo>(
+      "can_undo_redo_token_crafter",
+      { designSystemPath }
+    );
+    set((state) => {
+      return {
+        ...state,
+        canUndoRedo,
+      };
+    });
+  },
   setCollection: async (collection) => {
     set((state) => {
       return {
@@ -72,11 +93,14 @@ export const useTokenCrafterStore = create<TokenCrafterStore>((set, get) => ({
         collection,
       };
     });
+    const { designSystemPath } = get();
     await invoke("do_token_crafter", {
+      designSystemPath,
       tokenCrafter: collection,
     });
     const canUndoRedo = await invoke<CanUndoRedo>(
-      "can_undo_redo_token_crafter"
+      "can_undo_redo_token_crafter",
+      { designSystemPath }
     );
     set((state) => {
       return {
@@ -94,12 +118,14 @@ export const useTokenCrafterStore = create<TokenCrafterStore>((set, get) => ({
         },
       };
     });
-    const { collection } = get();
+    const { collection, designSystemPath } = get();
     await invoke("do_token_crafter", {
+      designSystemPath,
       tokenCrafter: collection,
     });
     const canUndoRedo = await invoke<CanUndoRedo>(
-      "can_undo_redo_token_crafter"
+      "can_undo_redo_token_crafter",
+      { designSystemPath }
     );
     set((state) => {
       return {
@@ -265,11 +291,14 @@ export const useTokenCrafterStore = create<TokenCrafterStore>((set, get) => ({
     });
   },
   undoTokenCrafter: async () => {
+    const { designSystemPath } = get();
     const collection = await invoke<ColorCombinationCollection>(
-      "undo_token_crafter"
+      "undo_token_crafter",
+      { designSystemPath }
     );
     const canUndoRedo = await invoke<CanUndoRedo>(
-      "can_undo_redo_token_crafter"
+      "can_undo_redo_token_crafter",
+      { designSystemPath }
     );
     set((state) => {
       return {
@@ -280,11 +309,14 @@ export const useTokenCrafterStore = create<TokenCrafterStore>((set, get) => ({
     });
   },
   redoTokenCrafter: async () => {
+    const { designSystemPath } = get();
     const collection = await invoke<ColorCombinationCollection>(
-      "redo_token_crafter"
+      "redo_token_crafter",
+      { designSystemPath }
     );
     const canUndoRedo = await invoke<CanUndoRedo>(
-      "can_undo_redo_token_crafter"
+      "can_undo_redo_token_crafter",
+      { designSystemPath }
     );
     set((state) => {
       return {